@@ -0,0 +1,167 @@
+use crate::{
+    expression::{Expr, ExprKind},
+    simplify,
+};
+
+/// Computes the derivative of `u` with respect to `x`.
+/// Parts which cannot be differentiated are returned as an unevaluated `D[u, x]`.
+pub fn derivative(u: &Expr, x: &Expr) -> Expr {
+    if u == x {
+        Expr::int(1)
+    } else if u.is_free_of(x) {
+        Expr::int(0)
+    } else {
+        match u.kind {
+            ExprKind::Sum => simplify(&u.operands.iter().map(|v| derivative(v, x)).sum()),
+            ExprKind::Difference => simplify(&Expr::minus(
+                derivative(&u.operands[0], x),
+                derivative(&u.operands[1], x),
+            )),
+            ExprKind::Product => {
+                // (v*w)' = v'*w + v*w'
+                let v = &u.operands[0];
+                let w = simplify(&Expr::product(u.operands[1..].to_vec()));
+                simplify(&Expr::plus(
+                    Expr::times(derivative(v, x), w.clone()),
+                    Expr::times(v.clone(), derivative(&w, x)),
+                ))
+            }
+            ExprKind::Quotient => derivative(&simplify(u), x),
+            ExprKind::Power => {
+                let v = u.base();
+                let w = u.exponent();
+                if w.is_free_of(x) {
+                    // (v^w)' = w * v^(w-1) * v'
+                    simplify(&Expr::product(vec![
+                        w.clone(),
+                        Expr::power(v.clone(), Expr::minus(w, Expr::int(1))),
+                        derivative(&v, x),
+                    ]))
+                } else {
+                    // (v^w)' = v^w * (w' * Log[v] + w * v'/v)
+                    simplify(&Expr::times(
+                        u.clone(),
+                        Expr::plus(
+                            Expr::times(derivative(&w, x), Expr::function("Log", vec![v.clone()])),
+                            Expr::quotient(Expr::times(w, derivative(&v, x)), v),
+                        ),
+                    ))
+                }
+            }
+            ExprKind::Func(ref name) if u.operands.len() == 1 => {
+                // Chain rule: f(v)' = f'(v) * v'
+                let v = &u.operands[0];
+                match derivative_function(name, v) {
+                    Some(df) => simplify(&Expr::times(df, derivative(v, x))),
+                    None => Expr::function("D", vec![u.clone(), x.clone()]),
+                }
+            }
+            _ => Expr::function("D", vec![u.clone(), x.clone()]),
+        }
+    }
+}
+
+/// Computes the `n`-th derivative of `u` with respect to `x`.
+pub fn derivative_n(u: &Expr, x: &Expr, n: u64) -> Expr {
+    let mut d = u.clone();
+    for _ in 0..n {
+        d = derivative(&d, x);
+    }
+    d
+}
+
+/// Returns the derivative of the elementary function `name` evaluated at `v`.
+fn derivative_function(name: &str, v: &Expr) -> Option<Expr> {
+    let f = |name: &str| Expr::function(name, vec![v.clone()]);
+    let df = match name {
+        "Sin" => f("Cos"),
+        "Cos" => Expr::times(Expr::int(-1), f("Sin")),
+        "Tan" => Expr::power(f("Cos"), Expr::int(-2)),
+        "Cot" => Expr::times(Expr::int(-1), Expr::power(f("Sin"), Expr::int(-2))),
+        "Exp" => f("Exp"),
+        "Log" => Expr::power(v.clone(), Expr::int(-1)),
+        "Sqrt" => Expr::times(Expr::frac(1, 2), Expr::power(v.clone(), Expr::frac(-1, 2))),
+        "ArcSin" => Expr::power(
+            Expr::minus(Expr::int(1), Expr::power(v.clone(), Expr::int(2))),
+            Expr::frac(-1, 2),
+        ),
+        "ArcCos" => Expr::times(
+            Expr::int(-1),
+            Expr::power(
+                Expr::minus(Expr::int(1), Expr::power(v.clone(), Expr::int(2))),
+                Expr::frac(-1, 2),
+            ),
+        ),
+        "ArcTan" => Expr::power(
+            Expr::plus(Expr::int(1), Expr::power(v.clone(), Expr::int(2))),
+            Expr::int(-1),
+        ),
+        "Sinh" => f("Cosh"),
+        "Cosh" => f("Sinh"),
+        "Tanh" => Expr::power(f("Cosh"), Expr::int(-2)),
+        _ => return None,
+    };
+    Some(df)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    use super::{derivative, derivative_n};
+
+    #[test]
+    fn test_derivative() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("x^3 + 2*x^2 + 5", "x", "3*x^2 + 4*x"),
+            ("x*y^2", "y", "2*x*y"),
+            ("1/x", "x", "-1/x^2"),
+            ("Sin[x^2]", "x", "2*x*Cos[x^2]"),
+            ("x^x", "x", "x^x*(1 + Log[x])"),
+            ("Log[x]*Exp[x]", "x", "Exp[x]/x + Exp[x]*Log[x]"),
+            ("a*x + b", "y", "0"),
+        ];
+        for (u, x, expected) in cases {
+            let (u, x) = (kernel.parse_eval_line(u), kernel.parse_eval_line(x));
+            assert_eq!(
+                derivative(&u, &x),
+                kernel.parse_eval_line(expected),
+                "{}",
+                u
+            );
+        }
+
+        let x = Expr::symbol("x");
+        let cases = [
+            ("x^5", 3, "60*x^2"),
+            ("x^5", 0, "x^5"),
+            ("Sin[x]", 2, "-Sin[x]"),
+            ("x^2", 3, "0"),
+        ];
+        for (u, n, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            assert_eq!(
+                derivative_n(&u, &x, n),
+                kernel.parse_eval_line(expected),
+                "{}",
+                u
+            );
+        }
+
+        // Unknown functions are left unevaluated
+        let u = kernel.parse_eval_line("f[x]");
+        assert_eq!(
+            derivative(&u, &x),
+            Expr::function("D", vec![u.clone(), x.clone()])
+        );
+        assert_eq!(
+            kernel.parse_eval_line("D[f[x], x]").to_string(),
+            "D[f[x], x]"
+        );
+        assert_eq!(
+            kernel.parse_eval_line("D[x^5, {x, 3}]"),
+            kernel.parse_eval_line("60*x^2")
+        );
+    }
+}
@@ -0,0 +1,510 @@
+use crate::{
+    expression::{Expr, ExprKind},
+    message::message,
+    polynomial::{
+        count_real_roots, polynomial_division, polynomial_quotient, rational_coefficients,
+        rational_roots, together,
+    },
+    simplify,
+};
+
+use super::{derivative, derivative_n};
+
+/// Maximum nesting of integration methods (substitution, integration by parts, ...) that call
+/// the integrator recursively.
+const MAX_DEPTH: usize = 6;
+
+/// Computes an antiderivative of `u` with respect to `x`.
+/// Returns `None` if none of the integration methods applies.
+pub fn integrate(u: &Expr, x: &Expr) -> Option<Expr> {
+    integrate_depth(u, x, 0)
+}
+
+/// Computes the definite integral of `u` with respect to `x` from `a` to `b`. Returns `None`
+/// if no antiderivative is found, or if `u` has a pole in `[a, b]` and emits `Integrate::idiv`.
+pub fn definite_integral(u: &Expr, x: &Expr, a: &Expr, b: &Expr) -> Option<Expr> {
+    let f = integrate(u, x)?;
+    if has_pole(u, x, a, b) {
+        message(
+            "Integrate",
+            "idiv",
+            &format!("Integral of {} does not converge on {{{}, {}}}.", u, a, b),
+        );
+        return None;
+    }
+    Some(simplify(&Expr::minus(
+        f.substitute(x, b),
+        f.substitute(x, a),
+    )))
+}
+
+/// Checks whether the denominator of `u` has a real zero in the interval between the rational
+/// numbers `a` and `b`, which are counted with a Sturm sequence. Only denominators which are
+/// polynomials in `x` with rational coefficients are checked.
+fn has_pole(u: &Expr, x: &Expr, a: &Expr, b: &Expr) -> bool {
+    if !a.is_rne() || !b.is_rne() {
+        return false;
+    }
+    let Some(coeffs) = rational_coefficients(&together(u).denominator(), x) else {
+        return false;
+    };
+    let (a, b) = (a.rational_rne(), b.rational_rne());
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    count_real_roots(&coeffs, &lo, &hi) > 0
+}
+
+fn integrate_depth(u: &Expr, x: &Expr, depth: usize) -> Option<Expr> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    if u.is_free_of(x) {
+        return Some(simplify(&Expr::times(u.clone(), x.clone())));
+    }
+    if let Some(f) = integrate_table(u, x) {
+        return Some(f);
+    }
+    match u.kind {
+        ExprKind::Sum => {
+            // Linearity: Integrate[v + w] = Integrate[v] + Integrate[w]
+            let terms = u
+                .operands
+                .iter()
+                .map(|v| integrate_depth(v, x, depth))
+                .collect::<Option<Vec<_>>>()?;
+            return Some(simplify(&Expr::sum(terms)));
+        }
+        ExprKind::Product => {
+            // Constant factors: Integrate[c * v] = c * Integrate[v]
+            let (constant, dependent): (Vec<_>, Vec<_>) =
+                u.operands.iter().cloned().partition(|v| v.is_free_of(x));
+            if !constant.is_empty() {
+                let v = simplify(&Expr::product(dependent));
+                let f = integrate_depth(&v, x, depth)?;
+                return Some(simplify(&Expr::times(Expr::product(constant), f)));
+            }
+        }
+        _ => {}
+    }
+    integrate_polynomial(u, x)
+        .or_else(|| integrate_rational(u, x))
+        .or_else(|| integrate_substitution(u, x, depth))
+        .or_else(|| integrate_by_parts(u, x, depth))
+        .or_else(|| {
+            let v = u.algebraic_expand();
+            if v != *u {
+                integrate_depth(&v, x, depth + 1)
+            } else {
+                None
+            }
+        })
+}
+
+/// Writes `v` as `a*x + b` with `a` and `b` free of `x` and `a` non-zero.
+fn linear_form(v: &Expr, x: &Expr) -> Option<(Expr, Expr)> {
+    let v = v.algebraic_expand();
+    if v.is_polynomial_gpe(x) && v.degree_gpe(x) == 1 {
+        Some((v.coefficient_gpe(x, 1), v.coefficient_gpe(x, 0)))
+    } else {
+        None
+    }
+}
+
+/// Integrates the elementary functions and powers of a linear argument `a*x + b`.
+fn integrate_table(u: &Expr, x: &Expr) -> Option<Expr> {
+    let f = match u.kind {
+        ExprKind::Symbol(_) | ExprKind::Power => {
+            let v = u.base();
+            let n = u.exponent();
+            if n.is_free_of(x) {
+                let (a, _) = linear_form(&v, x)?;
+                let f = if n == Expr::int(-1) {
+                    // 1/v -> Log[v]
+                    Expr::function("Log", vec![v])
+                } else {
+                    // v^n -> v^(n+1)/(n+1)
+                    let m = Expr::plus(n, Expr::int(1));
+                    Expr::quotient(Expr::power(v, m.clone()), m)
+                };
+                Expr::quotient(f, a)
+            } else if v.is_free_of(x) {
+                // c^w -> c^w/Log[c]
+                let (a, _) = linear_form(&n, x)?;
+                Expr::quotient(u.clone(), Expr::times(a, Expr::function("Log", vec![v])))
+            } else {
+                return None;
+            }
+        }
+        ExprKind::Func(ref name) if u.operands.len() == 1 => {
+            let v = &u.operands[0];
+            let (a, _) = linear_form(v, x)?;
+            let f = |name: &str| Expr::function(name, vec![v.clone()]);
+            let neg = |u: Expr| Expr::times(Expr::int(-1), u);
+            let g = match name.as_str() {
+                "Exp" => f("Exp"),
+                "Sin" => neg(f("Cos")),
+                "Cos" => f("Sin"),
+                "Tan" => neg(Expr::function("Log", vec![f("Cos")])),
+                "Cot" => Expr::function("Log", vec![f("Sin")]),
+                "Sinh" => f("Cosh"),
+                "Cosh" => f("Sinh"),
+                "Tanh" => Expr::function("Log", vec![f("Cosh")]),
+                // Log[v] -> v*Log[v] - v
+                "Log" => Expr::minus(Expr::times(v.clone(), f("Log")), v.clone()),
+                "Sqrt" => Expr::times(Expr::frac(2, 3), Expr::power(v.clone(), Expr::frac(3, 2))),
+                // ArcTan[v] -> v*ArcTan[v] - Log[1 + v^2]/2
+                "ArcTan" => Expr::minus(
+                    Expr::times(v.clone(), f("ArcTan")),
+                    Expr::times(
+                        Expr::frac(1, 2),
+                        Expr::function(
+                            "Log",
+                            vec![Expr::plus(
+                                Expr::int(1),
+                                Expr::power(v.clone(), Expr::int(2)),
+                            )],
+                        ),
+                    ),
+                ),
+                // ArcSin[v] -> v*ArcSin[v] + Sqrt[1 - v^2]
+                "ArcSin" => Expr::plus(
+                    Expr::times(v.clone(), f("ArcSin")),
+                    Expr::power(
+                        Expr::minus(Expr::int(1), Expr::power(v.clone(), Expr::int(2))),
+                        Expr::frac(1, 2),
+                    ),
+                ),
+                // ArcCos[v] -> v*ArcCos[v] - Sqrt[1 - v^2]
+                "ArcCos" => Expr::minus(
+                    Expr::times(v.clone(), f("ArcCos")),
+                    Expr::power(
+                        Expr::minus(Expr::int(1), Expr::power(v.clone(), Expr::int(2))),
+                        Expr::frac(1, 2),
+                    ),
+                ),
+                _ => return None,
+            };
+            Expr::quotient(g, a)
+        }
+        _ => return None,
+    };
+    Some(simplify(&f))
+}
+
+/// Integrates a polynomial in `x` term by term.
+fn integrate_polynomial(u: &Expr, x: &Expr) -> Option<Expr> {
+    if !u.is_polynomial_gpe(x) {
+        return None;
+    }
+    let terms = (0..=u.degree_gpe(x))
+        .map(|k| {
+            Expr::product(vec![
+                u.coefficient_gpe(x, k),
+                Expr::power(x.clone(), Expr::int(k + 1)),
+                Expr::frac(1, k + 1),
+            ])
+        })
+        .collect();
+    Some(simplify(&Expr::sum(terms)))
+}
+
+/// Integrates a rational function in `x`: the polynomial part is split off by polynomial division
+/// and the remaining proper fraction is integrated by partial fractions.
+fn integrate_rational(u: &Expr, x: &Expr) -> Option<Expr> {
+    let n = u.numerator().algebraic_expand();
+    let d = u.denominator().algebraic_expand();
+    if d.is_free_of(x) || !n.is_polynomial_gpe(x) || !d.is_polynomial_gpe(x) {
+        return None;
+    }
+    let (q, r) = polynomial_division(&n, &d, x);
+    let f = integrate_polynomial(&q, x)?;
+    if r.is_zero() {
+        Some(f)
+    } else {
+        let g = integrate_proper_rational(&r, &d, x)?;
+        Some(simplify(&Expr::plus(f, g)))
+    }
+}
+
+/// Integrates `r/d`, where `r` and `d` are polynomials in `x` with `deg(r) < deg(d)`.
+fn integrate_proper_rational(r: &Expr, d: &Expr, x: &Expr) -> Option<Expr> {
+    let log = |v: Expr| Expr::function("Log", vec![v]);
+
+    if d.degree_gpe(x) == 1 {
+        // r/(a*x + b) -> r/a * Log[a*x + b]
+        let a = d.coefficient_gpe(x, 1);
+        return Some(simplify(&Expr::times(
+            Expr::quotient(r.clone(), a),
+            log(d.clone()),
+        )));
+    }
+
+    // Split off the linear factors (x - c)^m for the rational roots c of d
+    rational_coefficients(r, x)?;
+    let roots = rational_roots(&rational_coefficients(d, x)?);
+    let mut terms = Vec::new();
+    let mut s = r.clone();
+    let mut linear = Expr::int(1);
+    for (c, m) in roots {
+        let c = Expr::from_rational(c);
+        let v = simplify(&Expr::minus(x.clone(), c.clone()));
+        let vm = simplify(&Expr::power(v.clone(), Expr::int(m as i64))).algebraic_expand();
+        let g = simplify(&Expr::quotient(r.clone(), polynomial_quotient(d, &vm, x)));
+        for j in 0..m {
+            // The coefficient of 1/v^k with k = m - j is g^(j)(c)/j!
+            let k = (m - j) as i64;
+            let a = simplify(&Expr::quotient(
+                derivative_n(&g, x, j as u64).substitute(x, &c),
+                Expr::factorial(Expr::int(j as i64)),
+            ));
+            if a.is_zero() {
+                continue;
+            }
+            let vk = simplify(&Expr::power(v.clone(), Expr::int(k))).algebraic_expand();
+            s = simplify(&Expr::minus(
+                s,
+                Expr::times(a.clone(), polynomial_quotient(d, &vk, x)),
+            ))
+            .algebraic_expand();
+            terms.push(if k == 1 {
+                Expr::times(a, log(v.clone()))
+            } else {
+                Expr::product(vec![
+                    a,
+                    Expr::power(v.clone(), Expr::int(1 - k)),
+                    Expr::frac(1, 1 - k),
+                ])
+            });
+        }
+        linear = simplify(&Expr::times(linear, vm)).algebraic_expand();
+    }
+
+    // What remains is s/h, where h has no rational roots
+    let h = polynomial_quotient(d, &linear, x);
+    let s = polynomial_quotient(&s, &linear, x);
+    if !s.is_zero() {
+        match h.degree_gpe(x) {
+            2 => terms.push(integrate_quadratic(&s, &h, x)),
+            _ => return None,
+        }
+    }
+    Some(simplify(&Expr::sum(terms)))
+}
+
+/// Integrates `(p*x + q)/(a*x^2 + b*x + c)`, where the denominator has no rational roots.
+fn integrate_quadratic(s: &Expr, h: &Expr, x: &Expr) -> Expr {
+    let log = |v: Expr| Expr::function("Log", vec![v]);
+    let sqrt = |v: Expr| Expr::power(v, Expr::frac(1, 2));
+
+    let p = s.coefficient_gpe(x, 1);
+    let q = s.coefficient_gpe(x, 0);
+    let a = h.coefficient_gpe(x, 2);
+    let b = h.coefficient_gpe(x, 1);
+    let c = h.coefficient_gpe(x, 0);
+
+    // (p*x + q)/h = p/(2*a) * h'/h + k/h
+    let two_a = Expr::times(Expr::int(2), a.clone());
+    let k = simplify(&Expr::minus(
+        q,
+        Expr::quotient(Expr::times(p.clone(), b.clone()), two_a.clone()),
+    ));
+    let w = Expr::plus(Expr::times(two_a.clone(), x.clone()), b.clone());
+    let disc = simplify(&Expr::minus(
+        Expr::product(vec![Expr::int(4), a, c]),
+        Expr::power(b, Expr::int(2)),
+    ));
+    let f = if disc.is_positive_num() {
+        // 1/h -> 2/Sqrt[disc] * ArcTan[(2*a*x + b)/Sqrt[disc]]
+        Expr::product(vec![
+            Expr::int(2),
+            Expr::power(disc.clone(), Expr::frac(-1, 2)),
+            Expr::function("ArcTan", vec![Expr::quotient(w, sqrt(disc))]),
+        ])
+    } else {
+        // 1/h -> 1/Sqrt[-disc] * Log[(2*a*x + b - Sqrt[-disc])/(2*a*x + b + Sqrt[-disc])]
+        let e = simplify(&sqrt(Expr::times(Expr::int(-1), disc)));
+        Expr::quotient(
+            log(Expr::quotient(
+                Expr::minus(w.clone(), e.clone()),
+                Expr::plus(w, e.clone()),
+            )),
+            e,
+        )
+    };
+    simplify(&Expr::plus(
+        Expr::times(Expr::quotient(p, two_a), log(h.clone())),
+        Expr::times(k, f),
+    ))
+}
+
+/// Collects all functions in `u` and the arguments of all functions and powers in `u`
+/// that depend on `x`.
+fn substitution_candidates(u: &Expr, x: &Expr, candidates: &mut Vec<Expr>) {
+    if let ExprKind::Func(_) | ExprKind::Power = u.kind {
+        for v in u.operands.iter() {
+            if v != x && !v.is_free_of(x) && !candidates.contains(v) {
+                candidates.push(v.clone());
+            }
+        }
+    }
+    for v in u.operands.iter() {
+        if let ExprKind::Func(_) = v.kind {
+            if !candidates.contains(v) {
+                candidates.push(v.clone());
+            }
+        }
+        substitution_candidates(v, x, candidates);
+    }
+}
+
+/// Tries the substitution `v = g(x)` for the arguments `g(x)` of functions and powers in `u`,
+/// which applies when `u/g'(x)` can be written in terms of `v` only.
+fn integrate_substitution(u: &Expr, x: &Expr, depth: usize) -> Option<Expr> {
+    let v = Expr::symbol("$v");
+    let mut candidates = Vec::new();
+    substitution_candidates(u, x, &mut candidates);
+    candidates.iter().find_map(|g| {
+        let dg = derivative(g, x);
+        if dg.is_zero() {
+            return None;
+        }
+        let w = simplify(&Expr::quotient(u.clone(), dg)).substitute(g, &v);
+        if w.is_free_of(x) {
+            let f = integrate_depth(&w, &v, depth + 1)?;
+            Some(f.substitute(&v, g))
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns the priority of `u` as the factor to be differentiated in integration by parts,
+/// where a lower number is tried first.
+fn parts_priority(u: &Expr, x: &Expr) -> Option<usize> {
+    match u.kind {
+        ExprKind::Func(ref name)
+            if matches!(name.as_str(), "Log" | "ArcTan" | "ArcSin" | "ArcCos") =>
+        {
+            Some(0)
+        }
+        _ if u.is_polynomial_gpe(x) => Some(1),
+        _ => None,
+    }
+}
+
+/// Integration by parts: Integrate[w * v'] = w * v - Integrate[w' * v].
+fn integrate_by_parts(u: &Expr, x: &Expr, depth: usize) -> Option<Expr> {
+    let ExprKind::Product = u.kind else {
+        return None;
+    };
+    let mut factors: Vec<(usize, usize)> = u
+        .operands
+        .iter()
+        .enumerate()
+        .filter_map(|(i, w)| parts_priority(w, x).map(|p| (p, i)))
+        .collect();
+    factors.sort();
+    factors.iter().find_map(|&(_, i)| {
+        let w = &u.operands[i];
+        let mut rest = u.operands.clone();
+        rest.remove(i);
+        let v = integrate_depth(&simplify(&Expr::product(rest)), x, depth + 1)?;
+        let dw = derivative(w, x);
+        let g = integrate_depth(&simplify(&Expr::times(dw, v.clone())), x, depth + 1)?;
+        Some(simplify(&Expr::minus(Expr::times(w.clone(), v), g)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        calculus::derivative, expression::Expr, parser::AlgomyKernel, polynomial::together,
+    };
+
+    use super::{definite_integral, integrate};
+
+    #[test]
+    fn test_integrate() {
+        let mut kernel = AlgomyKernel::new();
+        let x = Expr::symbol("x");
+        let cases = [
+            ("3*x^2 + 4*x + 5", Some("x^3 + 2*x^2 + 5*x")),
+            ("a*x^2", Some("a*x^3/3")),
+            ("(2*x + 1)^3", Some("(2*x + 1)^4/8")),
+            ("1/x", Some("Log[x]")),
+            ("Cos[3*x]", Some("Sin[3*x]/3")),
+            ("x*Exp[x^2]", Some("Exp[x^2]/2")),
+            ("Sin[x]*Cos[x]", Some("-1/2*Cos[x]^2")),
+            ("x*Exp[x]", Some("x*Exp[x] - Exp[x]")),
+            ("Log[x]", Some("x*Log[x] - x")),
+            ("y", Some("x*y")),
+            ("f[x]", None),
+            ("Exp[x^2]", None),
+        ];
+        for (u, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            assert_eq!(
+                integrate(&u, &x),
+                expected.map(|e| kernel.parse_eval_line(e)),
+                "{}",
+                u
+            );
+        }
+        assert_eq!(
+            kernel.parse_eval_line("Integrate[f[x], x]").to_string(),
+            "Integrate[f[x], x]"
+        );
+    }
+
+    #[test]
+    fn test_integrate_rational() {
+        let mut kernel = AlgomyKernel::new();
+        let x = Expr::symbol("x");
+        let cases = [
+            ("1/(x^2 - 1)", "Log[x - 1]/2 - Log[x + 1]/2"),
+            ("1/(x^2 + 1)", "ArcTan[x]"),
+            ("x/(x^2 + 1)", "Log[x^2 + 1]/2"),
+            ("(x^2 + 1)/(x + 1)", "x^2/2 - x + 2*Log[x + 1]"),
+            ("1/(x^3 - x^2)", "1/x - Log[x] + Log[x - 1]"),
+        ];
+        for (u, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            let result = integrate(&u, &x).unwrap();
+            assert_eq!(result, kernel.parse_eval_line(expected), "{}", u);
+            // The derivative of the antiderivative is u
+            let du = derivative(&result, &x);
+            assert_eq!(together(&du), together(&u), "{}", u);
+        }
+    }
+
+    #[test]
+    fn test_definite_integral() {
+        let mut kernel = AlgomyKernel::new();
+        let x = Expr::symbol("x");
+        let cases = [
+            ("x^2", "0", "3", Some("9")),
+            ("x^3 + 1", "1", "2", Some("19/4")),
+            ("1/x^2", "1", "2", Some("1/2")),
+            ("1/(x^2 + 1)", "-1", "1", Some("Pi/2")),
+            // Poles inside the interval or at its ends
+            ("1/x^2", "-1", "1", None),
+            ("1/x", "0", "1", None),
+            ("1/(x^2 - 2)", "0", "2", None),
+            ("1/(x - 1)^2", "2", "0", None),
+            ("1/(x^2 - 2)", "-2", "2", None),
+        ];
+        for (u, a, b, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            let (a, b) = (kernel.parse_eval_line(a), kernel.parse_eval_line(b));
+            assert_eq!(
+                definite_integral(&u, &x, &a, &b),
+                expected.map(|e| kernel.parse_eval_line(e)),
+                "{}",
+                u
+            );
+        }
+        // The irrational poles of 1/(x^2 - 2) lie outside [-1, 1]
+        let u = kernel.parse_eval_line("1/(x^2 - 2)");
+        let (a, b) = (Expr::int(-1), Expr::int(1));
+        assert!(definite_integral(&u, &x, &a, &b).is_some());
+    }
+}
@@ -0,0 +1,5 @@
+mod derivative;
+mod integrate;
+
+pub use derivative::*;
+pub use integrate::*;
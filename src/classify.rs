@@ -3,10 +3,38 @@ use crate::expression::{Expr, ExprKind};
 impl Expr {
     /// Checks if `self` is an integer.
     pub fn is_integer(&self) -> bool {
-        match self.kind {
-            ExprKind::Integer(_) => true,
-            _ => false,
-        }
+        matches!(self.kind, ExprKind::Integer(_))
+    }
+
+    /// Checks if `self` is an integer, fraction or real.
+    pub fn is_real_number(&self) -> bool {
+        matches!(
+            self.kind,
+            ExprKind::Integer(_) | ExprKind::Fraction(_, _) | ExprKind::Real(_)
+        )
+    }
+
+    /// Checks if `self` is an equation or inequality.
+    pub fn is_relation(&self) -> bool {
+        matches!(
+            self.kind,
+            ExprKind::Equal
+                | ExprKind::Unequal
+                | ExprKind::Less
+                | ExprKind::LessEqual
+                | ExprKind::Greater
+                | ExprKind::GreaterEqual
+        )
+    }
+
+    /// Checks if `self` is a rule `a -> b` or a delayed rule `a :> b`.
+    pub fn is_rule(&self) -> bool {
+        matches!(self.kind, ExprKind::Rule | ExprKind::RuleDelayed)
+    }
+
+    /// Checks if `self` is one of the symbolic constants `Pi` and `E`.
+    pub fn is_constant(&self) -> bool {
+        matches!(self.kind, ExprKind::Symbol(ref s) if s == "Pi" || s == "E")
     }
 
     /// Checks if `self` is a rational number expression (RNE).
@@ -0,0 +1,98 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{error::Error, message::Message, parser::Definition};
+
+/// The state of an evaluation, which is owned by a kernel: the user-defined functions in scope,
+/// the emitted messages, the first raised error and the nesting of user-defined functions.
+#[derive(Default)]
+pub struct Context {
+    pub definitions: Rc<Vec<Definition>>,
+    pub messages: Vec<Message>,
+    pub error: Option<Error>,
+    pub depth: usize,
+    pub aborted: bool,
+}
+
+thread_local! {
+    /// The context of the evaluation running on this thread, if any.
+    static CURRENT: RefCell<Option<Context>> = const { RefCell::new(None) };
+}
+
+/// Hands the entered context back to its owner and reinstates the enclosing one, also when the
+/// evaluation panics.
+struct Guard<'a> {
+    context: &'a mut Context,
+    enclosing: Option<Context>,
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        let current = CURRENT.replace(self.enclosing.take());
+        *self.context = current.expect("the entered context is current");
+    }
+}
+
+impl Context {
+    /// Evaluates `f` with `self` as the current context, which builtin functions reach through
+    /// [`with_context`].
+    pub fn enter<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        let enclosing = CURRENT.replace(Some(std::mem::take(self)));
+        let _guard = Guard {
+            context: self,
+            enclosing,
+        };
+        f()
+    }
+}
+
+/// Applies `f` to the current context. Returns `None` outside of an evaluation. The context is
+/// borrowed while `f` runs, so `f` must not evaluate expressions.
+pub fn with_context<T>(f: impl FnOnce(&mut Context) -> T) -> Option<T> {
+    CURRENT.with_borrow_mut(|context| context.as_mut().map(f))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{message::message, parser::AlgomyKernel};
+
+    use super::{with_context, Context};
+
+    #[test]
+    fn test_context() {
+        let (mut a, mut b) = (Context::default(), Context::default());
+        a.enter(|| {
+            message("A", "tag", "In a.");
+            b.enter(|| message("B", "tag", "In b."));
+            message("A", "tag", "Again in a.");
+        });
+        let texts = |c: &Context| c.messages.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+        assert_eq!(texts(&a), ["A::tag: In a.", "A::tag: Again in a."]);
+        assert_eq!(texts(&b), ["B::tag: In b."]);
+
+        // Outside of an evaluation there is no context
+        message("C", "tag", "Dropped.");
+        assert_eq!(with_context(|c| c.messages.len()), None);
+
+        // A panicking evaluation leaves no context behind
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            a.enter(|| {
+                message("A", "panic", "Before the panic.");
+                panic!("evaluation failed")
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(with_context(|_| ()), None);
+        assert_eq!(a.messages.len(), 3);
+
+        // Kernels do not share their definitions or messages
+        let mut first = AlgomyKernel::new();
+        let mut second = AlgomyKernel::new();
+        let line = first.parse_line("f[x_] := x + 1").unwrap();
+        assert!(first.evaluate_line(line).unwrap().is_none());
+        assert_eq!(first.parse_eval_line("f[1]").to_string(), "2");
+        assert_eq!(second.parse_eval_line("f[1]").to_string(), "f[1]");
+        first.parse_eval_line("1/0");
+        assert!(second.take_messages().is_empty());
+        assert_eq!(first.take_messages().len(), 1);
+    }
+}
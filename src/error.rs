@@ -0,0 +1,298 @@
+use std::fmt::Display;
+
+use crate::context::with_context;
+
+use crate::parser::Rule;
+
+/// A range of byte offsets in the source of a line or program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The input does not match the grammar.
+    Syntax(String),
+    /// A number literal which cannot be represented.
+    InvalidNumber(String),
+    /// A builtin function called with `given` arguments, while it accepts `min..=max`. Tagged
+    /// `f::argn` like messages.
+    Arity {
+        function: String,
+        min: usize,
+        max: usize,
+        given: usize,
+    },
+    /// A builtin function called with an argument of the wrong type. Tagged `f::arg`.
+    Type { function: String, message: String },
+}
+
+/// An error raised while parsing or evaluating input. Syntax errors carry the span of the
+/// offending source. Expressions do not keep their source positions, so errors raised during
+/// evaluation have no span until they are given the span of their line with [`Error::or_span`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub span: Option<Span>,
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind, span: Option<Span>) -> Self {
+        Self { kind, span }
+    }
+
+    pub fn arity(function: &str, min: usize, max: usize, given: usize) -> Self {
+        Self::new(
+            ErrorKind::Arity {
+                function: function.to_owned(),
+                min,
+                max,
+                given,
+            },
+            None,
+        )
+    }
+
+    pub fn type_error(function: &str, message: &str) -> Self {
+        Self::new(
+            ErrorKind::Type {
+                function: function.to_owned(),
+                message: message.to_owned(),
+            },
+            None,
+        )
+    }
+
+    /// Returns the error with the span `span`, unless it already has a more precise one.
+    pub fn or_span(self, span: Span) -> Self {
+        Self {
+            span: self.span.or(Some(span)),
+            ..self
+        }
+    }
+
+    /// Renders the error together with the line of `source` it occurred in, with a caret under
+    /// the offending column.
+    pub fn render(&self, source: &str) -> String {
+        let Some(span) = self.span else {
+            return self.to_string();
+        };
+        let start = span.start.min(source.len());
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |i| start + i);
+        let line = source[line_start..line_end].trim_end_matches('\r');
+        let row = source[..start].matches('\n').count() + 1;
+        let column = source[line_start..start].chars().count();
+        let width = source[start..span.end.clamp(start, line_end)]
+            .chars()
+            .count()
+            .max(1);
+        format!(
+            "{} (line {}, column {})\n{}\n{}{}",
+            self,
+            row,
+            column + 1,
+            line,
+            " ".repeat(column),
+            "^".repeat(width)
+        )
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            ErrorKind::Syntax(ref message) => write!(f, "Syntax error: {}", message),
+            ErrorKind::InvalidNumber(ref s) => write!(f, "Invalid number: {}", s),
+            ErrorKind::Arity {
+                ref function,
+                min,
+                max,
+                given,
+            } => {
+                let expected = match (min, max) {
+                    (min, max) if min == max => format!("{}", min),
+                    (min, usize::MAX) => format!("at least {}", min),
+                    (min, max) if max == min + 1 => format!("{} or {}", min, max),
+                    (min, max) => format!("{} to {}", min, max),
+                };
+                let plural = if expected == "1" { "" } else { "s" };
+                write!(
+                    f,
+                    "{}::argn: {} called with {} argument{}; {} argument{} expected",
+                    function,
+                    function,
+                    given,
+                    if given == 1 { "" } else { "s" },
+                    expected,
+                    plural
+                )
+            }
+            ErrorKind::Type {
+                ref function,
+                ref message,
+            } => write!(f, "{}::arg: {}", function, message),
+        }
+    }
+}
+
+impl From<pest::error::Error<Rule>> for Error {
+    fn from(err: pest::error::Error<Rule>) -> Self {
+        let span = match err.location {
+            pest::error::InputLocation::Pos(p) => Span::new(p, p + 1),
+            pest::error::InputLocation::Span((start, end)) => Span::new(start, end),
+        };
+        let err = err.renamed_rules(rule_name);
+        Error::new(
+            ErrorKind::Syntax(err.variant.message().into_owned()),
+            Some(span),
+        )
+    }
+}
+
+/// Describes a grammar rule in a syntax error, by its operator symbol for operators.
+fn rule_name(r: &Rule) -> String {
+    let name = match r {
+        Rule::replace_repeated => "\"//.\"",
+        Rule::replace_all => "\"/.\"",
+        Rule::condition => "\"/;\"",
+        Rule::rule => "\"->\"",
+        Rule::rule_delayed => "\":>\"",
+        Rule::add => "\"+\"",
+        Rule::sub | Rule::neg => "\"-\"",
+        Rule::mul => "\"*\"",
+        Rule::div => "\"/\"",
+        Rule::pow => "\"^\"",
+        Rule::and => "\"&&\"",
+        Rule::or => "\"||\"",
+        Rule::setdiff => "\"\\\"",
+        Rule::eq => "\"==\"",
+        Rule::neq => "\"!=\"",
+        Rule::le => "\"<=\"",
+        Rule::ge => "\">=\"",
+        Rule::lt => "\"<\"",
+        Rule::gt => "\">\"",
+        Rule::not | Rule::fac => "\"!\"",
+        Rule::part => "\"[[\"",
+        Rule::assign => "\"=\"",
+        Rule::delayed_assign => "\":=\"",
+        Rule::num | Rule::int => "number",
+        Rule::blank => "\"_\"",
+        Rule::blank_sequence => "\"__\"",
+        Rule::func_name => "function",
+        Rule::expr => "expression",
+        Rule::EOI => "end of input",
+        _ => return format!("{:?}", r),
+    };
+    name.to_owned()
+}
+
+/// Raises an error in the current context, which the kernel returns once the evaluation is
+/// done. Only the first error is kept.
+pub fn raise(error: Error) {
+    with_context(|context| {
+        context.error.get_or_insert(error);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorKind, Span};
+    use crate::parser::AlgomyKernel;
+
+    #[test]
+    fn test_errors() {
+        let mut kernel = AlgomyKernel::new();
+        let err = kernel.parse_line("1 + * 2").err().unwrap();
+        assert_eq!(err.span, Some(Span::new(4, 5)));
+        assert!(err.render("1 + * 2").ends_with("\n1 + * 2\n    ^"));
+        // Grammar rules are described by their operator symbols
+        assert_eq!(
+            err.to_string(),
+            "Syntax error: expected symbol, number, \"-\", \"!\", function, list, or pattern"
+        );
+        let err = kernel.parse_line("1 2").err().unwrap();
+        assert!(err
+            .to_string()
+            .starts_with("Syntax error: expected end of input, \"//.\", \"/.\", \"/;\", \"->\""));
+
+        let err = kernel.parse_program("x = 1\n2 +\n").err().unwrap();
+        assert!(err.render("x = 1\n2 +\n").contains("(line 2, column 4)"));
+
+        // Evaluation errors in a program point at their line
+        let source = "x = 1\ny = Sin[x, 2]\n";
+        let (line, line_span) = kernel.parse_program(source).unwrap().remove(1);
+        assert_eq!(line_span, Span::new(6, 19));
+        let err = kernel.evaluate_line(line).err().unwrap();
+        assert_eq!(err.span, None);
+        assert_eq!(
+            err.or_span(line_span).render(source),
+            "Sin::argn: Sin called with 2 arguments; 1 argument expected (line 2, column 1)\n\
+             y = Sin[x, 2]\n\
+             ^^^^^^^^^^^^^"
+        );
+
+        let err = kernel.parse_line("1e99999999999999999999").err().unwrap();
+        assert!(matches!(err.kind, ErrorKind::InvalidNumber(_)));
+
+        let cases = [
+            (
+                "Coefficient[x]",
+                "Coefficient::argn: Coefficient called with 1 argument; 3 arguments expected",
+            ),
+            (
+                "Sin[x, y]",
+                "Sin::argn: Sin called with 2 arguments; 1 argument expected",
+            ),
+            (
+                "Range[]",
+                "Range::argn: Range called with 0 arguments; 1 to 3 arguments expected",
+            ),
+            (
+                "Dot[{1}]",
+                "Dot::argn: Dot called with 1 argument; at least 2 arguments expected",
+            ),
+            (
+                "1 + Factor[]",
+                "Factor::argn: Factor called with 0 arguments; 1 or 2 arguments expected",
+            ),
+            (
+                "Coefficient[x^2, x, 1/2]",
+                "Coefficient::arg: the exponent must be an integer",
+            ),
+            (
+                "GroebnerBasis[{x - y}, {x, y}, Foo]",
+                "GroebnerBasis::arg: Foo is not a monomial order; expected Lex, GrLex or GrevLex",
+            ),
+            (
+                "MonomialList[x + y, {x, y}, MonomialOrder -> Foo]",
+                "MonomialList::arg: MonomialOrder -> Foo is not a monomial order; expected Lex, GrLex or GrevLex",
+            ),
+            (
+                "IdentityMatrix[0]",
+                "IdentityMatrix::arg: the dimension must be a positive integer",
+            ),
+        ];
+        for (input, expected) in cases {
+            let line = kernel.parse_line(input).unwrap();
+            let err = kernel.evaluate_line(line).err();
+            assert_eq!(
+                err.map(|err| err.to_string()),
+                Some(expected.to_owned()),
+                "{}",
+                input
+            );
+        }
+        assert!(kernel.parse_line("").is_ok());
+    }
+}
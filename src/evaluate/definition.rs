@@ -0,0 +1,139 @@
+use crate::{
+    context::with_context,
+    expression::Expr,
+    message::message,
+    parser::Definition,
+    pattern::{apply_bindings, match_pattern, pattern_variables},
+};
+
+/// Maximum number of nested applications of user-defined functions, like `$RecursionLimit` in
+/// Mathematica. Deeper calls are left unevaluated and emit `$RecursionLimit::reclim`, which
+/// stops runaway recursion such as `f[n_] := f[n + 1]`.
+pub const RECURSION_LIMIT: usize = 1024;
+
+/// Applies the first user definition in the current context whose left-hand side matches `expr`,
+/// trying definitions without patterns first. Returns `None` if no definition matches. If the recursion limit is
+/// reached, the whole outermost application is abandoned and left unevaluated.
+pub fn apply_definitions(expr: &Expr) -> Option<Expr> {
+    let (definitions, depth) = with_context(|context| {
+        (!context.aborted).then(|| (context.definitions.clone(), context.depth))
+    })??;
+    let (literal, general): (Vec<&Definition>, Vec<&Definition>) = definitions
+        .iter()
+        .partition(|d| pattern_variables(&d.lhs).is_empty());
+    let (rhs, bindings) = literal
+        .into_iter()
+        .chain(general)
+        .find_map(|d| Some((&d.rhs, match_pattern(&d.lhs, expr)?)))?;
+    if depth >= RECURSION_LIMIT {
+        message(
+            "$RecursionLimit",
+            "reclim",
+            &format!("Recursion depth of {} exceeded.", RECURSION_LIMIT),
+        );
+        with_context(|context| context.aborted = true);
+        return None;
+    }
+    with_context(|context| context.depth += 1);
+    let result = apply_bindings(rhs, &bindings);
+    let aborted = with_context(|context| {
+        context.depth -= 1;
+        context.depth == 0 && std::mem::take(&mut context.aborted)
+    })?;
+    if aborted {
+        return None;
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    #[test]
+    fn test_definitions() {
+        // Recursing up to the limit needs more stack than the test threads have
+        std::thread::Builder::new()
+            .stack_size(64 << 20)
+            .spawn(definitions)
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    fn definitions() {
+        let mut kernel = AlgomyKernel::new();
+        let definitions = [
+            "f[x_] := x^2 + 1",
+            "f[x_, y_] := x*y",
+            "g[x_, x_] := 0",
+            "fib[0] = 0",
+            "fib[1] = 1",
+            "fib[n_] := fib[n - 1] + fib[n - 2]",
+            "fact[0] = 1",
+            "fact[n_] := n*fact[n - 1]",
+            "a = 3",
+            "h[a_] := a + 1",
+            "k[x_] := a*x",
+            "swap[x_, y_] := {y, x}",
+            "loop[n_] := loop[n + 1]",
+            "double[x_Integer] := 2*x",
+            "pos[x_ /; x > 0] := True",
+        ];
+        for definition in definitions {
+            let line = kernel.parse_line(definition).unwrap();
+            assert!(
+                kernel.evaluate_line(line).unwrap().is_none(),
+                "{}",
+                definition
+            );
+        }
+        let cases = [
+            ("f[2]", Expr::int(5)),
+            ("f[2, 3]", Expr::int(6)),
+            ("g[x, x]", Expr::int(0)),
+            ("fib[10]", Expr::int(55)),
+            ("h[5]", Expr::int(6)),
+            ("k[2]", Expr::int(6)),
+            ("double[3]", Expr::int(6)),
+            ("pos[2]", Expr::bool(true)),
+            (
+                "swap[x, y]",
+                Expr::list(vec![Expr::symbol("y"), Expr::symbol("x")]),
+            ),
+            (
+                "Map[f, {1, 2}]",
+                Expr::list(vec![Expr::int(2), Expr::int(5)]),
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(kernel.parse_eval_line(input), expected, "{}", input);
+        }
+        assert_eq!(
+            kernel.parse_eval_line("f[y]"),
+            kernel.parse_eval_line("y^2 + 1")
+        );
+        assert_eq!(
+            kernel.parse_eval_line("fact[70]"),
+            Expr::int((1..=70).map(num::BigInt::from).product::<num::BigInt>())
+        );
+        assert!(kernel.take_messages().is_empty());
+
+        // Calls which no definition matches are left unevaluated
+        for input in ["f[1, 2, 3]", "g[x, y]", "double[y]", "pos[-2]"] {
+            assert_eq!(kernel.parse_eval_line(input).to_string(), input);
+        }
+        assert!(kernel.take_messages().is_empty());
+
+        // Runaway recursion stops at the recursion limit
+        for input in ["loop[0]", "loop[x]"] {
+            assert_eq!(kernel.parse_eval_line(input).to_string(), input);
+            let messages = kernel.take_messages();
+            assert_eq!(messages.len(), 1);
+            assert_eq!(
+                messages[0].to_string(),
+                "$RecursionLimit::reclim: Recursion depth of 1024 exceeded."
+            );
+        }
+    }
+}
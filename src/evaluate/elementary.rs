@@ -0,0 +1,384 @@
+use num::{BigInt, BigRational, One, Signed, Zero};
+
+use crate::{
+    expression::{Expr, ExprKind, Real},
+    message::infinite_expression,
+    simplify,
+};
+
+/// Names of the elementary functions with built-in simplification rules.
+pub const ELEMENTARY_FUNCTIONS: [&str; 14] = [
+    "Sin", "Cos", "Tan", "Cot", "Exp", "Log", "Sqrt", "ArcSin", "ArcCos", "ArcTan", "Sinh", "Cosh",
+    "Tanh", "Abs",
+];
+
+/// Evaluates the elementary function `name` at `u`. Returns the unevaluated function
+/// if no rule applies.
+pub fn evaluate_elementary(name: &str, u: &Expr) -> Expr {
+    let f = Expr::function(name, vec![u.clone()]);
+    if let ExprKind::Real(ref r) = u.kind {
+        return evaluate_elementary_real(name, r)
+            .map(Expr::real)
+            .unwrap_or(f);
+    }
+
+    // Parity: f(-x) = -f(x) for odd and f(-x) = f(x) for even functions
+    if is_negative_term(u) {
+        let v = simplify(&Expr::times(Expr::int(-1), u.clone()));
+        match name {
+            "Sin" | "Tan" | "Cot" | "ArcSin" | "ArcTan" | "Sinh" | "Tanh" => {
+                return simplify(&Expr::times(Expr::int(-1), evaluate_elementary(name, &v)))
+            }
+            "Cos" | "Cosh" | "Abs" => return evaluate_elementary(name, &v),
+            _ => {}
+        }
+    }
+
+    let value = match name {
+        "Sin" => evaluate_sin(u),
+        "Cos" => evaluate_cos(u),
+        "Tan" => evaluate_tan(u, false),
+        "Cot" => evaluate_tan(u, true),
+        "Exp" => evaluate_exp(u),
+        "Log" => evaluate_log(u),
+        "Sqrt" => Some(simplify(&Expr::power(u.clone(), Expr::frac(1, 2)))),
+        "ArcSin" => evaluate_arcsin(u),
+        // ArcCos[x] = Pi/2 - ArcSin[x]
+        "ArcCos" => evaluate_arcsin(u)
+            .map(|v| simplify(&Expr::minus(Expr::times(Expr::frac(1, 2), Expr::pi()), v))),
+        "ArcTan" => evaluate_arctan(u),
+        "Sinh" | "Tanh" if u.is_zero() => Some(Expr::int(0)),
+        "Cosh" if u.is_zero() => Some(Expr::int(1)),
+        "Abs" => evaluate_abs(u),
+        _ => None,
+    };
+    value.unwrap_or(f)
+}
+
+/// Numerically evaluates the elementary function `name` at the real `r`.
+/// Returns `None` if the result is not a real number.
+fn evaluate_elementary_real(name: &str, r: &Real) -> Option<Real> {
+    match name {
+        "Sin" => Some(r.sin()),
+        "Cos" => Some(r.cos()),
+        "Tan" => r.tan(),
+        "Cot" => Real::new(BigInt::one(), 0, r.precision()).div(&r.tan()?),
+        "Exp" => Some(r.exp()),
+        "Log" => r.ln(),
+        "Sqrt" => r.root(2),
+        "ArcSin" => r.asin(),
+        "ArcCos" => r.acos(),
+        "ArcTan" => Some(r.atan()),
+        "Sinh" => Some(r.sinh()),
+        "Cosh" => Some(r.cosh()),
+        "Tanh" => Some(r.tanh()),
+        "Abs" if r.is_negative() => Some(r.neg()),
+        "Abs" => Some(r.clone()),
+        _ => None,
+    }
+}
+
+/// Checks if `u` is a negative number or a product with a negative numerical coefficient.
+fn is_negative_term(u: &Expr) -> bool {
+    match u.kind {
+        ExprKind::Integer(_) | ExprKind::Fraction(_, _) | ExprKind::Real(_) => {
+            !u.is_zero() && !u.is_positive_num()
+        }
+        ExprKind::Product => is_negative_term(&u.operands[0]),
+        _ => false,
+    }
+}
+
+/// Writes `u` as `r*Pi` for a rational number `r`.
+fn pi_coefficient(u: &Expr) -> Option<BigRational> {
+    if u.is_zero() {
+        Some(BigRational::zero())
+    } else if *u == Expr::pi() {
+        Some(BigRational::one())
+    } else {
+        match &u.operands[..] {
+            [c, p] if u.kind == ExprKind::Product && c.is_rne() && *p == Expr::pi() => {
+                Some(c.rational_rne())
+            }
+            _ => None,
+        }
+    }
+}
+
+fn rat(n: i64, d: i64) -> BigRational {
+    BigRational::new(BigInt::from(n), BigInt::from(d))
+}
+
+/// Returns `Cos[r*Pi]` when `r` is a multiple of 1/4 or 1/6.
+fn cos_pi(r: &BigRational) -> Option<Expr> {
+    // Reduce to 0 <= r <= 1/2 using the period and symmetries of the cosine
+    let two = rat(2, 1);
+    let mut r = r - (r / &two).floor() * &two;
+    if r > BigRational::one() {
+        r = &two - r;
+    }
+    let mut sign = 1;
+    if r > rat(1, 2) {
+        r = BigRational::one() - r;
+        sign = -1;
+    }
+    let value = if r.is_zero() {
+        Expr::int(1)
+    } else if r == rat(1, 6) {
+        Expr::times(
+            Expr::frac(1, 2),
+            Expr::power(Expr::int(3), Expr::frac(1, 2)),
+        )
+    } else if r == rat(1, 4) {
+        Expr::times(
+            Expr::frac(1, 2),
+            Expr::power(Expr::int(2), Expr::frac(1, 2)),
+        )
+    } else if r == rat(1, 3) {
+        Expr::frac(1, 2)
+    } else if r == rat(1, 2) {
+        Expr::int(0)
+    } else {
+        return None;
+    };
+    Some(simplify(&Expr::times(Expr::int(sign), value)))
+}
+
+fn evaluate_sin(u: &Expr) -> Option<Expr> {
+    match u.kind {
+        // Sin[ArcSin[x]] = x
+        ExprKind::Func(ref name) if name == "ArcSin" && u.operands.len() == 1 => {
+            Some(u.operands[0].clone())
+        }
+        // Sin[r*Pi] = Cos[(1/2 - r)*Pi]
+        _ => cos_pi(&(rat(1, 2) - pi_coefficient(u)?)),
+    }
+}
+
+fn evaluate_cos(u: &Expr) -> Option<Expr> {
+    match u.kind {
+        // Cos[ArcCos[x]] = x
+        ExprKind::Func(ref name) if name == "ArcCos" && u.operands.len() == 1 => {
+            Some(u.operands[0].clone())
+        }
+        _ => cos_pi(&pi_coefficient(u)?),
+    }
+}
+
+/// Evaluates `Tan[u]`, or `Cot[u]` if `reciprocal` is set. Poles emit `Power::infy`.
+fn evaluate_tan(u: &Expr, reciprocal: bool) -> Option<Expr> {
+    match u.kind {
+        // Tan[ArcTan[x]] = x and Cot[ArcTan[x]] = 1/x
+        ExprKind::Func(ref name) if name == "ArcTan" && u.operands.len() == 1 => match reciprocal {
+            true => Some(simplify(&Expr::power(u.operands[0].clone(), Expr::int(-1)))),
+            false => Some(u.operands[0].clone()),
+        },
+        _ => {
+            let r = pi_coefficient(u)?;
+            let sin = cos_pi(&(rat(1, 2) - &r))?;
+            let cos = cos_pi(&r)?;
+            let (num, den) = if reciprocal { (cos, sin) } else { (sin, cos) };
+            if den.is_zero() {
+                infinite_expression();
+                Some(Expr::undefined())
+            } else {
+                Some(simplify(&Expr::quotient(num, den)))
+            }
+        }
+    }
+}
+
+fn evaluate_exp(u: &Expr) -> Option<Expr> {
+    match u.kind {
+        _ if u.is_zero() => Some(Expr::int(1)),
+        // Exp[Log[x]] = x
+        ExprKind::Func(ref name) if name == "Log" && u.operands.len() == 1 => {
+            Some(u.operands[0].clone())
+        }
+        _ => None,
+    }
+}
+
+fn evaluate_log(u: &Expr) -> Option<Expr> {
+    if u.is_one() {
+        Some(Expr::int(0))
+    } else if *u == Expr::e() {
+        Some(Expr::int(1))
+    } else if u.kind == ExprKind::Power && u.base() == Expr::e() && u.exponent().is_rne() {
+        // Log[E^r] = r for rational r
+        Some(u.exponent())
+    } else {
+        match u.kind {
+            // Log[Exp[r]] = r for rational r
+            ExprKind::Func(ref name) if name == "Exp" && u.operands.len() == 1 => {
+                u.operands[0].is_rne().then(|| u.operands[0].clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+fn evaluate_arcsin(u: &Expr) -> Option<Expr> {
+    // Values of ArcSin at 0, 1/2, 1/Sqrt[2], Sqrt[3]/2 and 1, as multiples of Pi
+    let r = if u.is_zero() {
+        BigRational::zero()
+    } else if *u == Expr::frac(1, 2) {
+        rat(1, 6)
+    } else if *u == simplify(&Expr::power(Expr::int(2), Expr::frac(-1, 2))) {
+        rat(1, 4)
+    } else if *u
+        == simplify(&Expr::times(
+            Expr::frac(1, 2),
+            Expr::power(Expr::int(3), Expr::frac(1, 2)),
+        ))
+    {
+        rat(1, 3)
+    } else if u.is_one() {
+        rat(1, 2)
+    } else if is_negative_term(u) {
+        // ArcSin[-x] = -ArcSin[x]
+        let v = evaluate_arcsin(&simplify(&Expr::times(Expr::int(-1), u.clone())))?;
+        return Some(simplify(&Expr::times(Expr::int(-1), v)));
+    } else {
+        return None;
+    };
+    Some(simplify(&Expr::times(Expr::from_rational(r), Expr::pi())))
+}
+
+fn evaluate_arctan(u: &Expr) -> Option<Expr> {
+    // Values of ArcTan at 0, 1/Sqrt[3], 1 and Sqrt[3], as multiples of Pi
+    let r = if u.is_zero() {
+        BigRational::zero()
+    } else if *u == simplify(&Expr::power(Expr::int(3), Expr::frac(-1, 2))) {
+        rat(1, 6)
+    } else if u.is_one() {
+        rat(1, 4)
+    } else if *u == simplify(&Expr::power(Expr::int(3), Expr::frac(1, 2))) {
+        rat(1, 3)
+    } else {
+        return None;
+    };
+    Some(simplify(&Expr::times(Expr::from_rational(r), Expr::pi())))
+}
+
+fn evaluate_abs(u: &Expr) -> Option<Expr> {
+    match u.kind {
+        ExprKind::Integer(ref n) => Some(Expr::int(n.abs())),
+        ExprKind::Fraction(ref n, ref d) => Some(Expr::frac(n.abs(), d.clone())),
+        _ if u.is_constant() => Some(u.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    use super::evaluate_elementary;
+
+    #[test]
+    fn test_special_values() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("Sin", "0", Some("0")),
+            ("Sin", "Pi", Some("0")),
+            ("Cos", "Pi", Some("-1")),
+            ("Sin", "Pi/6", Some("1/2")),
+            ("Cos", "Pi/6", Some("3^(1/2)/2")),
+            ("Sin", "3*Pi/4", Some("2^(1/2)/2")),
+            ("Cos", "5*Pi/3", Some("1/2")),
+            ("Tan", "Pi/4", Some("1")),
+            ("Tan", "Pi/2", Some("Undefined")),
+            ("Cot", "Pi/2", Some("0")),
+            ("Cot", "Pi/6", Some("3^(1/2)")),
+            ("Cot", "ArcTan[x]", Some("1/x")),
+            ("Log", "1", Some("0")),
+            ("Log", "E", Some("1")),
+            ("Log", "E^3", Some("3")),
+            ("Exp", "0", Some("1")),
+            ("Exp", "Log[x]", Some("x")),
+            ("Sqrt", "16", Some("4")),
+            ("Sqrt", "x", Some("x^(1/2)")),
+            ("ArcSin", "1", Some("Pi/2")),
+            ("ArcSin", "-1/2", Some("-Pi/6")),
+            ("ArcCos", "0", Some("Pi/2")),
+            ("ArcCos", "-1", Some("Pi")),
+            ("ArcTan", "1", Some("Pi/4")),
+            ("Sin", "ArcSin[x]", Some("x")),
+            ("Cosh", "0", Some("1")),
+            ("Abs", "-3/2", Some("3/2")),
+            ("Sin", "x", None),
+            ("Cos", "Pi/7", None),
+            ("Log", "2", None),
+            ("ArcSin", "2", None),
+            ("Sinh", "1", None),
+        ];
+        for (name, u, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            let f = Expr::function(name, vec![u.clone()]);
+            assert_eq!(
+                evaluate_elementary(name, &u),
+                expected.map_or_else(|| f.clone(), |e| kernel.parse_eval_line(e)),
+                "{}",
+                f
+            );
+        }
+    }
+
+    #[test]
+    fn test_parity() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("Sin", "-x", "-Sin[x]"),
+            ("Cos", "-2*x", "Cos[2*x]"),
+            ("Tan", "-x", "-Tan[x]"),
+            ("ArcTan", "-x", "-ArcTan[x]"),
+            ("Abs", "-x", "Abs[x]"),
+            ("Sin", "-Pi/6", "-1/2"),
+        ];
+        for (name, u, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            assert_eq!(
+                evaluate_elementary(name, &u),
+                kernel.parse_eval_line(expected),
+                "{}[{}]",
+                name,
+                u
+            );
+        }
+        assert_eq!(kernel.parse_eval_line("Tan[-x] + Tan[x]"), Expr::int(0));
+
+        // Exp is neither odd nor even
+        let u = kernel.parse_eval_line("-x");
+        assert_eq!(
+            evaluate_elementary("Exp", &u),
+            Expr::function("Exp", vec![u])
+        );
+    }
+
+    #[test]
+    fn test_numeric_values() {
+        let mut kernel = AlgomyKernel::new();
+        assert_eq!(
+            kernel.parse_eval_line("N[Pi, 30]").to_string(),
+            "3.14159265358979323846264338328"
+        );
+        assert_eq!(
+            kernel.parse_eval_line("N[E]").to_string(),
+            "2.718281828459045"
+        );
+        assert_eq!(
+            kernel.parse_eval_line("N[Sin[1], 20]").to_string(),
+            "0.84147098480789650665"
+        );
+        assert_eq!(
+            kernel.parse_eval_line("Cos[1.]").to_string(),
+            "0.5403023058681397"
+        );
+        assert_eq!(
+            kernel.parse_eval_line("N[ArcTan[2], 20]").to_string(),
+            "1.107148717794090503"
+        );
+        assert_eq!(kernel.parse_eval_line("Log[-1.]").to_string(), "Log[-1.]");
+    }
+}
@@ -1,11 +1,113 @@
+use super::{
+    apply_definitions, evaluate_elementary, evaluate_list_function, evaluate_numeric,
+    evaluate_numeric_machine, ELEMENTARY_FUNCTIONS, LIST_FUNCTIONS,
+};
 use crate::{
+    calculus::{definite_integral, derivative, derivative_n, integrate},
+    error::{raise, Error},
     expression::{Expr, ExprKind},
-    polynomial::{polynomial_expansion, polynomial_quotient, polynomial_remainder, polynomial_gcd},
+    matrix::{
+        characteristic_polynomial, det, dot, eigenvalues, eigenvectors, identity_matrix, inverse,
+        null_space, rank, row_reduce, transpose,
+    },
+    message::message,
+    pattern::evaluate_replace,
+    polynomial::{
+        apart, cancel, discriminant, factor, factor_expr, groebner_basis, is_square_free,
+        polynomial_expansion, polynomial_extended_gcd, polynomial_gcd, polynomial_gcd_multivariate,
+        polynomial_inverse, polynomial_quotient, polynomial_remainder, polynomials_in, resultant,
+        square_free_factor_expr, together, MonomialOrder,
+    },
+    simplify,
+    solve::{equation_expr, linear_solve, solve_linear, solve_polynomial},
 };
 
+/// The numbers of arguments `(min, max)` accepted by the builtin functions, besides the
+/// elementary functions which take one argument.
+const ARITIES: &[(&str, usize, usize)] = &[
+    ("Numerator", 1, 1),
+    ("Denominator", 1, 1),
+    ("Re", 1, 1),
+    ("Im", 1, 1),
+    ("Expand", 1, 1),
+    ("Coefficient", 3, 3),
+    ("PolynomialQuotient", 3, 3),
+    ("PolynomialRemainder", 3, 3),
+    ("IsInteger", 1, 1),
+    ("IsRNE", 1, 1),
+    ("IsGRNE", 1, 1),
+    ("Variables", 1, 1),
+    ("FreeOf", 2, 2),
+    ("Substitute", 2, 3),
+    ("PolynomialExpansion", 4, 4),
+    ("N", 1, 2),
+    ("D", 2, 2),
+    ("Integrate", 2, 2),
+    ("Factor", 1, 2),
+    ("Together", 1, 1),
+    ("Cancel", 1, 1),
+    ("Apart", 2, 2),
+    ("SquareFreeFactor", 2, 2),
+    ("SquareFreeQ", 2, 2),
+    ("PolynomialExtendedGCD", 3, 3),
+    ("PolynomialInverse", 3, 3),
+    ("Resultant", 3, 3),
+    ("Discriminant", 2, 2),
+    ("MonomialList", 2, 3),
+    ("LeadingTerm", 2, 3),
+    ("LeadingMonomial", 2, 3),
+    ("LeadingCoefficient", 2, 3),
+    ("Solve", 2, 2),
+    ("LinearSolve", 2, 2),
+    ("ReplaceAll", 2, 2),
+    ("ReplaceRepeated", 2, 2),
+    ("Length", 1, 1),
+    ("Part", 2, usize::MAX),
+    ("First", 1, 1),
+    ("Last", 1, 1),
+    ("Append", 2, 2),
+    ("Join", 0, usize::MAX),
+    ("Reverse", 1, 1),
+    ("Range", 1, 3),
+    ("Table", 2, usize::MAX),
+    ("Map", 2, 2),
+    ("Select", 2, 2),
+    ("Dot", 2, usize::MAX),
+    ("IdentityMatrix", 1, 1),
+    ("CharacteristicPolynomial", 2, 2),
+    ("Transpose", 1, 1),
+    ("Det", 1, 1),
+    ("Inverse", 1, 1),
+    ("RowReduce", 1, 1),
+    ("Rank", 1, 1),
+    ("NullSpace", 1, 1),
+    ("Eigenvalues", 1, 1),
+    ("Eigenvectors", 1, 1),
+    ("GroebnerBasis", 2, 3),
+    ("PolynomialReduce", 3, 4),
+    ("PolynomialGCD", 3, 3),
+];
+
+/// Checks whether the builtin function `name` accepts `given` arguments, and raises an error if
+/// it does not.
+fn check_arity(name: &str, given: usize) -> bool {
+    let (min, max) = match ARITIES.iter().find(|(f, _, _)| *f == name) {
+        Some(&(_, min, max)) => (min, max),
+        None if ELEMENTARY_FUNCTIONS.contains(&name) => (1, 1),
+        None => return true,
+    };
+    let valid = (min..=max).contains(&given);
+    if !valid {
+        raise(Error::arity(name, min, max, given));
+    }
+    valid
+}
+
 pub fn evaluate_function(expr: &Expr) -> Expr {
     if let ExprKind::Func(ref name) = expr.kind {
-        if expr
+        if !check_arity(name, expr.operands.len()) {
+            expr.clone()
+        } else if expr
             .operands
             .iter()
             .find(|u| u.kind == ExprKind::Undefined)
@@ -24,16 +126,17 @@ fn evaluate_function_by_name(name: &str, expr: &Expr) -> Expr {
     match (name, expr.operands.len()) {
         ("Numerator", 1) => expr.operands[0].numerator(),
         ("Denominator", 1) => expr.operands[0].denominator(),
+        (name, 1) if ELEMENTARY_FUNCTIONS.contains(&name) => {
+            evaluate_elementary(name, &expr.operands[0])
+        }
         ("Re", 1) => expr.operands[0].re(),
         ("Im", 1) => expr.operands[0].im(),
-        ("Expand", 1) => expr.operands[0].algebraic_expand(),
-        ("Coefficient", 3) => {
-            if let ExprKind::Integer(n) = expr.operands[2].kind {
-                expr.operands[0].coefficient_gpe(&expr.operands[1], n)
-            } else {
-                Expr::undefined()
-            }
-        }
+        ("Expand", 1) => expr.operands[0].expand(),
+        ("Coefficient", 3) => match expr.operands[2].as_i64() {
+            Some(n) if n >= 0 => expr.operands[0].coefficient(&expr.operands[1], n as u32),
+            Some(n) => expr.operands[0].coefficient_gpe(&expr.operands[1], n),
+            None => type_error(expr, "the exponent must be an integer"),
+        },
         ("PolynomialQuotient", 3) => {
             polynomial_quotient(&expr.operands[0], &expr.operands[1], &expr.operands[2])
         }
@@ -46,13 +149,268 @@ fn evaluate_function_by_name(name: &str, expr: &Expr) -> Expr {
         ("Variables", 1) => expr.operands[0].variables(),
         ("FreeOf", 2) => expr.operands[0].free_of(&expr.operands[1]),
         ("Substitute", 3) => expr.operands[0].substitute(&expr.operands[1], &expr.operands[2]),
+        ("Substitute", 2) => match expr.operands[1] {
+            ref rule if rule.is_rule() => {
+                expr.operands[0].substitute_rules(std::slice::from_ref(rule))
+            }
+            ref rules
+                if rules.kind == ExprKind::List && rules.operands.iter().all(|r| r.is_rule()) =>
+            {
+                expr.operands[0].substitute_rules(&rules.operands)
+            }
+            _ => expr.clone(),
+        },
         ("PolynomialExpansion", 4) => polynomial_expansion(
             &expr.operands[0],
             &expr.operands[1],
             &expr.operands[2],
             &expr.operands[3],
         ),
-        ("PolynomialGCD", 3) => polynomial_gcd(&expr.operands[0], &expr.operands[1], &expr.operands[2]),
+        ("N", 1) => evaluate_numeric_machine(&expr.operands[0]),
+        ("N", 2) => match expr.operands[1].as_i64() {
+            Some(digits) if digits > 0 => evaluate_numeric(&expr.operands[0], digits as u32),
+            _ => type_error(expr, "the number of digits must be a positive integer"),
+        },
+        ("D", 2) => evaluate_derivative(expr),
+        ("Integrate", 2) => evaluate_integral(expr),
+        ("Factor", 1) => factor_expr(&expr.operands[0]),
+        ("Factor", 2) => factor(&expr.operands[0], &expr.operands[1]),
+        ("Together", 1) => together(&expr.operands[0]),
+        ("Cancel", 1) => cancel(&expr.operands[0]),
+        ("Apart", 2) => apart(&expr.operands[0], &expr.operands[1]),
+        ("SquareFreeFactor", 2) => square_free_factor_expr(&expr.operands[0], &expr.operands[1]),
+        ("SquareFreeQ", 2) => match expr.operands[0].is_polynomial_gpe(&expr.operands[1]) {
+            true => Expr::bool(is_square_free(&expr.operands[0], &expr.operands[1])),
+            false => expr.clone(),
+        },
+        ("PolynomialExtendedGCD", 3) => {
+            let (g, s, t) =
+                polynomial_extended_gcd(&expr.operands[0], &expr.operands[1], &expr.operands[2]);
+            Expr::list(vec![g, s, t])
+        }
+        ("PolynomialInverse", 3) => {
+            polynomial_inverse(&expr.operands[0], &expr.operands[1], &expr.operands[2])
+                .unwrap_or_else(|| {
+                    message(
+                        "PolynomialInverse",
+                        "ninv",
+                        &format!(
+                            "{} is not invertible modulo {}.",
+                            expr.operands[0], expr.operands[1]
+                        ),
+                    );
+                    Expr::undefined()
+                })
+        }
+        ("Resultant", 3) => resultant(&expr.operands[0], &expr.operands[1], &expr.operands[2])
+            .unwrap_or_else(|| expr.clone()),
+        ("Discriminant", 2) => {
+            discriminant(&expr.operands[0], &expr.operands[1]).unwrap_or_else(|| expr.clone())
+        }
+        ("MonomialList" | "LeadingTerm" | "LeadingMonomial" | "LeadingCoefficient", 2 | 3) => {
+            evaluate_monomial_list(name, expr)
+        }
+        ("Solve", 2) => match (&expr.operands[0].kind, &expr.operands[1].kind) {
+            (ExprKind::List, ExprKind::List) => {
+                solve_linear(&expr.operands[0].operands, &expr.operands[1].operands)
+            }
+            _ => solve_polynomial(&equation_expr(&expr.operands[0]), &expr.operands[1]),
+        }
+        .unwrap_or_else(|| expr.clone()),
+        ("LinearSolve", 2) => match (expr.operands[0].as_matrix(), expr.operands[1].as_vector()) {
+            (Some(m), Some(b)) => linear_solve(&m, &b).map_or_else(|| expr.clone(), Expr::list),
+            _ => expr.clone(),
+        },
+        ("ReplaceAll" | "ReplaceRepeated", 2) => {
+            evaluate_replace(name, &expr.operands[0], &expr.operands[1])
+                .unwrap_or_else(|| expr.clone())
+        }
+        (name, _) if LIST_FUNCTIONS.contains(&name) => {
+            evaluate_list_function(name, expr).unwrap_or_else(|| expr.clone())
+        }
+        ("Dot", n) if n >= 2 => expr.operands[1..]
+            .iter()
+            .try_fold(expr.operands[0].clone(), |u, v| dot(&u, v))
+            .unwrap_or_else(|| expr.clone()),
+        ("IdentityMatrix", 1) => match expr.operands[0].as_i64() {
+            Some(n) if n > 0 => Expr::matrix(identity_matrix(n as usize)),
+            _ => type_error(expr, "the dimension must be a positive integer"),
+        },
+        ("CharacteristicPolynomial", 2) => match expr.operands[0].as_matrix() {
+            Some(m) => {
+                characteristic_polynomial(&m, &expr.operands[1]).unwrap_or_else(|| expr.clone())
+            }
+            None => expr.clone(),
+        },
+        (
+            "Transpose" | "Det" | "Inverse" | "RowReduce" | "Rank" | "NullSpace" | "Eigenvalues"
+            | "Eigenvectors",
+            1,
+        ) => evaluate_matrix_function(name, expr),
+        ("GroebnerBasis", 2 | 3) => evaluate_groebner_basis(expr),
+        ("PolynomialReduce", 3 | 4) => evaluate_polynomial_reduce(expr),
+        ("PolynomialGCD", 3) => match expr.operands[2].kind {
+            ExprKind::List => polynomial_gcd_multivariate(
+                &expr.operands[0],
+                &expr.operands[1],
+                &expr.operands[2].operands,
+            )
+            .unwrap_or_else(|| expr.clone()),
+            _ => polynomial_gcd(&expr.operands[0], &expr.operands[1], &expr.operands[2]),
+        },
+        _ => apply_definitions(expr).unwrap_or_else(|| expr.clone()),
+    }
+}
+
+/// Reads the monomial order given as argument `i` of `expr`, which defaults to `Lex`. Raises a
+/// type error for an unknown order.
+fn monomial_order(expr: &Expr, i: usize) -> Option<MonomialOrder> {
+    let Some(u) = expr.operands.get(i) else {
+        return Some(MonomialOrder::Lex);
+    };
+    let order = MonomialOrder::from_expr(u);
+    if order.is_none() {
+        type_error(
+            expr,
+            &format!(
+                "{} is not a monomial order; expected Lex, GrLex or GrevLex",
+                u
+            ),
+        );
+    }
+    order
+}
+
+/// Raises a type error for the builtin function call `expr`, which is left unevaluated.
+fn type_error(expr: &Expr, message: &str) -> Expr {
+    if let ExprKind::Func(ref name) = expr.kind {
+        raise(Error::type_error(name, message));
+    }
+    expr.clone()
+}
+
+/// Evaluates `D[u, x]` and `D[u, {x, n}]`
+fn evaluate_derivative(expr: &Expr) -> Expr {
+    let u = &expr.operands[0];
+    let spec = &expr.operands[1];
+    match spec.kind {
+        ExprKind::List => match &spec.operands[..] {
+            [x, n] if !x.is_real_number() => match n.as_i64() {
+                Some(n) if n >= 0 => derivative_n(u, x, n as u64),
+                _ => expr.clone(),
+            },
+            _ => expr.clone(),
+        },
+        _ if spec.is_real_number() => expr.clone(),
+        _ => derivative(u, spec),
+    }
+}
+
+/// Evaluates `Integrate[u, x]` and the definite integral `Integrate[u, {x, a, b}]`
+fn evaluate_integral(expr: &Expr) -> Expr {
+    let u = &expr.operands[0];
+    let spec = &expr.operands[1];
+    match spec.kind {
+        ExprKind::List => match &spec.operands[..] {
+            [x, a, b] if !x.is_real_number() => {
+                definite_integral(u, x, a, b).unwrap_or_else(|| expr.clone())
+            }
+            _ => expr.clone(),
+        },
+        _ if spec.is_real_number() => expr.clone(),
+        _ => integrate(u, spec).unwrap_or_else(|| expr.clone()),
+    }
+}
+
+/// Evaluates `GroebnerBasis[{p, ...}, {x, ...}]` and `GroebnerBasis[{p, ...}, {x, ...}, order]`
+fn evaluate_groebner_basis(expr: &Expr) -> Expr {
+    let order = monomial_order(expr, 2);
+    match (&expr.operands[0].kind, &expr.operands[1].kind, order) {
+        (ExprKind::List, ExprKind::List, Some(order)) => {
+            match polynomials_in(&expr.operands[0].operands, &expr.operands[1].operands) {
+                Some(ps) => Expr::list(
+                    groebner_basis(&ps, order)
+                        .iter()
+                        .map(|p| p.to_expr())
+                        .collect(),
+                ),
+                None => expr.clone(),
+            }
+        }
+        _ => expr.clone(),
+    }
+}
+
+/// Evaluates `PolynomialReduce[p, {g, ...}, {x, ...}]` and
+/// `PolynomialReduce[p, {g, ...}, {x, ...}, order]`, which returns the quotients and remainder
+/// `{{q, ...}, r}` of the division of `p` by the polynomials `g`.
+fn evaluate_polynomial_reduce(expr: &Expr) -> Expr {
+    let order = monomial_order(expr, 3);
+    match (&expr.operands[1].kind, &expr.operands[2].kind, order) {
+        (ExprKind::List, ExprKind::List, Some(order)) => {
+            let mut us = vec![expr.operands[0].clone()];
+            us.extend(expr.operands[1].operands.iter().cloned());
+            match polynomials_in(&us, &expr.operands[2].operands) {
+                Some(ps) => {
+                    let (quotients, r) = ps[0].divide_by(&ps[1..], order);
+                    Expr::list(vec![
+                        Expr::list(quotients.iter().map(|q| q.to_expr()).collect()),
+                        r.to_expr(),
+                    ])
+                }
+                None => expr.clone(),
+            }
+        }
+        _ => expr.clone(),
+    }
+}
+
+/// Evaluates `MonomialList[u, vars]` and the leading term functions `LeadingTerm`,
+/// `LeadingMonomial` and `LeadingCoefficient`, with an optional monomial order as third argument.
+fn evaluate_monomial_list(name: &str, expr: &Expr) -> Expr {
+    let vars = match expr.operands[1].kind {
+        ExprKind::List => expr.operands[1].operands.clone(),
+        _ => vec![expr.operands[1].clone()],
+    };
+    let order = monomial_order(expr, 2);
+    let Some(terms) = order.and_then(|order| expr.operands[0].monomial_list(&vars, order)) else {
+        return expr.clone();
+    };
+    let term = |(exps, c): &(Vec<u32>, Expr)| {
+        simplify(&Expr::times(c.clone(), Expr::monomial(&vars, exps)))
+    };
+    match (name, terms.first()) {
+        ("MonomialList", _) if terms.is_empty() => Expr::list(vec![Expr::int(0)]),
+        ("MonomialList", _) => Expr::list(terms.iter().map(term).collect()),
+        (_, None) => Expr::int(0),
+        ("LeadingTerm", Some(t)) => term(t),
+        ("LeadingMonomial", Some((exps, _))) => Expr::monomial(&vars, exps),
+        ("LeadingCoefficient", Some((_, c))) => c.clone(),
+        _ => unreachable!(),
+    }
+}
+
+/// Evaluates the linear algebra functions of a single matrix, e.g. `Det[m]` or `NullSpace[m]`.
+/// `Inverse` returns `Undefined` for singular matrices, and emits `Inverse::sing`.
+fn evaluate_matrix_function(name: &str, expr: &Expr) -> Expr {
+    let Some(m) = expr.operands[0].as_matrix() else {
+        return expr.clone();
+    };
+    match name {
+        "Transpose" => Expr::matrix(transpose(&m)),
+        "Det" => det(&m).unwrap_or_else(|| expr.clone()),
+        "Inverse" if m.len() == m[0].len() => inverse(&m).map_or_else(
+            || {
+                message("Inverse", "sing", "Matrix is singular.");
+                Expr::undefined()
+            },
+            Expr::matrix,
+        ),
+        "RowReduce" => Expr::matrix(row_reduce(&m)),
+        "Rank" => Expr::int(rank(&m) as i64),
+        "NullSpace" => Expr::matrix(null_space(&m)),
+        "Eigenvalues" => eigenvalues(&m).map_or_else(|| expr.clone(), Expr::list),
+        "Eigenvectors" => eigenvectors(&m).map_or_else(|| expr.clone(), Expr::matrix),
         _ => expr.clone(),
     }
 }
@@ -1,12 +1,15 @@
-use crate::expression::Expr;
+use crate::{
+    expression::Expr,
+    message::{indeterminate_expression, infinite_expression},
+};
 
 use super::{
     evaluate_difference, evaluate_power, evaluate_product, evaluate_quotient, evaluate_sum,
 };
 
-/// Evaluates v + w where `v` and `w` are integers, fractions or gaussian numbers
+/// Evaluates v + w where `v` and `w` are integers, fractions, reals or gaussian numbers
 pub fn evaluate_sum_gaussian(v: &Expr, w: &Expr) -> Expr {
-    if v.is_rne() && w.is_rne() {
+    if v.is_real_number() && w.is_real_number() {
         evaluate_sum(v, w)
     } else {
         let v_re = v.re();
@@ -17,9 +20,9 @@ pub fn evaluate_sum_gaussian(v: &Expr, w: &Expr) -> Expr {
     }
 }
 
-/// Evaluates v - w where `v` and `w` are integers, fractions or gaussian numbers
+/// Evaluates v - w where `v` and `w` are integers, fractions, reals or gaussian numbers
 pub fn evaluate_difference_gaussian(v: &Expr, w: &Expr) -> Expr {
-    if v.is_rne() && w.is_rne() {
+    if v.is_real_number() && w.is_real_number() {
         evaluate_difference(v, w)
     } else {
         let v_re = v.re();
@@ -28,14 +31,14 @@ pub fn evaluate_difference_gaussian(v: &Expr, w: &Expr) -> Expr {
         let w_im = w.im();
         Expr::gaussian(
             evaluate_difference(&v_re, &w_re),
-            evaluate_sum(&v_im, &w_im),
+            evaluate_difference(&v_im, &w_im),
         )
     }
 }
 
-/// Evaluates v * w where `v` and `w` are integers, fractions or gaussian numbers
+/// Evaluates v * w where `v` and `w` are integers, fractions, reals or gaussian numbers
 pub fn evaluate_product_gaussian(v: &Expr, w: &Expr) -> Expr {
-    if v.is_rne() && w.is_rne() {
+    if v.is_real_number() && w.is_real_number() {
         evaluate_product(v, w)
     } else {
         let v_re = v.re();
@@ -55,12 +58,13 @@ pub fn evaluate_product_gaussian(v: &Expr, w: &Expr) -> Expr {
     }
 }
 
-/// Evaluates v/w where `v` and `w` are integers, fractions or gaussian numbers
+/// Evaluates v/w where `v` and `w` are integers, fractions, reals or gaussian numbers
 pub fn evaluate_quotient_gaussian(v: &Expr, w: &Expr) -> Expr {
-    if v.is_rne() && w.is_rne() {
+    if v.is_real_number() && w.is_real_number() {
         evaluate_quotient(v, w)
     } else {
-        if w.re().numerator_rne() == 0 && w.im().numerator_rne() == 0 {
+        if w.re().is_zero() && w.im().is_zero() {
+            infinite_expression();
             Expr::undefined()
         } else {
             let v_re = v.re(); // a
@@ -89,12 +93,12 @@ pub fn evaluate_quotient_gaussian(v: &Expr, w: &Expr) -> Expr {
     }
 }
 
-/// Evaluates v^n where `v` is an integer, fraction or gaussian number with non-zero denominator and `n` is an integer
+/// Evaluates v^n where `v` is an integer, fraction, real or gaussian number with non-zero denominator and `n` is an integer
 pub fn evaluate_power_gaussian(v: &Expr, n: i64) -> Expr {
-    if v.is_rne() {
+    if v.is_real_number() {
         evaluate_power(v, n)
     } else {
-        if v.re().numerator_rne() != 0 || v.im().numerator_rne() != 0 {
+        if !v.re().is_zero() || !v.im().is_zero() {
             if n > 0 {
                 let s = evaluate_power_gaussian(v, n - 1);
                 evaluate_product_gaussian(&s, v)
@@ -111,10 +115,42 @@ pub fn evaluate_power_gaussian(v: &Expr, n: i64) -> Expr {
             // v.numerator() == 0
             if n >= 1 {
                 Expr::int(0)
+            } else if n == 0 {
+                indeterminate_expression();
+                Expr::undefined()
             } else {
-                // n <= 0
+                // n < 0
+                infinite_expression();
                 Expr::undefined()
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use num::BigRational;
+
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    use super::evaluate_difference_gaussian;
+
+    #[test]
+    fn test_difference_gaussian() {
+        // (1 + 2*I) - (1 + I) -> I
+        let v = Expr::gaussian(Expr::int(1), Expr::int(2));
+        let w = Expr::gaussian(Expr::int(1), Expr::int(1));
+        let d = evaluate_difference_gaussian(&v, &w);
+        assert_eq!(d.re().rational_rne(), BigRational::from_integer(0.into()));
+        assert_eq!(d.im().rational_rne(), BigRational::from_integer(1.into()));
+
+        let mut kernel = AlgomyKernel::new();
+        let i = Expr::gaussian(Expr::int(0), Expr::int(1));
+        assert_eq!(kernel.parse_eval_line("(1 + 2*I) - (1 + I)"), i);
+        assert_eq!(kernel.parse_eval_line("(1/2 + 3*I) - (1/2 + 2*I)"), i);
+        assert_eq!(
+            kernel.parse_eval_line("3 - (1 + I)"),
+            Expr::gaussian(Expr::int(2), Expr::int(-1))
+        );
+    }
+}
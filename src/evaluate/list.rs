@@ -0,0 +1,242 @@
+use crate::{
+    expression::{Expr, ExprKind},
+    simplify,
+};
+
+pub const LIST_FUNCTIONS: [&str; 11] = [
+    "Length", "Part", "First", "Last", "Append", "Join", "Reverse", "Range", "Table", "Map",
+    "Select",
+];
+
+/// Evaluates the list function `name`, e.g. `Part[list, i]` or `Table[u, {i, n}]`. Returns
+/// `None` if the arguments are invalid.
+pub fn evaluate_list_function(name: &str, expr: &Expr) -> Option<Expr> {
+    let args = &expr.operands;
+    match (name, args.len()) {
+        ("Length", 1) => args[0].length().map(|n| Expr::int(n as i64)),
+        ("Part", n) if n >= 2 => part(&args[0], &args[1..]),
+        ("First", 1) => part(&args[0], &[Expr::int(1)]),
+        ("Last", 1) => part(&args[0], &[Expr::int(-1)]),
+        ("Append", 2) => append(&args[0], &args[1]),
+        ("Join", _) => join(args),
+        ("Reverse", 1) => reverse(&args[0]),
+        ("Range", _) => range(args),
+        ("Table", n) if n >= 2 => table(&args[0], &args[1..]),
+        ("Map", 2) => map(&args[0], &args[1]),
+        ("Select", 2) => select(&args[0], &args[1]),
+        _ => None,
+    }
+}
+
+/// Returns the part of `u` at the given indices, where `Part[u, i, j]` is `Part[Part[u, i], j]`.
+/// Negative indices count from the end. Returns `None` if an index is not an integer or out of
+/// range.
+pub fn part(u: &Expr, indices: &[Expr]) -> Option<Expr> {
+    indices.iter().try_fold(u.clone(), |u, i| {
+        if u.is_atomic() {
+            return None;
+        }
+        let n = u.operands.len() as i64;
+        let i = i.as_i64()?;
+        let k = if i < 0 { n + i } else { i - 1 };
+        (0..n).contains(&k).then(|| u.operands[k as usize].clone())
+    })
+}
+
+/// Appends `x` to a list.
+pub fn append(list: &Expr, x: &Expr) -> Option<Expr> {
+    let mut operands = as_list(list)?.to_vec();
+    operands.push(x.clone());
+    Some(Expr::list(operands))
+}
+
+/// Concatenates lists.
+pub fn join(lists: &[Expr]) -> Option<Expr> {
+    let mut operands = Vec::new();
+    for list in lists {
+        operands.extend(as_list(list)?.iter().cloned());
+    }
+    Some(Expr::list(operands))
+}
+
+/// Reverses the order of the elements of a list.
+pub fn reverse(list: &Expr) -> Option<Expr> {
+    Some(Expr::list(as_list(list)?.iter().rev().cloned().collect()))
+}
+
+/// Evaluates `Range[n]`, `Range[a, b]` and `Range[a, b, step]`, the list of numbers from `a`
+/// (default 1) up to `b` in steps of `step` (default 1). Returns `None` if the bounds are not
+/// real numbers or the step is zero.
+pub fn range(args: &[Expr]) -> Option<Expr> {
+    let (a, b, step) = match args {
+        [b] => (Expr::int(1), b.clone(), Expr::int(1)),
+        [a, b] => (a.clone(), b.clone(), Expr::int(1)),
+        [a, b, step] => (a.clone(), b.clone(), step.clone()),
+        _ => return None,
+    };
+    if !a.is_real_number() || !b.is_real_number() || !step.is_real_number() || step.is_zero() {
+        return None;
+    }
+    let direction = step.cmp(&Expr::int(0));
+    let mut values = Vec::new();
+    let mut x = a;
+    while x.cmp(&b) != direction {
+        values.push(x.clone());
+        x = simplify(&Expr::plus(x, step.clone()));
+    }
+    Some(Expr::list(values))
+}
+
+/// Evaluates `Table[u, iterators...]` by substituting the values of the iterators into `u`. An
+/// iterator is one of `{n}`, `{i, n}`, `{i, a, b}`, `{i, a, b, step}` or `{i, {values...}}`, and
+/// later iterators are nested inside earlier ones. Returns `None` for an invalid iterator.
+pub fn table(u: &Expr, iterators: &[Expr]) -> Option<Expr> {
+    let Some((iterator, rest)) = iterators.split_first() else {
+        return Some(u.clone());
+    };
+    let spec = as_list(iterator)?;
+    let (var, values) = match spec {
+        [n] => (None, range(std::slice::from_ref(n))?),
+        [i, values] if values.kind == ExprKind::List => (Some(i), values.clone()),
+        [i, bounds @ ..] => (Some(i), range(bounds)?),
+        [] => return None,
+    };
+    let elements = values
+        .operands
+        .iter()
+        .map(|v| match var {
+            Some(i) => table(&u.substitute(i, v), rest),
+            None => table(u, rest),
+        })
+        .collect::<Option<Vec<Expr>>>()?;
+    Some(Expr::list(elements))
+}
+
+/// Applies the function `f` to every element of a list or set.
+pub fn map(f: &Expr, list: &Expr) -> Option<Expr> {
+    match list.kind {
+        ExprKind::List | ExprKind::Set => {
+            let operands = list
+                .operands
+                .iter()
+                .map(|x| apply(f, x))
+                .collect::<Option<Vec<Expr>>>()?;
+            Some(simplify(&Expr {
+                kind: list.kind.clone(),
+                operands,
+            }))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the elements `x` of a list for which `f[x]` is `True`.
+pub fn select(list: &Expr, f: &Expr) -> Option<Expr> {
+    let mut operands = Vec::new();
+    for x in as_list(list)? {
+        if apply(f, x)? == Expr::bool(true) {
+            operands.push(x.clone());
+        }
+    }
+    Some(Expr::list(operands))
+}
+
+/// Applies a function, given by its name, to `x`.
+fn apply(f: &Expr, x: &Expr) -> Option<Expr> {
+    match f.kind {
+        ExprKind::Symbol(ref name) => Some(simplify(&Expr::function(name, vec![x.clone()]))),
+        _ => None,
+    }
+}
+
+fn as_list(u: &Expr) -> Option<&[Expr]> {
+    match u.kind {
+        ExprKind::List => Some(&u.operands),
+        _ => None,
+    }
+}
+
+impl Expr {
+    /// Returns the number of elements of a list or set, or the number of operands of any other
+    /// non-atomic expression, such that `Part[u, Length[u]]` is the last part of `u`.
+    pub fn length(&self) -> Option<usize> {
+        match self.is_atomic() {
+            true => None,
+            false => Some(self.num_operands()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    use super::evaluate_list_function;
+
+    #[test]
+    fn test_lists() {
+        let mut kernel = AlgomyKernel::new();
+        let cases: [(&str, &[&str], Option<&str>); 30] = [
+            ("Length", &["{a, b, c}"], Some("3")),
+            ("Length", &["{}"], Some("0")),
+            ("Length", &["x + y + z"], Some("3")),
+            ("Length", &["f[x, y]"], Some("2")),
+            ("Length", &["x"], None),
+            ("Part", &["f[x, y]", "2"], Some("y")),
+            ("Part", &["{a, b, c}", "1"], Some("a")),
+            ("Part", &["{a, b, c}", "-1"], Some("c")),
+            ("Part", &["{{1, 2}, {3, 4}}", "2", "1"], Some("3")),
+            ("Part", &["{a, b}", "3"], None),
+            ("Part", &["{a, b}", "x"], None),
+            ("First", &["{3, 1, 2}"], Some("3")),
+            ("Last", &["{3, 1, 2}"], Some("2")),
+            ("Append", &["{1, 2}", "x"], Some("{1, 2, x}")),
+            (
+                "Join",
+                &["{1, 2}", "{3}", "{2, 1}"],
+                Some("{1, 2, 3, 2, 1}"),
+            ),
+            ("Reverse", &["{1, 2, 3}"], Some("{3, 2, 1}")),
+            ("Range", &["4"], Some("{1, 2, 3, 4}")),
+            ("Range", &["0"], Some("{}")),
+            ("Range", &["2", "5"], Some("{2, 3, 4, 5}")),
+            ("Range", &["1", "2", "1/3"], Some("{1, 4/3, 5/3, 2}")),
+            ("Range", &["5", "1", "-2"], Some("{5, 3, 1}")),
+            ("Range", &["1", "2", "0"], None),
+            ("Table", &["i^2", "{i, 4}"], Some("{1, 4, 9, 16}")),
+            ("Table", &["x", "{3}"], Some("{x, x, x}")),
+            ("Table", &["x^k", "{k, 0, 2}"], Some("{1, x, x^2}")),
+            (
+                "Table",
+                &["i + j", "{i, 2}", "{j, 0, 1}"],
+                Some("{{1, 2}, {2, 3}}"),
+            ),
+            ("Table", &["2*i", "{i, {a, b}}"], Some("{2*a, 2*b}")),
+            ("Map", &["Sin", "{0, x}"], Some("{0, Sin[x]}")),
+            ("Map", &["IsInteger", "{1, 1/2}"], Some("{True, False}")),
+            ("Select", &["{1, 1/2, 3, x}", "IsInteger"], Some("{1, 3}")),
+        ];
+        for (name, args, expected) in cases {
+            let args = args.iter().map(|a| kernel.parse_eval_line(a)).collect();
+            let expr = Expr::function(name, args);
+            assert_eq!(
+                evaluate_list_function(name, &expr),
+                expected.map(|e| kernel.parse_eval_line(e)),
+                "{}",
+                expr
+            );
+        }
+
+        // Lists keep their order, and [[...]] is Part
+        assert_eq!(
+            kernel.parse_eval_line("List[3, 1, 2]"),
+            Expr::list(vec![Expr::int(3), Expr::int(1), Expr::int(2)])
+        );
+        assert_eq!(kernel.parse_eval_line("{a, b, c}[[2]]"), Expr::symbol("b"));
+        assert_eq!(kernel.parse_eval_line("-{1, 2}[[1]]"), Expr::int(-1));
+        assert_eq!(
+            kernel.parse_eval_line("{a, b}[[3]]"),
+            Expr::function("Part", vec![kernel.parse_eval_line("{a, b}"), Expr::int(3)])
+        );
+    }
+}
@@ -1,38 +1,81 @@
-use crate::expression::Expr;
+use num::{traits::Pow, BigRational, ToPrimitive, Zero};
 
+use crate::{
+    expression::{Expr, ExprKind, Real},
+    message::{indeterminate_expression, infinite_expression},
+};
+
+mod definition;
+mod elementary;
 mod function;
 mod gaussian;
+mod list;
+mod numeric;
 
+pub use definition::*;
+pub use elementary::*;
 pub use function::*;
 pub use gaussian::*;
+pub use list::*;
+pub use numeric::*;
+
+/// Converts `v` and `w` to reals when at least one of them is a real.
+/// The exact operand gets the precision of the real one.
+fn as_reals(v: &Expr, w: &Expr) -> Option<(Real, Real)> {
+    let precision = match (&v.kind, &w.kind) {
+        (ExprKind::Real(a), ExprKind::Real(b)) => a.precision().min(b.precision()),
+        (ExprKind::Real(a), _) | (_, ExprKind::Real(a)) => a.precision(),
+        _ => return None,
+    };
+    Some((v.to_real(precision), w.to_real(precision)))
+}
 
-/// Evaluates v + w where `v` and `w` are integers or fractions
+/// Evaluates v + w where `v` and `w` are integers, fractions or reals
 pub fn evaluate_sum(v: &Expr, w: &Expr) -> Expr {
+    if let Some((a, b)) = as_reals(v, w) {
+        return Expr::real(a.add(&b));
+    }
     Expr::frac(
         v.numerator_rne() * w.denominator_rne() + w.numerator_rne() * v.denominator_rne(),
         v.denominator_rne() * w.denominator_rne(),
     )
 }
 
-/// Evaluates v - w where `v` and `w` are integers or fractions
+/// Evaluates v - w where `v` and `w` are integers, fractions or reals
 pub fn evaluate_difference(v: &Expr, w: &Expr) -> Expr {
+    if let Some((a, b)) = as_reals(v, w) {
+        return Expr::real(a.sub(&b));
+    }
     Expr::frac(
         v.numerator_rne() * w.denominator_rne() - w.numerator_rne() * v.denominator_rne(),
         v.denominator_rne() * w.denominator_rne(),
     )
 }
 
-/// Evaluates v * w where `v` and `w` are integers or fractions
+/// Evaluates v * w where `v` and `w` are integers, fractions or reals
 pub fn evaluate_product(v: &Expr, w: &Expr) -> Expr {
+    if let Some((a, b)) = as_reals(v, w) {
+        return Expr::real(a.mul(&b));
+    }
     Expr::frac(
         v.numerator_rne() * w.numerator_rne(),
         v.denominator_rne() * w.denominator_rne(),
     )
 }
 
-/// Evaluates v/w where `v` and `w` are integers or fractions
+/// Evaluates v/w where `v` and `w` are integers, fractions or reals
 pub fn evaluate_quotient(v: &Expr, w: &Expr) -> Expr {
-    if w.numerator_rne() == 0 {
+    if let Some((a, b)) = as_reals(v, w) {
+        return a.div(&b).map_or_else(
+            || {
+                infinite_expression();
+                Expr::undefined()
+            },
+            Expr::real,
+        );
+    }
+    if w.numerator_rne().is_zero() {
+        infinite_expression();
         Expr::undefined()
     } else {
         Expr::frac(
@@ -42,28 +85,79 @@ pub fn evaluate_quotient(v: &Expr, w: &Expr) -> Expr {
     }
 }
 
-/// Evaluates v^n where `v` is an integer or fraction with non-zero denominator and `n` is an integer
+/// Evaluates v^n where `v` is an integer, fraction or real with non-zero denominator and `n` is an integer
 pub fn evaluate_power(v: &Expr, n: i64) -> Expr {
-    if v.numerator_rne() != 0 {
-        if n > 0 {
-            let s = evaluate_power(v, n - 1);
-            evaluate_product(&s, v)
-        } else if n == 0 {
-            Expr::int(1)
-        } else if n == -1 {
-            Expr::frac(v.denominator_rne(), v.numerator_rne())
-        } else {
-            // n < -1
-            let s = Expr::frac(v.denominator_rne(), v.numerator_rne());
-            evaluate_power(&s, -n)
+    if let ExprKind::Real(ref r) = v.kind {
+        // Only a zero base with n <= 0 cannot be computed
+        if let Some(r) = r.powi(n) {
+            return Expr::real(r);
         }
+    } else if !v.numerator_rne().is_zero() {
+        let e = n.unsigned_abs() as usize;
+        return if n >= 0 {
+            Expr::frac(
+                Pow::pow(v.numerator_rne(), e),
+                Pow::pow(v.denominator_rne(), e),
+            )
+        } else {
+            Expr::frac(
+                Pow::pow(v.denominator_rne(), e),
+                Pow::pow(v.numerator_rne(), e),
+            )
+        };
+    }
+    // v == 0
+    if n >= 1 {
+        Expr::int(0)
+    } else if n == 0 {
+        indeterminate_expression();
+        Expr::undefined()
     } else {
-        // v.numerator() == 0
-        if n >= 1 {
-            Expr::int(0)
+        // n < 0
+        infinite_expression();
+        Expr::undefined()
+    }
+}
+
+/// Evaluates v^w where `v` and `w` are integers, fractions or reals and at least one of them is a real.
+/// A negative base with a non-integer exponent gives the principal value, a gaussian number.
+/// Returns `None` when the result cannot be computed.
+pub fn evaluate_real_power(v: &Expr, w: &Expr) -> Option<Expr> {
+    let (a, b) = as_reals(v, w)?;
+    let turns = match w.kind {
+        ExprKind::Fraction(ref p, ref q) => BigRational::new(p.clone(), q.clone()),
+        _ => b.to_rational(),
+    };
+    if a.is_zero() {
+        if b.is_negative() || b.is_zero() {
+            match b.is_zero() {
+                true => indeterminate_expression(),
+                false => infinite_expression(),
+            }
+            Some(Expr::undefined())
         } else {
-            // n <= 0
-            Expr::undefined()
+            Some(Expr::real(a))
         }
+    } else if turns.is_integer() {
+        a.powi(turns.to_integer().to_i64()?).map(Expr::real)
+    } else if a.is_negative() {
+        // (-a)^b = a^b * (cos(b*Pi) + I*sin(b*Pi))
+        let r = real_power(&a.neg(), w, &b)?;
+        let (sin, cos) = Real::sin_cos_pi(&turns, r.precision());
+        Some(Expr::gaussian(
+            Expr::real(r.mul(&cos)),
+            Expr::real(r.mul(&sin)),
+        ))
+    } else {
+        real_power(&a, w, &b).map(Expr::real)
+    }
+}
+
+/// Evaluates a^w for a positive real `a`, where `b` is `w` as a real.
+fn real_power(a: &Real, w: &Expr, b: &Real) -> Option<Real> {
+    match w.kind {
+        // Roots are computed directly, which is more accurate than exp(w*ln(v))
+        ExprKind::Fraction(ref p, ref q) => a.root(q.to_u32()?)?.powi(p.to_i64()?),
+        _ => a.pow(b),
     }
 }
@@ -0,0 +1,118 @@
+use crate::{
+    expression::{Expr, ExprKind, Real, MACHINE_PRECISION},
+    simplify,
+};
+
+/// Number of extra digits used while numerically evaluating an expression.
+const GUARD_DIGITS: u32 = 5;
+
+/// Numerically evaluates `u`, such that all exact numbers are replaced by reals with
+/// `digits` significant digits.
+pub fn evaluate_numeric(u: &Expr, digits: u32) -> Expr {
+    round_reals(
+        &evaluate_numeric_recursive(u, digits + GUARD_DIGITS),
+        digits,
+    )
+}
+
+/// Numerically evaluates `u` to machine precision.
+pub fn evaluate_numeric_machine(u: &Expr) -> Expr {
+    evaluate_numeric(u, MACHINE_PRECISION)
+}
+
+fn evaluate_numeric_recursive(u: &Expr, digits: u32) -> Expr {
+    match u.kind {
+        ExprKind::Integer(_) | ExprKind::Fraction(_, _) | ExprKind::Real(_) => {
+            Expr::real(u.to_real(digits))
+        }
+        ExprKind::Symbol(ref s) if s == "Pi" => Expr::real(Real::pi(digits)),
+        ExprKind::Symbol(ref s) if s == "E" => Expr::real(Expr::int(1).to_real(digits).exp()),
+        _ if u.is_atomic() && u.kind != ExprKind::Gaussian => u.clone(),
+        _ => simplify(&u.map(|v| evaluate_numeric_recursive(v, digits))),
+    }
+}
+
+/// Rounds all reals in `u` to at most `digits` significant digits.
+fn round_reals(u: &Expr, digits: u32) -> Expr {
+    match u.kind {
+        ExprKind::Real(ref r) => Expr::real(r.with_precision(digits)),
+        _ => u.map(|v| round_reals(v, digits)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{evaluate::evaluate_real_power, parser::AlgomyKernel};
+
+    use super::{evaluate_numeric, evaluate_numeric_machine};
+
+    #[test]
+    fn test_evaluate_numeric() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("1/3", 16, "0.3333333333333333"),
+            ("2^(1/2)", 30, "1.41421356237309504880168872421"),
+            ("x/4 + 1/8", 16, "(0.125 + (0.25 * x))"),
+            ("Pi", 10, "3.141592654"),
+            ("(-2)^(1/2)", 16, "1.414213562373095𝕚"),
+            ("(-8)^(1/3)", 16, "(1.+1.732050807568877𝕚)"),
+            ("1 + I", 16, "(1.+1.𝕚)"),
+        ];
+        for (u, digits, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            assert_eq!(evaluate_numeric(&u, digits).to_string(), expected, "{}", u);
+        }
+
+        // Exact and inexact inputs give equal values
+        let u = kernel.parse_eval_line("(-2)^(1/2)");
+        assert_eq!(
+            evaluate_numeric_machine(&u),
+            kernel.parse_eval_line("(-2.0)^(1/2)")
+        );
+        assert_eq!(
+            kernel.parse_eval_line("N[(-8)^(1/3)]"),
+            kernel.parse_eval_line("(-8.0)^(1/3)")
+        );
+
+        assert_eq!(kernel.parse_eval_line("1.5 + 1/2").to_string(), "2.");
+        assert_eq!(
+            kernel.parse_eval_line("2*x + 0.5*x").to_string(),
+            "(2.5 * x)"
+        );
+        assert_eq!(kernel.parse_eval_line("1e-5*3").to_string(), "0.00003");
+    }
+
+    #[test]
+    fn test_real_power() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("2", "0.5", Some("1.414213562373095")),
+            ("2.", "1/2", Some("1.414213562373095")),
+            ("4.", "-1/2", Some("0.5")),
+            ("-2.", "3", Some("-8.")),
+            ("-2.", "2.", Some("4.")),
+            // Negative bases give the principal value
+            ("-8.", "1/3", Some("(1.+1.732050807568877𝕚)")),
+            ("-2.", "1/2", Some("1.414213562373095𝕚")),
+            ("-2.", "0.5", Some("1.414213562373095𝕚")),
+            ("-2.", "-1/2", Some("-0.7071067811865475𝕚")),
+            ("-4.", "3/2", Some("-8.𝕚")),
+            ("0.", "2", Some("0.")),
+            ("0.", "-1", Some("undefined")),
+        ];
+        for (v, w, expected) in cases {
+            let (v, w) = (kernel.parse_eval_line(v), kernel.parse_eval_line(w));
+            assert_eq!(
+                evaluate_real_power(&v, &w).map(|u| u.to_string()),
+                expected.map(|e| e.to_owned()),
+                "{}^{}",
+                v,
+                w
+            );
+        }
+        assert_eq!(
+            kernel.parse_eval_line("(-8)^(1/3)").to_string(),
+            "(-8 ^ 1/3)"
+        );
+    }
+}
@@ -7,11 +7,12 @@ impl Display for Expr {
         match self.kind {
             ExprKind::Undefined => write!(f, "undefined"),
             ExprKind::Symbol(ref s) => write!(f, "{}", s),
-            ExprKind::Integer(n) => write!(f, "{}", n),
-            ExprKind::Fraction(n, d) => write!(f, "{}/{}", n, d),
+            ExprKind::Integer(ref n) => write!(f, "{}", n),
+            ExprKind::Fraction(ref n, ref d) => write!(f, "{}/{}", n, d),
+            ExprKind::Real(ref r) => write!(f, "{}", r),
             ExprKind::Gaussian => {
-                if self.operands[0].kind == ExprKind::Integer(0) {
-                    if self.operands[1].kind == ExprKind::Integer(1) {
+                if self.operands[0].is_zero() {
+                    if self.operands[1].is_one() {
                         write!(f, "\u{1d55a}")
                     } else {
                         write!(f, "{}\u{1d55a}", self.operands[1])
@@ -91,7 +92,15 @@ impl Display for Expr {
                 }
                 write!(f, "({})", s)
             }
-            ExprKind::Set => {
+            ExprKind::Equal => write!(f, "{} == {}", self.operands[0], self.operands[1]),
+            ExprKind::Unequal => write!(f, "{} != {}", self.operands[0], self.operands[1]),
+            ExprKind::Less => write!(f, "{} < {}", self.operands[0], self.operands[1]),
+            ExprKind::LessEqual => write!(f, "{} <= {}", self.operands[0], self.operands[1]),
+            ExprKind::Greater => write!(f, "{} > {}", self.operands[0], self.operands[1]),
+            ExprKind::GreaterEqual => write!(f, "{} >= {}", self.operands[0], self.operands[1]),
+            ExprKind::Rule => write!(f, "{} -> {}", self.operands[0], self.operands[1]),
+            ExprKind::RuleDelayed => write!(f, "{} :> {}", self.operands[0], self.operands[1]),
+            ExprKind::List | ExprKind::Set => {
                 let mut s = String::new();
                 for (i, operand) in self.operands.iter().enumerate() {
                     if i > 0 {
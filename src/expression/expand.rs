@@ -1,3 +1,5 @@
+use num::{BigInt, ToPrimitive};
+
 use crate::{
     expression::{Expr, ExprKind},
     simplify::simplify,
@@ -25,14 +27,12 @@ impl Expr {
             ExprKind::Power => {
                 let b = self.base();
                 let e = self.exponent();
-                if let ExprKind::Integer(e) = e.kind {
-                    if e >= 2 {
-                        expand_power(&b.algebraic_expand(), e)
-                    } else {
-                        self.clone()
-                    }
-                } else {
-                    self.clone()
+                match e.kind {
+                    ExprKind::Integer(ref e) if *e >= BigInt::from(2) => match e.to_i64() {
+                        Some(e) => expand_power(&b.algebraic_expand(), e),
+                        None => self.clone(),
+                    },
+                    _ => self.clone(),
                 }
             }
             _ => self.clone(),
@@ -83,7 +83,9 @@ fn expand_power(u: &Expr, n: i64) -> Expr {
 
 #[cfg(test)]
 mod tests {
-    use crate::{parser::AlgomyKernel, simplify::simplify};
+    use num::{traits::Pow, BigInt};
+
+    use crate::{expression::Expr, parser::AlgomyKernel};
 
     #[test]
     pub fn test_algebraic_expand() {
@@ -108,5 +110,23 @@ mod tests {
             expr.algebraic_expand(),
             kernel.parse_eval_line("1 + 2*x + x^2 + 5*x*y + 5*x^2*y + 4*x*y^2 + 10*x^2*y^2 + x*y^3 + 10*x^2*y^3 + 5*x^2*y^4 + x^2*y^5")
         );
+
+        let expr = kernel.parse_eval_line("Coefficient[Expand[(x+2)^40], x, 1]");
+        assert_eq!(expr, Expr::int(21990232555520i64));
+
+        // Coefficients and values beyond 64 bits
+        let x = Expr::symbol("x");
+        let expanded = kernel.parse_eval_line("(x+2)^40").algebraic_expand();
+        let coefficient = |n: u32| {
+            let binomial = (1..=n).fold(BigInt::from(1), |b, k| b * (41 - k) / k);
+            Expr::int(binomial * BigInt::from(2).pow(40 - n))
+        };
+        for n in [0, 13, 40] {
+            assert_eq!(expanded.coefficient_gpe(&x, n as i64), coefficient(n));
+        }
+        assert_eq!(
+            expanded.substitute(&x, &Expr::int(1)),
+            Expr::int(BigInt::from(3).pow(40u32))
+        );
     }
 }
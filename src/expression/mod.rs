@@ -1,20 +1,23 @@
 mod display;
+mod expand;
 mod numeric;
-mod polynomial;
 mod order;
+mod polynomial;
+mod real;
 mod utility;
-mod expand;
 
-pub use numeric::*;
-pub use polynomial::*;
+use num::{BigInt, BigRational, One};
+
+pub use real::{Real, MACHINE_PRECISION};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ExprKind {
     Undefined,
     Func(String),
     Symbol(String),
-    Integer(i64),
-    Fraction(i64, i64),
+    Integer(BigInt),
+    Fraction(BigInt, BigInt),
+    Real(Real),
     Gaussian,
     Sum,
     Difference,
@@ -28,6 +31,18 @@ pub enum ExprKind {
     Or,
     And,
 
+    Equal,
+    Unequal,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+
+    Rule,
+    RuleDelayed,
+
+    List,
+
     Set,
     Union,
     Intersection,
@@ -56,20 +71,39 @@ impl Expr {
         }
     }
 
-    pub fn int(n: i64) -> Expr {
+    /// The constant `Pi`.
+    pub fn pi() -> Expr {
+        Expr::symbol("Pi")
+    }
+
+    /// The constant `E`, the base of the natural logarithm.
+    pub fn e() -> Expr {
+        Expr::symbol("E")
+    }
+
+    pub fn int<T: Into<BigInt>>(n: T) -> Expr {
         Expr {
-            kind: ExprKind::Integer(n),
+            kind: ExprKind::Integer(n.into()),
             operands: vec![],
         }
     }
 
-    pub fn frac(n: i64, d: i64) -> Expr {
+    pub fn frac<T: Into<BigInt>, U: Into<BigInt>>(n: T, d: U) -> Expr {
         Expr {
-            kind: ExprKind::Fraction(n, d),
+            kind: ExprKind::Fraction(n.into(), d.into()),
             operands: vec![],
         }
     }
 
+    /// Converts a `BigRational` to an integer or a fraction in standard form.
+    pub fn from_rational(r: BigRational) -> Expr {
+        if r.denom().is_one() {
+            Expr::int(r.numer().clone())
+        } else {
+            Expr::frac(r.numer().clone(), r.denom().clone())
+        }
+    }
+
     pub fn power(base: Expr, exponent: Expr) -> Expr {
         Expr {
             kind: ExprKind::Power,
@@ -92,7 +126,7 @@ impl Expr {
     }
 
     pub fn product(operands: Vec<Expr>) -> Expr {
-        if operands.len() > 0 {
+        if !operands.is_empty() {
             Expr {
                 kind: ExprKind::Product,
                 operands,
@@ -110,7 +144,7 @@ impl Expr {
     }
 
     pub fn sum(operands: Vec<Expr>) -> Expr {
-        if operands.len() > 0 {
+        if !operands.is_empty() {
             Expr {
                 kind: ExprKind::Sum,
                 operands,
@@ -162,6 +196,27 @@ impl Expr {
         }
     }
 
+    pub fn relation(kind: ExprKind, lhs: Expr, rhs: Expr) -> Expr {
+        Expr {
+            kind,
+            operands: vec![lhs, rhs],
+        }
+    }
+
+    pub fn rule(lhs: Expr, rhs: Expr) -> Expr {
+        Expr {
+            kind: ExprKind::Rule,
+            operands: vec![lhs, rhs],
+        }
+    }
+
+    pub fn rule_delayed(lhs: Expr, rhs: Expr) -> Expr {
+        Expr {
+            kind: ExprKind::RuleDelayed,
+            operands: vec![lhs, rhs],
+        }
+    }
+
     pub fn set(mut expr: Vec<Expr>) -> Expr {
         expr.sort();
         expr.dedup();
@@ -170,5 +225,11 @@ impl Expr {
             operands: expr,
         }
     }
-}
 
+    pub fn list(operands: Vec<Expr>) -> Expr {
+        Expr {
+            kind: ExprKind::List,
+            operands,
+        }
+    }
+}
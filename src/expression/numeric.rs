@@ -1,3 +1,5 @@
+use num::{BigInt, BigRational, One, Signed, ToPrimitive, Zero};
+
 use crate::simplify;
 
 use super::{Expr, ExprKind};
@@ -5,33 +7,64 @@ use super::{Expr, ExprKind};
 impl Expr {
     pub fn is_positive_num(&self) -> bool {
         match self.kind {
-            ExprKind::Integer(n) => n > 0,
-            ExprKind::Fraction(n, d) => !((n > 0) ^ (d > 0)),
+            ExprKind::Integer(ref n) => n.is_positive(),
+            ExprKind::Fraction(ref n, ref d) => n.is_positive() == d.is_positive(),
+            ExprKind::Real(ref r) => !r.is_negative() && !r.is_zero(),
             _ => unreachable!(),
         }
     }
 
-    pub fn numerator_rne(&self) -> i64 {
+    /// Checks if `self` is a number equal to 0.
+    pub fn is_zero(&self) -> bool {
+        match self.kind {
+            ExprKind::Integer(ref n) | ExprKind::Fraction(ref n, _) => n.is_zero(),
+            ExprKind::Real(ref r) => r.is_zero(),
+            _ => false,
+        }
+    }
+
+    /// Checks if `self` is the integer 1.
+    pub fn is_one(&self) -> bool {
+        matches!(self.kind, ExprKind::Integer(ref n) if n.is_one())
+    }
+
+    /// Returns the value of an integer if it fits in an `i64`
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.kind {
+            ExprKind::Integer(ref n) => n.to_i64(),
+            _ => None,
+        }
+    }
+
+    pub fn numerator_rne(&self) -> BigInt {
         match self.kind {
-            ExprKind::Integer(n) => n,
-            ExprKind::Fraction(n, _) => n,
+            ExprKind::Integer(ref n) => n.clone(),
+            ExprKind::Fraction(ref n, _) => n.clone(),
             _ => unreachable!(),
         }
     }
 
-    pub fn denominator_rne(&self) -> i64 {
+    pub fn denominator_rne(&self) -> BigInt {
         match self.kind {
-            ExprKind::Integer(_) => 1,
-            ExprKind::Fraction(_, d) => d,
+            ExprKind::Integer(_) => BigInt::one(),
+            ExprKind::Fraction(_, ref d) => d.clone(),
             _ => unreachable!(),
         }
     }
 
+    /// Returns the value of an integer or fraction with non-zero denominator as a `BigRational`
+    pub fn rational_rne(&self) -> BigRational {
+        BigRational::new(self.numerator_rne(), self.denominator_rne())
+    }
+
     pub fn numerator(&self) -> Expr {
         match self.kind {
             ExprKind::Integer(_) | ExprKind::Fraction(_, _) => Expr::int(self.numerator_rne()),
+            ExprKind::Real(_) => self.clone(),
             ExprKind::Quotient => self.operands[0].clone(),
             ExprKind::Product => simplify(&self.operands.iter().map(|x| x.numerator()).product()),
+            // v^(-n) -> 1
+            ExprKind::Power if self.has_negative_exponent() => Expr::int(1),
             _ => self.clone(),
         }
     }
@@ -39,12 +72,24 @@ impl Expr {
     pub fn denominator(&self) -> Expr {
         match self.kind {
             ExprKind::Integer(_) | ExprKind::Fraction(_, _) => Expr::int(self.denominator_rne()),
+            ExprKind::Real(_) => Expr::int(1),
             ExprKind::Quotient => self.operands[1].clone(),
             ExprKind::Product => simplify(&self.operands.iter().map(|x| x.denominator()).product()),
-            _ => self.clone(),
+            // v^(-n) -> v^n
+            ExprKind::Power if self.has_negative_exponent() => simplify(&Expr::power(
+                self.base(),
+                Expr::times(Expr::int(-1), self.exponent()),
+            )),
+            _ => Expr::int(1),
         }
     }
 
+    /// Checks if `self` is a power with a negative numerical exponent.
+    fn has_negative_exponent(&self) -> bool {
+        let e = self.exponent();
+        e.is_real_number() && !e.is_positive_num() && !e.is_zero()
+    }
+
     pub fn base(&self) -> Expr {
         match self.kind {
             ExprKind::Power => self.operands[0].clone(),
@@ -62,7 +107,7 @@ impl Expr {
     /// Returns the real part of a gaussian number
     pub fn re(&self) -> Expr {
         match self.kind {
-            ExprKind::Integer(_) | ExprKind::Fraction(_, _) => self.clone(),
+            ExprKind::Integer(_) | ExprKind::Fraction(_, _) | ExprKind::Real(_) => self.clone(),
             ExprKind::Gaussian => self.operands[0].clone(),
             ExprKind::Sum => simplify(&self.operands.iter().map(|u| u.re()).sum()),
             ExprKind::Product => simplify(&self.operands.iter().map(|u| u.re()).product()),
@@ -73,7 +118,7 @@ impl Expr {
     /// Returns the imaginary part of a gaussian number
     pub fn im(&self) -> Expr {
         match self.kind {
-            ExprKind::Integer(_) | ExprKind::Fraction(_, _) => Expr::int(0),
+            ExprKind::Integer(_) | ExprKind::Fraction(_, _) | ExprKind::Real(_) => Expr::int(0),
             ExprKind::Gaussian => self.operands[1].clone(),
             ExprKind::Sum => simplify(&self.operands.iter().map(|u| u.im()).sum()),
             ExprKind::Product => simplify(&self.operands.iter().map(|u| u.im()).product()),
@@ -81,3 +126,28 @@ impl Expr {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    #[test]
+    fn test_numerator_denominator() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("2/3", "2", "3"),
+            ("x", "x", "1"),
+            ("Sin[x]", "Sin[x]", "1"),
+            ("x^(-2)", "1", "x^2"),
+            ("(x + 1)^(-1/2)", "1", "(x + 1)^(1/2)"),
+            ("x^2", "x^2", "1"),
+            ("2*x^2/(3*y^3)", "2*x^2", "3*y^3"),
+        ];
+        for (u, n, d) in cases {
+            let u = kernel.parse_eval_line(u);
+            assert_eq!(u.numerator(), kernel.parse_eval_line(n), "{}", u);
+            assert_eq!(u.denominator(), kernel.parse_eval_line(d), "{}", u);
+        }
+        assert_eq!(Expr::int(5).denominator(), Expr::int(1));
+    }
+}
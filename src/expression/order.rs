@@ -15,17 +15,30 @@ impl Ord for Expr {
             (
                 ExprKind::Integer(_) | ExprKind::Fraction(_, _),
                 ExprKind::Integer(_) | ExprKind::Fraction(_, _),
-            ) => {
-                let n1 = self.numerator_rne();
-                let d1 = self.denominator_rne();
-                let n2 = other.numerator_rne();
-                let d2 = other.denominator_rne();
-                (n1 * d2).cmp(&(n2 * d1))
+            ) => self.rational_rne().cmp(&other.rational_rne()),
+            (ExprKind::Real(r), ExprKind::Integer(_) | ExprKind::Fraction(_, _)) => {
+                r.to_rational().cmp(&other.rational_rne())
+            }
+            (ExprKind::Integer(_) | ExprKind::Fraction(_, _), ExprKind::Real(r)) => {
+                self.rational_rne().cmp(&r.to_rational())
             }
+            (ExprKind::Real(r1), ExprKind::Real(r2)) => r1.cmp(r2),
             (ExprKind::Gaussian, ExprKind::Gaussian) => match self.re().cmp(&other.re()) {
                 Ordering::Equal => self.im().cmp(&other.im()),
                 ord => ord,
             },
+            // 2 < 2 + I < 3
+            (
+                ExprKind::Gaussian,
+                ExprKind::Integer(_) | ExprKind::Fraction(_, _) | ExprKind::Real(_),
+            ) => match self.re().cmp(other) {
+                Ordering::Equal => self.im().cmp(&Expr::int(0)),
+                ord => ord,
+            },
+            (
+                ExprKind::Integer(_) | ExprKind::Fraction(_, _) | ExprKind::Real(_),
+                ExprKind::Gaussian,
+            ) => other.cmp(self).reverse(),
             (ExprKind::Boolean(b1), ExprKind::Boolean(b2)) => b1.cmp(b2),
             // 0..9 < A..Z < a..z
             (ExprKind::Symbol(ref s1), ExprKind::Symbol(ref s2)) => s1.cmp(s2),
@@ -35,7 +48,7 @@ impl Ord for Expr {
                 if self.operands[m] != other.operands[n] {
                     self.operands[m].cmp(&other.operands[n])
                 } else {
-                    for k in 1..m.min(n) {
+                    for k in 1..=m.min(n) {
                         if self.operands[m - k] != other.operands[n - k] {
                             return self.operands[m - k].cmp(&other.operands[n - k]);
                         }
@@ -56,7 +69,7 @@ impl Ord for Expr {
                 if self.operands[m] != other.operands[n] {
                     self.operands[m].cmp(&other.operands[n])
                 } else {
-                    for k in 1..m.min(n) {
+                    for k in 1..=m.min(n) {
                         if self.operands[m - k] != other.operands[n - k] {
                             return self.operands[m - k].cmp(&other.operands[n - k]);
                         }
@@ -64,6 +77,30 @@ impl Ord for Expr {
                     m.cmp(&n)
                 }
             }
+            (k1, k2) if self.is_relation() && other.is_relation() => self
+                .operands
+                .cmp(&other.operands)
+                .then_with(|| relation_rank(k1).cmp(&relation_rank(k2))),
+            (_, _) if self.is_rule() && other.is_rule() => {
+                self.operands.cmp(&other.operands).then_with(|| {
+                    other
+                        .kind
+                        .eq(&ExprKind::Rule)
+                        .cmp(&self.kind.eq(&ExprKind::Rule))
+                })
+            }
+            (_, ExprKind::List | ExprKind::Set) if self.is_rule() => Ordering::Less,
+            (_, _) if self.is_rule() => Ordering::Greater,
+            (ExprKind::List, ExprKind::List) | (ExprKind::Set, ExprKind::Set) => {
+                for (u, v) in self.operands.iter().zip(other.operands.iter()) {
+                    if u != v {
+                        return u.cmp(v);
+                    }
+                }
+                self.operands.len().cmp(&other.operands.len())
+            }
+            (ExprKind::List, ExprKind::Set) => Ordering::Less,
+            (ExprKind::List | ExprKind::Set, _) => Ordering::Greater,
             (ExprKind::Factorial, ExprKind::Factorial) => self.operands[0].cmp(&other.operands[0]),
             (ExprKind::Func(ref f1), ExprKind::Func(ref f2)) => {
                 let m = self.operands.len() - 1;
@@ -81,12 +118,20 @@ impl Ord for Expr {
                     m.cmp(&n)
                 }
             }
-            (ExprKind::Integer(_) | ExprKind::Fraction(_, _), _) => Ordering::Less,
+            (ExprKind::Integer(_) | ExprKind::Fraction(_, _) | ExprKind::Real(_), _) => {
+                Ordering::Less
+            }
             (ExprKind::Gaussian, _) => Ordering::Less,
             (ExprKind::Boolean(_), _) => Ordering::Less,
             (
                 ExprKind::And,
                 ExprKind::Or
+                | ExprKind::Equal
+                | ExprKind::Unequal
+                | ExprKind::Less
+                | ExprKind::LessEqual
+                | ExprKind::Greater
+                | ExprKind::GreaterEqual
                 | ExprKind::Not
                 | ExprKind::Product
                 | ExprKind::Sum
@@ -99,7 +144,13 @@ impl Ord for Expr {
             }),
             (
                 ExprKind::Or,
-                ExprKind::Not
+                ExprKind::Equal
+                | ExprKind::Unequal
+                | ExprKind::Less
+                | ExprKind::LessEqual
+                | ExprKind::Greater
+                | ExprKind::GreaterEqual
+                | ExprKind::Not
                 | ExprKind::Product
                 | ExprKind::Sum
                 | ExprKind::Factorial
@@ -109,6 +160,17 @@ impl Ord for Expr {
                 kind: ExprKind::Or,
                 operands: vec![other.clone()],
             }),
+            // Equations and inequalities come after algebraic expressions
+            (
+                _,
+                ExprKind::Not
+                | ExprKind::Product
+                | ExprKind::Power
+                | ExprKind::Sum
+                | ExprKind::Factorial
+                | ExprKind::Func(_)
+                | ExprKind::Symbol(_),
+            ) if self.is_relation() => Ordering::Greater,
             (
                 ExprKind::Not,
                 ExprKind::Product
@@ -154,7 +216,7 @@ impl Ord for Expr {
                 if f == s {
                     Ordering::Greater
                 } else {
-                    f.cmp(&s)
+                    f.cmp(s)
                 }
             }
             _ => match other.cmp(self) {
@@ -164,4 +226,49 @@ impl Ord for Expr {
             },
         }
     }
-}
\ No newline at end of file
+}
+
+/// Fixed order of the relational operators, to compare relations with equal operands.
+fn relation_rank(kind: &ExprKind) -> usize {
+    match kind {
+        ExprKind::Equal => 0,
+        ExprKind::Unequal => 1,
+        ExprKind::Less => 2,
+        ExprKind::LessEqual => 3,
+        ExprKind::Greater => 4,
+        ExprKind::GreaterEqual => 5,
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::expression::Expr;
+
+    #[test]
+    fn test_order() {
+        let (a, b, c) = (Expr::symbol("a"), Expr::symbol("b"), Expr::symbol("c"));
+        // a + c < b + c, which compares the first operands after equal last operands
+        let u = Expr::plus(a.clone(), c.clone());
+        let v = Expr::plus(b.clone(), c.clone());
+        assert_eq!(u.cmp(&v), Ordering::Less);
+        assert_eq!(v.cmp(&u), Ordering::Greater);
+        // a*c < b*c
+        let u = Expr::times(a.clone(), c.clone());
+        let v = Expr::times(b.clone(), c.clone());
+        assert_eq!(u.cmp(&v), Ordering::Less);
+        // c < a*c, since a product with more operands comes later
+        let u = Expr::product(vec![c.clone()]);
+        let v = Expr::times(a.clone(), c.clone());
+        assert_eq!(u.cmp(&v), Ordering::Less);
+        // Lists are compared operand by operand, then by length
+        let u = Expr::list(vec![Expr::int(1), Expr::int(2)]);
+        let v = Expr::list(vec![Expr::int(1), Expr::int(3)]);
+        assert_eq!(u.cmp(&v), Ordering::Less);
+        assert_eq!(Expr::list(vec![Expr::int(1)]).cmp(&u), Ordering::Less);
+        assert_eq!(u.cmp(&u.clone()), Ordering::Equal);
+        assert_eq!(u.cmp(&Expr::set(vec![Expr::int(1)])), Ordering::Less);
+    }
+}
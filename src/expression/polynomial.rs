@@ -6,7 +6,10 @@ impl Expr {
         match self.kind {
             ExprKind::Product => match self.operands[0].kind {
                 // TODO: We assume the coefficient is the leading term. This is probably not always true.
-                ExprKind::Integer(_) | ExprKind::Fraction(_, _) => self.operands[0].clone(),
+                ExprKind::Integer(_)
+                | ExprKind::Fraction(_, _)
+                | ExprKind::Real(_)
+                | ExprKind::Gaussian => self.operands[0].clone(),
                 _ => Expr::int(1),
             },
             _ => Expr::int(1),
@@ -18,7 +21,10 @@ impl Expr {
         match self.kind {
             ExprKind::Product => match self.operands[0].kind {
                 // TODO: We assume the coefficient is the leading term. This is probably not always true.
-                ExprKind::Integer(_) | ExprKind::Fraction(_, _) => {
+                ExprKind::Integer(_)
+                | ExprKind::Fraction(_, _)
+                | ExprKind::Real(_)
+                | ExprKind::Gaussian => {
                     if self.operands.len() == 2 {
                         self.operands[1].clone()
                     } else {
@@ -33,4 +39,4 @@ impl Expr {
             _ => self.clone(),
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,696 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use num::{BigInt, BigRational, Integer, One, Signed, ToPrimitive, Zero};
+
+use super::{Expr, ExprKind};
+
+/// Number of significant digits of reals which are not given an explicit precision.
+pub const MACHINE_PRECISION: u32 = 16;
+
+/// Number of extra digits carried in intermediate computations.
+const GUARD_DIGITS: u32 = 10;
+
+/// An arbitrary-precision real number `mantissa * 10^exponent`, accurate to `precision`
+/// significant digits. Reals are equal and ordered by value, regardless of their precision.
+#[derive(Clone, Debug)]
+pub struct Real {
+    mantissa: BigInt,
+    exponent: i64,
+    precision: u32,
+}
+
+fn pow10(n: u64) -> BigInt {
+    num::pow(BigInt::from(10), n as usize)
+}
+
+/// Number of decimal digits of `n`, where `0` has no digits.
+fn num_digits(n: &BigInt) -> i64 {
+    if n.is_zero() {
+        0
+    } else {
+        n.magnitude().to_string().len() as i64
+    }
+}
+
+/// Divides `a` by `b > 0`, rounding half away from zero.
+fn round_div(a: &BigInt, b: &BigInt) -> BigInt {
+    let two_a: BigInt = a * 2;
+    if a.is_negative() {
+        (two_a - b) / (b * 2)
+    } else {
+        (two_a + b) / (b * 2)
+    }
+}
+
+impl Real {
+    /// Creates the real `mantissa * 10^exponent`, rounded to `precision` significant digits.
+    pub fn new(mantissa: BigInt, exponent: i64, precision: u32) -> Real {
+        let precision = precision.max(1);
+        let mut mantissa = mantissa;
+        let mut exponent = exponent;
+        let excess = num_digits(&mantissa) - precision as i64;
+        if excess > 0 {
+            mantissa = round_div(&mantissa, &pow10(excess as u64));
+            exponent += excess;
+        }
+        if mantissa.is_zero() {
+            exponent = 0;
+        } else {
+            let ten = BigInt::from(10);
+            while mantissa.is_multiple_of(&ten) {
+                mantissa /= &ten;
+                exponent += 1;
+            }
+        }
+        Real {
+            mantissa,
+            exponent,
+            precision,
+        }
+    }
+
+    pub fn zero(precision: u32) -> Real {
+        Real::new(BigInt::zero(), 0, precision)
+    }
+
+    /// Rounds a rational number to a real with `precision` significant digits.
+    pub fn from_rational(r: &BigRational, precision: u32) -> Real {
+        if r.is_zero() {
+            return Real::zero(precision);
+        }
+        let shift = precision as i64 + 2 - (num_digits(r.numer()) - num_digits(r.denom()));
+        let mantissa = if shift >= 0 {
+            round_div(&(r.numer() * pow10(shift as u64)), r.denom())
+        } else {
+            round_div(r.numer(), &(r.denom() * pow10(shift.unsigned_abs())))
+        };
+        Real::new(mantissa, -shift, precision)
+    }
+
+    /// Returns the exact rational value of `self`.
+    pub fn to_rational(&self) -> BigRational {
+        if self.exponent >= 0 {
+            BigRational::from_integer(&self.mantissa * pow10(self.exponent as u64))
+        } else {
+            BigRational::new(self.mantissa.clone(), pow10(self.exponent.unsigned_abs()))
+        }
+    }
+
+    pub fn precision(&self) -> u32 {
+        self.precision
+    }
+
+    /// Returns `self` rounded to (at most) `precision` significant digits.
+    pub fn with_precision(&self, precision: u32) -> Real {
+        Real::new(self.mantissa.clone(), self.exponent, precision)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.mantissa.is_negative()
+    }
+
+    /// Position of the most significant digit, such that `10^(m-1) <= |self| < 10^m`.
+    fn magnitude(&self) -> i64 {
+        num_digits(&self.mantissa) + self.exponent
+    }
+
+    pub fn neg(&self) -> Real {
+        Real::new(-&self.mantissa, self.exponent, self.precision)
+    }
+
+    pub fn add(&self, other: &Real) -> Real {
+        let precision = self.precision.min(other.precision);
+        if self.is_zero() {
+            return other.with_precision(precision);
+        } else if other.is_zero() {
+            return self.with_precision(precision);
+        }
+        // Digits of the smaller operand beyond the precision do not affect the result
+        let gap = self.magnitude() - other.magnitude();
+        if gap > precision as i64 + 2 {
+            return self.with_precision(precision);
+        } else if -gap > precision as i64 + 2 {
+            return other.with_precision(precision);
+        }
+        let e = self.exponent.min(other.exponent);
+        let m = &self.mantissa * pow10((self.exponent - e) as u64)
+            + &other.mantissa * pow10((other.exponent - e) as u64);
+        Real::new(m, e, precision)
+    }
+
+    pub fn sub(&self, other: &Real) -> Real {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &Real) -> Real {
+        Real::new(
+            &self.mantissa * &other.mantissa,
+            self.exponent + other.exponent,
+            self.precision.min(other.precision),
+        )
+    }
+
+    /// Returns `self / other`, or `None` if `other` is zero.
+    pub fn div(&self, other: &Real) -> Option<Real> {
+        if other.is_zero() {
+            return None;
+        }
+        let precision = self.precision.min(other.precision);
+        let shift = (precision as i64 + 2 + num_digits(&other.mantissa)
+            - num_digits(&self.mantissa))
+        .max(0);
+        let m = round_div(
+            &(&self.mantissa * pow10(shift as u64) * other.mantissa.signum()),
+            &other.mantissa.abs(),
+        );
+        Some(Real::new(
+            m,
+            self.exponent - other.exponent - shift,
+            precision,
+        ))
+    }
+
+    /// Returns `self^n`, or `None` if `self` is zero and `n <= 0`.
+    pub fn powi(&self, n: i64) -> Option<Real> {
+        if n <= 0 && self.is_zero() {
+            return None;
+        }
+        let mut base = self.with_working_precision(self.precision + GUARD_DIGITS);
+        let mut result = Real::new(BigInt::one(), 0, base.precision);
+        let mut e = n.unsigned_abs();
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result.mul(&base);
+            }
+            base = base.mul(&base);
+            e >>= 1;
+        }
+        if n < 0 {
+            result = Real::new(BigInt::one(), 0, result.precision).div(&result)?;
+        }
+        Some(result.with_precision(self.precision))
+    }
+
+    /// Returns `self^other` for a positive base, or `None` otherwise.
+    pub fn pow(&self, other: &Real) -> Option<Real> {
+        if self.is_negative() || self.is_zero() {
+            return None;
+        }
+        let precision = self.precision.min(other.precision);
+        let working = precision + GUARD_DIGITS;
+        let log = self.with_working_precision(working).ln()?;
+        Some(
+            log.mul(&other.with_working_precision(working))
+                .exp()
+                .with_precision(precision),
+        )
+    }
+
+    /// Returns the `n`-th root of `self`, or `None` if it is not real.
+    pub fn root(&self, n: u32) -> Option<Real> {
+        if n == 0 || (self.is_negative() && n.is_multiple_of(2)) {
+            return None;
+        } else if self.is_zero() {
+            return Some(self.clone());
+        }
+        // Shift the mantissa such that the exponent is divisible by n and the root has enough digits
+        let wanted = (self.precision + GUARD_DIGITS) as i64 * n as i64;
+        let mut shift = (wanted - num_digits(&self.mantissa)).max(0);
+        shift += (self.exponent - shift).mod_floor(&(n as i64));
+        let m = (&self.mantissa * pow10(shift as u64)).nth_root(n);
+        Some(Real::new(
+            m,
+            (self.exponent - shift) / n as i64,
+            self.precision,
+        ))
+    }
+
+    /// Returns `self` with its precision set to `precision`, without rounding.
+    fn with_working_precision(&self, precision: u32) -> Real {
+        Real {
+            precision,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the value of `self` as a fixed-point number scaled by `10^digits`.
+    fn to_fixed(&self, digits: u32) -> BigInt {
+        let e = self.exponent + digits as i64;
+        if e >= 0 {
+            &self.mantissa * pow10(e as u64)
+        } else {
+            round_div(&self.mantissa, &pow10(e.unsigned_abs()))
+        }
+    }
+
+    /// Returns the exponential function `e^self`.
+    pub fn exp(&self) -> Real {
+        let precision = self.precision;
+        if self.is_negative() {
+            let e = self.neg().exp();
+            let one = Real::new(BigInt::one(), 0, precision);
+            return one.div(&e).unwrap();
+        }
+        // Reduce the argument by halving until it is smaller than 1/2
+        let halvings = (self.magnitude() as f64 * 10f64.log2()).ceil().max(0.0) as u32 + 1;
+        let digits = precision + GUARD_DIGITS + halvings / 3;
+        let scale = pow10(digits as u64);
+        let x = self.to_fixed(digits) >> halvings;
+        let mut sum = scale.clone();
+        let mut term = scale.clone();
+        let mut i = 1u32;
+        while !term.is_zero() {
+            term = &term * &x / (&scale * i);
+            sum += &term;
+            i += 1;
+        }
+        for _ in 0..halvings {
+            sum = &sum * &sum / &scale;
+        }
+        Real::new(sum, -(digits as i64), precision)
+    }
+
+    /// Returns the natural logarithm of `self`, or `None` if `self <= 0`.
+    pub fn ln(&self) -> Option<Real> {
+        if self.is_negative() || self.is_zero() {
+            return None;
+        }
+        // ln(m * 10^e) = ln(y) + j*ln(2) + e*ln(10) where m = y * 2^j and 1/2 <= y < 1
+        let j = self.mantissa.bits() as i64;
+        let extra =
+            (num_digits(&BigInt::from(j)) + num_digits(&BigInt::from(self.exponent))) as u32;
+        let digits = self.precision + GUARD_DIGITS + extra;
+        let scale = pow10(digits as u64);
+        let y = (&self.mantissa * &scale) >> j;
+        let ln_y = 2 * atanh_fixed(&(&y - &scale), &(&y + &scale), &scale);
+        let ln2 = ln2_fixed(&scale);
+        let ln10 = 3 * &ln2 + 2 * atanh_fixed(&BigInt::one(), &BigInt::from(9), &scale);
+        let sum = ln_y + j * ln2 + self.exponent * ln10;
+        Some(Real::new(sum, -(digits as i64), self.precision))
+    }
+
+    /// Returns `pi` with `precision` significant digits.
+    pub fn pi(precision: u32) -> Real {
+        let digits = precision + GUARD_DIGITS;
+        let pi = pi_fixed(&pow10(digits as u64));
+        Real::new(pi, -(digits as i64), precision)
+    }
+
+    /// Returns `(sin(self), cos(self))`.
+    pub fn sin_cos(&self) -> (Real, Real) {
+        // Reduce the argument to |r| <= pi/4 with x = r + k*pi/2
+        let digits = self.precision + GUARD_DIGITS + self.magnitude().max(0) as u32;
+        let scale = pow10(digits as u64);
+        let x = self.to_fixed(digits);
+        let half_pi = pi_fixed(&scale) / 2;
+        let k = round_div(&x, &half_pi);
+        let r = x - &k * &half_pi;
+        let (sin, cos) = sin_cos_fixed(&r, &scale);
+        let (sin, cos) = match k.mod_floor(&BigInt::from(4)).to_u32().unwrap() {
+            0 => (sin, cos),
+            1 => (cos, -sin),
+            2 => (-sin, -cos),
+            _ => (-cos, sin),
+        };
+        (
+            Real::new(sin, -(digits as i64), self.precision),
+            Real::new(cos, -(digits as i64), self.precision),
+        )
+    }
+
+    /// Returns `(sin(r*pi), cos(r*pi))` for a rational `r`, where the values which vanish are
+    /// exactly zero.
+    pub fn sin_cos_pi(r: &BigRational, precision: u32) -> (Real, Real) {
+        let working = precision + GUARD_DIGITS;
+        let (sin, cos) = Real::from_rational(r, working)
+            .mul(&Real::pi(working))
+            .sin_cos();
+        let half_turns = r * BigInt::from(2);
+        match (r.is_integer(), half_turns.is_integer()) {
+            (true, _) => (Real::zero(precision), cos.with_precision(precision)),
+            (false, true) => (sin.with_precision(precision), Real::zero(precision)),
+            _ => (sin.with_precision(precision), cos.with_precision(precision)),
+        }
+    }
+
+    pub fn sin(&self) -> Real {
+        self.sin_cos().0
+    }
+
+    pub fn cos(&self) -> Real {
+        self.sin_cos().1
+    }
+
+    /// Returns `tan(self)`, or `None` if the cosine vanishes.
+    pub fn tan(&self) -> Option<Real> {
+        let (sin, cos) = self
+            .with_working_precision(self.precision + GUARD_DIGITS)
+            .sin_cos();
+        Some(sin.div(&cos)?.with_precision(self.precision))
+    }
+
+    /// Returns the inverse tangent `atan(self)` in `(-pi/2, pi/2)`.
+    pub fn atan(&self) -> Real {
+        let digits = self.precision + GUARD_DIGITS;
+        let scale = pow10(digits as u64);
+        let x = self.to_fixed(digits);
+        let atan = if x.magnitude() > scale.magnitude() {
+            // atan(x) = sign(x)*pi/2 - atan(1/x)
+            let half_pi: BigInt = pi_fixed(&scale) / 2;
+            let y = atan_fixed_reduced(&(&scale * &scale / &x), &scale);
+            if x.is_negative() {
+                -half_pi - y
+            } else {
+                half_pi - y
+            }
+        } else {
+            atan_fixed_reduced(&x, &scale)
+        };
+        Real::new(atan, -(digits as i64), self.precision)
+    }
+
+    /// Returns the inverse sine `asin(self)` in `[-pi/2, pi/2]`, or `None` if `|self| > 1`.
+    pub fn asin(&self) -> Option<Real> {
+        let working = self.with_working_precision(self.precision + GUARD_DIGITS);
+        let one = Real::new(BigInt::one(), 0, working.precision);
+        let c = one.sub(&working.mul(&working));
+        if c.is_negative() {
+            return None;
+        } else if c.is_zero() {
+            // asin(1) = pi/2, asin(-1) = -pi/2
+            let half_pi =
+                Real::pi(self.precision).div(&Real::new(BigInt::from(2), 0, self.precision))?;
+            return Some(if self.is_negative() {
+                half_pi.neg()
+            } else {
+                half_pi
+            });
+        }
+        // asin(x) = atan(x/sqrt(1 - x^2))
+        Some(
+            working
+                .div(&c.root(2)?)?
+                .atan()
+                .with_precision(self.precision),
+        )
+    }
+
+    /// Returns the inverse cosine `acos(self)` in `[0, pi]`, or `None` if `|self| > 1`.
+    pub fn acos(&self) -> Option<Real> {
+        let working = self.with_working_precision(self.precision + GUARD_DIGITS);
+        let half_pi =
+            Real::pi(working.precision).div(&Real::new(BigInt::from(2), 0, working.precision))?;
+        Some(half_pi.sub(&working.asin()?).with_precision(self.precision))
+    }
+
+    /// Returns `(e^self + s*e^(-self))/2` with `s = 1` or `s = -1`.
+    fn exp_combination(&self, s: i64) -> Real {
+        let working = self.with_working_precision(self.precision + GUARD_DIGITS);
+        let a = working.exp();
+        let b = working.neg().exp();
+        let b = if s < 0 { b.neg() } else { b };
+        a.add(&b)
+            .div(&Real::new(BigInt::from(2), 0, working.precision))
+            .unwrap()
+            .with_precision(self.precision)
+    }
+
+    pub fn sinh(&self) -> Real {
+        self.exp_combination(-1)
+    }
+
+    pub fn cosh(&self) -> Real {
+        self.exp_combination(1)
+    }
+
+    pub fn tanh(&self) -> Real {
+        let working = self.with_working_precision(self.precision + GUARD_DIGITS);
+        working
+            .sinh()
+            .div(&working.cosh())
+            .unwrap()
+            .with_precision(self.precision)
+    }
+}
+
+/// Returns `pi = 16*atan(1/5) - 4*atan(1/239)` as a fixed-point number with the given `scale`.
+fn pi_fixed(scale: &BigInt) -> BigInt {
+    16 * atan_fixed(&BigInt::one(), &BigInt::from(5), scale)
+        - 4 * atan_fixed(&BigInt::one(), &BigInt::from(239), scale)
+}
+
+/// Returns `atan(p/q)` as a fixed-point number with the given `scale`, where `|p/q| < 1`.
+fn atan_fixed(p: &BigInt, q: &BigInt, scale: &BigInt) -> BigInt {
+    let mut power = scale * p / q;
+    let q2 = q * q;
+    let p2 = p * p;
+    let mut sum = BigInt::zero();
+    let mut k = 1u32;
+    while !power.is_zero() {
+        if k % 4 == 1 {
+            sum += &power / k;
+        } else {
+            sum -= &power / k;
+        }
+        power = power * &p2 / &q2;
+        k += 2;
+    }
+    sum
+}
+
+/// Returns `atan(x)` for a fixed-point number `|x| <= 1` with the given `scale`.
+fn atan_fixed_reduced(x: &BigInt, scale: &BigInt) -> BigInt {
+    // atan(x) = 2*atan(x/(1 + sqrt(1 + x^2))), applied until the series converges quickly
+    const HALVINGS: u32 = 3;
+    let mut x = x.clone();
+    for _ in 0..HALVINGS {
+        let root = (scale * scale + &x * &x).sqrt();
+        x = &x * scale / (scale + root);
+    }
+    atan_fixed(&x, scale, scale) << HALVINGS
+}
+
+/// Returns `(sin(x), cos(x))` for a fixed-point number `x` with the given `scale`, where `|x| <= 1`.
+fn sin_cos_fixed(x: &BigInt, scale: &BigInt) -> (BigInt, BigInt) {
+    let mut sin = BigInt::zero();
+    let mut cos = BigInt::zero();
+    let mut term = scale.clone();
+    let mut k = 0u32;
+    while !term.is_zero() {
+        match k % 4 {
+            0 => cos += &term,
+            1 => sin += &term,
+            2 => cos -= &term,
+            _ => sin -= &term,
+        }
+        k += 1;
+        term = &term * x / (scale * k);
+    }
+    (sin, cos)
+}
+
+/// Returns `atanh(p/q)` as a fixed-point number with the given `scale`, where `|p/q| < 1`.
+fn atanh_fixed(p: &BigInt, q: &BigInt, scale: &BigInt) -> BigInt {
+    let mut power = scale * p / q;
+    let q2 = q * q;
+    let p2 = p * p;
+    let mut sum = BigInt::zero();
+    let mut k = 1u32;
+    while !power.is_zero() {
+        sum += &power / k;
+        power = power * &p2 / &q2;
+        k += 2;
+    }
+    sum
+}
+
+/// Returns `ln(2) = 2*atanh(1/3)` as a fixed-point number with the given `scale`.
+fn ln2_fixed(scale: &BigInt) -> BigInt {
+    2 * atanh_fixed(&BigInt::one(), &BigInt::from(3), scale)
+}
+
+impl PartialEq for Real {
+    fn eq(&self, other: &Self) -> bool {
+        // The mantissa has no trailing zeros, so equal values have equal representations
+        self.mantissa == other.mantissa && self.exponent == other.exponent
+    }
+}
+
+impl Eq for Real {}
+
+impl PartialOrd for Real {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Real {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_rational().cmp(&other.to_rational())
+    }
+}
+
+impl FromStr for Real {
+    type Err = ();
+
+    /// Parses a decimal literal such as `3.14`, `2.` or `1e-5`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (digits, exponent) = match s.find(['e', 'E']) {
+            Some(i) => (&s[..i], s[i + 1..].parse::<i64>().map_err(|_| ())?),
+            None => (s, 0),
+        };
+        let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+        let mantissa = format!("{}{}", int, frac)
+            .parse::<BigInt>()
+            .map_err(|_| ())?;
+        let significant = num_digits(&mantissa) as u32;
+        Ok(Real::new(
+            mantissa,
+            exponent - frac.len() as i64,
+            significant.max(MACHINE_PRECISION),
+        ))
+    }
+}
+
+impl Display for Real {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0.");
+        }
+        let sign = if self.is_negative() { "-" } else { "" };
+        let digits = self.mantissa.magnitude().to_string();
+        let point = self.magnitude();
+        if !(-6..=21).contains(&point) {
+            let (first, rest) = digits.split_at(1);
+            write!(f, "{}{}.{}e{}", sign, first, rest, point - 1)
+        } else if point <= 0 {
+            write!(
+                f,
+                "{}0.{}{}",
+                sign,
+                "0".repeat(point.unsigned_abs() as usize),
+                digits
+            )
+        } else if point as usize >= digits.len() {
+            write!(
+                f,
+                "{}{}{}.",
+                sign,
+                digits,
+                "0".repeat(point as usize - digits.len())
+            )
+        } else {
+            let (int, frac) = digits.split_at(point as usize);
+            write!(f, "{}{}.{}", sign, int, frac)
+        }
+    }
+}
+
+impl Expr {
+    pub fn real(r: Real) -> Expr {
+        Expr {
+            kind: ExprKind::Real(r),
+            operands: vec![],
+        }
+    }
+
+    /// Converts an integer, fraction or real to a real with `precision` significant digits.
+    /// Reals with a lower precision keep their precision.
+    pub fn to_real(&self, precision: u32) -> Real {
+        match self.kind {
+            ExprKind::Real(ref r) => r.clone(),
+            ExprKind::Integer(_) | ExprKind::Fraction(_, _) => {
+                Real::from_rational(&self.rational_rne(), precision)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigInt, BigRational};
+
+    use super::Real;
+
+    #[test]
+    fn test_real_arithmetic() {
+        let a: Real = "1.5".parse().unwrap();
+        let b: Real = "0.25".parse().unwrap();
+        assert_eq!(a.add(&b).to_string(), "1.75");
+        assert_eq!(a.mul(&b).to_string(), "0.375");
+        assert_eq!(a.div(&b).unwrap().to_string(), "6.");
+        assert_eq!(b.sub(&a).to_string(), "-1.25");
+        assert_eq!("1e-5".parse::<Real>().unwrap().to_string(), "0.00001");
+        assert_eq!("2.5e30".parse::<Real>().unwrap().to_string(), "2.5e30");
+
+        let third = Real::from_rational(&BigRational::new(BigInt::from(1), BigInt::from(3)), 20);
+        assert_eq!(third.to_string(), "0.33333333333333333333");
+    }
+
+    #[test]
+    fn test_real_functions() {
+        let two = Real::new(BigInt::from(2), 0, 30);
+        assert_eq!(
+            two.root(2).unwrap().to_string(),
+            "1.41421356237309504880168872421"
+        );
+        assert_eq!(
+            Real::new(BigInt::from(1), 0, 30).exp().to_string(),
+            "2.71828182845904523536028747135"
+        );
+        assert_eq!(
+            two.ln().unwrap().to_string(),
+            "0.693147180559945309417232121458"
+        );
+        assert_eq!(
+            two.pow(&Real::new(BigInt::from(5), -1, 30)).unwrap(),
+            two.root(2).unwrap()
+        );
+        assert_eq!(two.powi(-2).unwrap().to_string(), "0.25");
+        assert_eq!(Real::pi(20).to_string(), "3.1415926535897932385");
+        assert_eq!(
+            Real::new(BigInt::from(100), 0, 20).sin().to_string(),
+            "-0.50636564110975879366"
+        );
+        assert_eq!(
+            Real::new(BigInt::from(5), -1, 20).asin().unwrap(),
+            Real::pi(20)
+                .div(&Real::new(BigInt::from(6), 0, 20))
+                .unwrap()
+        );
+        assert_eq!(two.asin(), None);
+
+        let rat = |n: i64, d: i64| BigRational::new(BigInt::from(n), BigInt::from(d));
+        let (sin, cos) = Real::sin_cos_pi(&rat(1, 2), 20);
+        assert_eq!(
+            (sin.to_string(), cos.to_string()),
+            ("1.".to_owned(), "0.".to_owned())
+        );
+        let (sin, cos) = Real::sin_cos_pi(&rat(-3, 1), 20);
+        assert_eq!(
+            (sin.to_string(), cos.to_string()),
+            ("0.".to_owned(), "-1.".to_owned())
+        );
+        let (sin, cos) = Real::sin_cos_pi(&rat(1, 3), 16);
+        assert_eq!(sin.to_string(), "0.8660254037844386");
+        assert_eq!(cos.to_string(), "0.5");
+    }
+
+    #[test]
+    fn test_real_equality() {
+        // Equality and order compare values, not precisions
+        let a: Real = "1.5".parse().unwrap();
+        let b = Real::new(BigInt::from(15), -1, 30);
+        assert_eq!(a, b);
+        assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal);
+        assert_eq!(Real::zero(5), Real::zero(20));
+        assert_eq!(Real::new(BigInt::from(1500), -3, 10), a);
+        assert_ne!(a, "1.50001".parse().unwrap());
+        assert!(a < "1.50001".parse().unwrap());
+    }
+}
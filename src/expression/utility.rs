@@ -18,6 +18,11 @@ impl Expr {
         Expr::bool(Expr::free_of_bool(self, t))
     }
 
+    /// Checks whether `t` is not equal to any sub-expression of `self`.
+    pub fn is_free_of(&self, t: &Expr) -> bool {
+        Expr::free_of_bool(self, t)
+    }
+
     fn free_of_bool(u: &Expr, t: &Expr) -> bool {
         if u == t {
             false
@@ -37,26 +42,37 @@ impl Expr {
             simplify(&self.map(|v| v.substitute(t, s)))
         }
     }
+
+    /// Substitutes every sub-expression of `self` equal to the left-hand side of one of the
+    /// rules `t -> s` with the right-hand side `s`. All rules are applied simultaneously, so
+    /// `{x -> y, y -> x}` swaps `x` and `y`.
+    pub fn substitute_rules(&self, rules: &[Expr]) -> Expr {
+        if let Some(rule) = rules.iter().find(|r| r.operands[0] == *self) {
+            rule.operands[1].clone()
+        } else if self.is_atomic() {
+            self.clone()
+        } else {
+            simplify(&self.map(|v| v.substitute_rules(rules)))
+        }
+    }
 }
 
 impl Expr {
     pub fn is_undefined(&self) -> bool {
-        match self.kind {
-            ExprKind::Undefined => true,
-            _ => false,
-        }
+        matches!(self.kind, ExprKind::Undefined)
     }
 
     pub fn is_atomic(&self) -> bool {
-        match self.kind {
-            ExprKind::Undefined => true,
-            ExprKind::Symbol(_) => true,
-            ExprKind::Integer(_) => true,
-            ExprKind::Fraction(_, _) => true,
-            ExprKind::Gaussian => true,
-            ExprKind::Boolean(_) => true,
-            _ => false,
-        }
+        matches!(
+            self.kind,
+            ExprKind::Undefined
+                | ExprKind::Symbol(_)
+                | ExprKind::Integer(_)
+                | ExprKind::Fraction(_, _)
+                | ExprKind::Real(_)
+                | ExprKind::Gaussian
+                | ExprKind::Boolean(_)
+        )
     }
 
     pub fn num_operands(&self) -> usize {
@@ -91,3 +107,53 @@ impl Product for Expr {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    #[test]
+    fn test_substitute_rules() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("x^2 + 1", "{x -> 2}", "5"),
+            ("x + y", "{x -> 1, y -> 2}", "3"),
+            ("{x, y}", "{x -> y, y -> x}", "{y, x}"),
+            ("x*y^2", "{x -> y, y -> x}", "y*x^2"),
+            ("f[x]", "{f[x] :> a}", "a"),
+            ("f[x] + x", "{x -> 2}", "f[2] + 2"),
+            ("x + y", "{z -> 1}", "x + y"),
+            ("x + y", "{}", "x + y"),
+        ];
+        for (u, rules, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            let rules = kernel.parse_eval_line(rules);
+            assert_eq!(
+                u.substitute_rules(&rules.operands),
+                kernel.parse_eval_line(expected),
+                "{} with {}",
+                u,
+                rules
+            );
+        }
+
+        // The rules of a solution
+        let solution = kernel.parse_eval_line("Solve[{x + y == 3, x - y == 1}, {x, y}][[1]]");
+        assert_eq!(
+            kernel
+                .parse_eval_line("{x + y, x - y}")
+                .substitute_rules(&solution.operands),
+            Expr::list(vec![Expr::int(3), Expr::int(1)])
+        );
+
+        // Substitute with a rule or a list of rules, and otherwise unevaluated
+        assert_eq!(
+            kernel.parse_eval_line("Substitute[x^2 + 1, x -> 2]"),
+            Expr::int(5)
+        );
+        assert_eq!(
+            kernel.parse_eval_line("Substitute[x, {1, 2}]").to_string(),
+            "Substitute[x, {1, 2}]"
+        );
+    }
+}
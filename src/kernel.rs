@@ -1,37 +1,102 @@
+use std::rc::Rc;
+
 use crate::{
-    expression::Expr,
-    parser::{AlgomyKernel, Assignment, Line},
+    error::Error,
+    expression::{Expr, ExprKind},
+    message::Message,
+    parser::{AlgomyKernel, Assignment, Definition, Line},
+    pattern::pattern_variables,
     simplify,
 };
 
 impl AlgomyKernel {
-    pub fn evaluate_line(&mut self, line: Line) -> Option<Expr> {
+    pub fn evaluate_line(&mut self, line: Line) -> Result<Option<Expr>, Error> {
         match line {
             Line::Expr(mut expr) => {
                 for Assignment { var, val } in self.assignments.iter() {
                     expr = expr.substitute(var, val);
                 }
-                Some(simplify(&expr))
+                Ok(Some(self.simplify(&expr)?))
             }
             Line::Assignment(Assignment { var, mut val }) => {
-                for Assignment { var: a_var, val: a_val } in self.assignments.iter() {
+                for Assignment {
+                    var: a_var,
+                    val: a_val,
+                } in self.assignments.iter()
+                {
                     val = val.substitute(a_var, a_val);
                 }
-                self.add_assignment(var, simplify(&val));
-                None
+                let val = self.simplify(&val)?;
+                self.add_assignment(var, val);
+                Ok(None)
             }
             Line::DelayedAssignment(Assignment { var, val }) => {
                 self.add_assignment(var, val);
-                None
+                Ok(None)
+            }
+            Line::Definition(Definition { lhs, rhs }) => {
+                let rhs = self.simplify(&self.substitute_assignments(&lhs, &rhs))?;
+                self.add_definition(lhs, rhs);
+                Ok(None)
+            }
+            Line::DelayedDefinition(Definition { lhs, rhs }) => {
+                self.add_definition(lhs, rhs);
+                Ok(None)
             }
-            Line::None => None,
+            Line::None => Ok(None),
         }
     }
 
     #[allow(unused)]
     pub fn parse_eval_line(&mut self, source: &str) -> Expr {
         let line = self.parse_line(source).unwrap();
-        self.evaluate_line(line).unwrap()
+        self.evaluate_line(line).unwrap().unwrap()
+    }
+
+    /// Returns the messages emitted since the last call, e.g. `Power::infy` for a division by
+    /// zero. Calls of builtin functions with the wrong number or type of arguments, such as
+    /// `Coefficient::argn`, are not messages: they stop the evaluation and are returned as the
+    /// [`Error`] of [`evaluate_line`](Self::evaluate_line) instead.
+    pub fn take_messages(&mut self) -> Vec<Message> {
+        std::mem::take(&mut self.context.messages)
+    }
+
+    /// Simplifies `expr` in the kernel's context, with the user-defined functions in scope, and
+    /// keeps the emitted messages. Returns the first error raised by a builtin function.
+    fn simplify(&mut self, expr: &Expr) -> Result<Expr, Error> {
+        let definitions: Vec<Definition> = self
+            .definitions
+            .iter()
+            .map(|Definition { lhs, rhs }| Definition {
+                lhs: lhs.clone(),
+                rhs: self.substitute_assignments(lhs, rhs),
+            })
+            .collect();
+        self.context.definitions = Rc::new(definitions);
+        self.context.depth = 0;
+        self.context.aborted = false;
+        let result = self.context.enter(|| simplify(expr));
+        match self.context.error.take() {
+            Some(error) => Err(error),
+            None => Ok(result),
+        }
+    }
+
+    /// Substitutes the assigned variables in the right-hand side of the definition of `lhs`,
+    /// except for its pattern variables.
+    fn substitute_assignments(&self, lhs: &Expr, rhs: &Expr) -> Expr {
+        let patterns = pattern_variables(lhs);
+        self.assignments
+            .iter()
+            .filter(|a| !patterns.contains(&a.var))
+            .fold(rhs.clone(), |rhs, a| rhs.substitute(&a.var, &a.val))
+    }
+
+    fn add_definition(&mut self, lhs: Expr, rhs: Expr) {
+        match self.definitions.iter_mut().find(|d| d.lhs == lhs) {
+            Some(definition) => definition.rhs = rhs,
+            None => self.definitions.push(Definition { lhs, rhs }),
+        }
     }
 
     fn add_assignment(&mut self, var: Expr, val: Expr) {
@@ -52,6 +117,7 @@ impl AlgomyKernel {
 
     pub fn clear_session(&mut self) {
         self.assignments.clear();
+        self.definitions.clear();
     }
 
     pub fn clear_variable(&mut self, var: &Expr) {
@@ -63,5 +129,8 @@ impl AlgomyKernel {
         if let Some((i, _)) = idx {
             self.assignments.remove(i);
         }
+        // Clear the definitions of a function with the same name
+        self.definitions
+            .retain(|d| !matches!((&d.lhs.kind, &var.kind), (ExprKind::Func(f), ExprKind::Symbol(s)) if f == s));
     }
 }
@@ -2,16 +2,23 @@ use std::{fs, io::Write};
 
 use parser::AlgomyKernel;
 
-use crate::{parser::Line, simplify::simplify};
+use crate::{error::Span, parser::Line, simplify::simplify};
 use clap::Parser as ClapParser;
 
+mod calculus;
 mod classify;
+mod context;
+mod error;
 mod evaluate;
 mod expression;
 mod kernel;
+mod matrix;
+mod message;
 mod parser;
+mod pattern;
 mod polynomial;
 mod simplify;
+mod solve;
 
 extern crate pest;
 #[macro_use]
@@ -30,20 +37,29 @@ fn main() {
     let mut kernel = AlgomyKernel::new();
 
     if let Some(path) = args.input {
-        let program = fs::read_to_string(path).expect("Unable to read file");
+        let program = match fs::read_to_string(&path) {
+            Ok(program) => program,
+            Err(err) => {
+                eprintln!("Unable to read {}: {}", path, err);
+                return;
+            }
+        };
 
         match kernel.parse_program(program.as_str()) {
             Ok(lines) => {
-                for line in lines {
-                    if let Some(result) = kernel.evaluate_line(line) {
-                        println!("{}", result);
+                for (line, span) in lines {
+                    let result = kernel.evaluate_line(line);
+                    for message in kernel.take_messages() {
+                        eprintln!("{}", message);
+                    }
+                    match result {
+                        Ok(Some(result)) => println!("{}", result),
+                        Ok(None) => {}
+                        Err(err) => eprintln!("{}", err.or_span(span).render(&program)),
                     }
                 }
             }
-            Err(err) => {
-                eprintln!("Interpreter Error:");
-                eprintln!("{}", err.to_string())
-            }
+            Err(err) => eprintln!("{}", err.render(&program)),
         }
     } else {
         // REPL mode
@@ -59,22 +75,27 @@ fn main() {
             match args[..] {
                 [":Exit"] => break,
                 [":ClearSession"] => kernel.clear_session(),
-                [":Clear", var] => {
-                    if let Line::Expr(var) = kernel.parse_line(var).unwrap() {
-                        kernel.clear_variable(&var);
+                [":Clear", var] => match kernel.parse_line(var) {
+                    Ok(Line::Expr(var)) => kernel.clear_variable(&var),
+                    Ok(_) => eprintln!("Expected a variable to clear"),
+                    Err(err) => eprintln!("{}", err.render(var)),
+                },
+                _ => {
+                    let result = kernel
+                        .parse_line(input.as_str())
+                        .and_then(|line| kernel.evaluate_line(line));
+                    for message in kernel.take_messages() {
+                        eprintln!("{}", message);
                     }
-                }
-                _ => match kernel.parse_line(input.as_str()) {
-                    Ok(line) => {
-                        if let Some(result) = kernel.evaluate_line(line) {
-                            println!("{}", result);
+                    match result {
+                        Ok(Some(result)) => println!("{}", result),
+                        Ok(None) => {}
+                        Err(err) => {
+                            let input = input.trim_end();
+                            eprintln!("{}", err.or_span(Span::new(0, input.len())).render(input))
                         }
                     }
-                    Err(err) => {
-                        eprintln!("Interpreter Error:");
-                        eprintln!("{}", err.to_string())
-                    }
-                },
+                }
             }
         }
     }
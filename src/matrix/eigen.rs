@@ -0,0 +1,157 @@
+use crate::{expression::Expr, simplify, solve::polynomial_roots};
+
+use super::{det, null_space};
+
+/// Computes the characteristic polynomial `Det[m - x*I]` of a square matrix. Returns `None` if
+/// the matrix is not square.
+pub fn characteristic_polynomial(m: &[Vec<Expr>], x: &Expr) -> Option<Expr> {
+    Some(det(&shift(m, x))?.algebraic_expand())
+}
+
+/// Computes the eigenvalues of a square matrix as the exact roots of its characteristic
+/// polynomial, repeated according to their algebraic multiplicity. Returns `None` if the matrix
+/// is not square or if not all roots of the characteristic polynomial can be found.
+pub fn eigenvalues(m: &[Vec<Expr>]) -> Option<Vec<Expr>> {
+    Some(
+        distinct_eigenvalues(m)?
+            .into_iter()
+            .flat_map(|(r, multiplicity)| std::iter::repeat_n(r, multiplicity))
+            .collect(),
+    )
+}
+
+/// Computes a basis of eigenvectors for every distinct eigenvalue of a square matrix, in the
+/// order of [`eigenvalues`]. Returns `None` if not all eigenvalues can be found, or if the
+/// eigenspace of an eigenvalue cannot be computed exactly.
+pub fn eigenvectors(m: &[Vec<Expr>]) -> Option<Vec<Vec<Expr>>> {
+    let mut vectors = Vec::new();
+    for (r, _) in distinct_eigenvalues(m)? {
+        let basis = null_space(&shift(m, &r));
+        if basis.is_empty() {
+            return None;
+        }
+        vectors.extend(basis);
+    }
+    Some(vectors)
+}
+
+fn distinct_eigenvalues(m: &[Vec<Expr>]) -> Option<Vec<(Expr, usize)>> {
+    // A symbol which cannot occur in the input
+    let x = Expr::symbol("$lambda");
+    polynomial_roots(&characteristic_polynomial(m, &x)?, &x)
+}
+
+/// Returns the matrix `m - x*I`.
+fn shift(m: &[Vec<Expr>], x: &Expr) -> Vec<Vec<Expr>> {
+    m.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(j, u)| match i == j {
+                    true => simplify(&Expr::minus(u.clone(), x.clone())),
+                    false => u.clone(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, matrix::tests::matrix, parser::AlgomyKernel};
+
+    use super::{characteristic_polynomial, eigenvalues, eigenvectors};
+
+    #[test]
+    fn test_characteristic_polynomial() {
+        let mut kernel = AlgomyKernel::new();
+        let (x, t) = (Expr::symbol("x"), Expr::symbol("t"));
+        let m = matrix(&mut kernel, "{{1, 2}, {3, 4}}");
+        assert_eq!(
+            characteristic_polynomial(&m, &x),
+            Some(kernel.parse_eval_line("x^2 - 5*x - 2"))
+        );
+        let m = matrix(&mut kernel, "{{a, b}, {c, d}}");
+        assert_eq!(
+            characteristic_polynomial(&m, &x),
+            Some(kernel.parse_eval_line("x^2 - a*x - d*x + a*d - b*c"))
+        );
+        // Det[m - t*I] has a negative leading coefficient for odd dimensions
+        let m = matrix(&mut kernel, "{{2, 0, 0}, {0, 3, 4}, {0, 4, 9}}");
+        assert_eq!(
+            characteristic_polynomial(&m, &t),
+            Some(kernel.parse_eval_line("-t^3 + 14*t^2 - 35*t + 22"))
+        );
+    }
+
+    #[test]
+    fn test_eigenvalues() {
+        let mut kernel = AlgomyKernel::new();
+        let m = matrix(&mut kernel, "{{2, 1}, {1, 2}}");
+        assert_eq!(eigenvalues(&m), Some(vec![Expr::int(1), Expr::int(3)]));
+        let m = matrix(&mut kernel, "{{2, 0, 0}, {0, 3, 4}, {0, 4, 9}}");
+        assert_eq!(
+            eigenvalues(&m),
+            Some(vec![Expr::int(1), Expr::int(2), Expr::int(11)])
+        );
+        // Repeated by multiplicity
+        let m = matrix(&mut kernel, "{{1, 1}, {0, 1}}");
+        assert_eq!(eigenvalues(&m), Some(vec![Expr::int(1), Expr::int(1)]));
+        // Complex and irrational eigenvalues
+        let m = matrix(&mut kernel, "{{0, -1}, {1, 0}}");
+        assert_eq!(
+            eigenvalues(&m),
+            Some(kernel.parse_eval_line("{-I, I}").operands)
+        );
+        let m = matrix(&mut kernel, "{{1, 1}, {1, 0}}");
+        assert_eq!(
+            eigenvalues(&m),
+            Some(
+                kernel
+                    .parse_eval_line("{1/2 - 1/2*5^(1/2), 1/2 + 1/2*5^(1/2)}")
+                    .operands
+            )
+        );
+        // Gaussian entries
+        let m = matrix(&mut kernel, "{{I, 0}, {1, 2}}");
+        assert_eq!(
+            eigenvalues(&m),
+            Some(kernel.parse_eval_line("{I, 2}").operands)
+        );
+        let m = matrix(&mut kernel, "{{1, I}, {-I, 1}}");
+        assert_eq!(eigenvalues(&m), Some(vec![Expr::int(0), Expr::int(2)]));
+        let m = matrix(&mut kernel, "{{2*I, 1}, {0, 1 + I}}");
+        assert_eq!(
+            eigenvalues(&m),
+            Some(kernel.parse_eval_line("{2*I, 1 + I}").operands)
+        );
+        let m = matrix(&mut kernel, "{{1, 2, 3}}");
+        assert_eq!(eigenvalues(&m), None);
+    }
+
+    #[test]
+    fn test_eigenvectors() {
+        let mut kernel = AlgomyKernel::new();
+        let m = matrix(&mut kernel, "{{2, 1}, {1, 2}}");
+        assert_eq!(
+            eigenvectors(&m),
+            Some(matrix(&mut kernel, "{{-1, 1}, {1, 1}}"))
+        );
+        let m = matrix(&mut kernel, "{{2, 0, 0}, {0, 3, 4}, {0, 4, 9}}");
+        assert_eq!(
+            eigenvectors(&m),
+            Some(matrix(&mut kernel, "{{0, -2, 1}, {1, 0, 0}, {0, 1/2, 1}}"))
+        );
+        // A defective matrix has fewer eigenvectors than its dimension
+        let m = matrix(&mut kernel, "{{1, 1}, {0, 1}}");
+        assert_eq!(eigenvectors(&m), Some(matrix(&mut kernel, "{{1, 0}}")));
+        let m = matrix(&mut kernel, "{{0, -1}, {1, 0}}");
+        assert_eq!(
+            eigenvectors(&m),
+            Some(matrix(&mut kernel, "{{-I, 1}, {I, 1}}"))
+        );
+        let m = matrix(&mut kernel, "{{1, 0}, {0, 1}}");
+        assert_eq!(eigenvectors(&m), Some(m.clone()));
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{
+    expression::Expr,
+    polynomial::{cancel, determinant, Polynomial},
+    simplify,
+};
+
+use super::identity_matrix;
+
+/// Converts the entries of a matrix to polynomials in the same variables, and returns them
+/// together with the variables.
+pub fn polynomial_matrix(m: &[Vec<Expr>]) -> (Vec<Vec<Polynomial>>, Vec<Expr>) {
+    let rows: Vec<Vec<Polynomial>> = m
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|u| Polynomial::from_expr(&u.algebraic_expand()))
+                .collect()
+        })
+        .collect();
+    let zero = rows
+        .iter()
+        .flatten()
+        .fold(Polynomial::zero(&[]), |acc, p| acc.unify(p).0);
+    // Unifying with the zero polynomial in all variables keeps their order
+    let rows = rows
+        .iter()
+        .map(|row| row.iter().map(|p| zero.unify(p).1).collect())
+        .collect();
+    let vars = zero.vars;
+    (rows, vars)
+}
+
+/// Brings a matrix of polynomials into reduced row echelon form with fraction-free
+/// Gauss-Jordan elimination, in which every pivot equals the last one and every division is
+/// exact. Only the first `columns` columns are searched for pivots, but all columns are
+/// transformed. Returns the pivot columns.
+pub fn fraction_free_eliminate(matrix: &mut [Vec<Polynomial>], columns: usize) -> Vec<usize> {
+    let width = matrix.first().map_or(0, |row| row.len());
+    let Some(vars) = matrix
+        .first()
+        .and_then(|row| row.first())
+        .map(|p| p.vars.clone())
+    else {
+        return Vec::new();
+    };
+    let mut previous = Polynomial::constant(Expr::int(1), &vars);
+    let mut pivots = Vec::new();
+    for k in 0..columns {
+        let r = pivots.len();
+        let Some(pivot) = (r..matrix.len()).find(|&i| !matrix[i][k].is_zero()) else {
+            continue;
+        };
+        matrix.swap(pivot, r);
+        for i in (0..matrix.len()).filter(|&i| i != r) {
+            for j in (0..width).filter(|&j| j != k) {
+                let p = matrix[r][k]
+                    .mul(&matrix[i][j])
+                    .sub(&matrix[i][k].mul(&matrix[r][j]));
+                matrix[i][j] = p.divide(std::slice::from_ref(&previous)).0.remove(0);
+            }
+            matrix[i][k] = Polynomial::zero(&vars);
+        }
+        previous = matrix[r][k].clone();
+        pivots.push(k);
+    }
+    pivots
+}
+
+/// Computes the reduced row echelon form of a matrix, and returns it together with its pivot
+/// columns.
+fn reduced_row_echelon(m: &[Vec<Expr>]) -> (Vec<Vec<Expr>>, Vec<usize>) {
+    let (mut matrix, _) = polynomial_matrix(m);
+    let columns = m[0].len();
+    let pivots = fraction_free_eliminate(&mut matrix, columns);
+    let rows = matrix
+        .iter()
+        .enumerate()
+        .map(|(i, row)| match pivots.get(i) {
+            // Divide the pivot row by its pivot
+            Some(&k) => {
+                let d = row[k].to_expr();
+                row.iter()
+                    .map(|p| cancel(&simplify(&Expr::quotient(p.to_expr(), d.clone()))))
+                    .collect()
+            }
+            None => row.iter().map(|p| p.to_expr()).collect(),
+        })
+        .collect();
+    (rows, pivots)
+}
+
+/// Computes the reduced row echelon form of a matrix.
+pub fn row_reduce(m: &[Vec<Expr>]) -> Vec<Vec<Expr>> {
+    reduced_row_echelon(m).0
+}
+
+/// Computes the rank of a matrix, i.e. the number of linearly independent rows.
+pub fn rank(m: &[Vec<Expr>]) -> usize {
+    let (mut matrix, _) = polynomial_matrix(m);
+    fraction_free_eliminate(&mut matrix, m[0].len()).len()
+}
+
+/// Computes a basis of the null space of a matrix, i.e. the solutions of `m . v = 0`. There is
+/// one basis vector for every column without a pivot in the reduced row echelon form.
+pub fn null_space(m: &[Vec<Expr>]) -> Vec<Vec<Expr>> {
+    let (rows, pivots) = reduced_row_echelon(m);
+    let columns = m[0].len();
+    (0..columns)
+        .filter(|j| !pivots.contains(j))
+        .map(|free| {
+            let mut v = vec![Expr::int(0); columns];
+            v[free] = Expr::int(1);
+            for (i, &k) in pivots.iter().enumerate() {
+                v[k] = simplify(&Expr::times(Expr::int(-1), rows[i][free].clone()));
+            }
+            v
+        })
+        .collect()
+}
+
+/// Computes the determinant of a square matrix. Returns `None` if the matrix is not square.
+pub fn det(m: &[Vec<Expr>]) -> Option<Expr> {
+    if m.len() != m[0].len() {
+        return None;
+    }
+    let (matrix, vars) = polynomial_matrix(m);
+    Some(determinant(matrix, &vars).to_expr())
+}
+
+/// Computes the inverse of a square matrix by row reducing `[m | I]`. Returns `None` if the
+/// matrix is not square or singular.
+pub fn inverse(m: &[Vec<Expr>]) -> Option<Vec<Vec<Expr>>> {
+    let n = m.len();
+    if n != m[0].len() {
+        return None;
+    }
+    let augmented: Vec<Vec<Expr>> = m
+        .iter()
+        .zip(identity_matrix(n))
+        .map(|(row, unit)| row.iter().cloned().chain(unit).collect())
+        .collect();
+    let (mut matrix, _) = polynomial_matrix(&augmented);
+    let pivots = fraction_free_eliminate(&mut matrix, n);
+    if pivots.len() < n {
+        return None;
+    }
+    Some(
+        matrix
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let d = row[i].to_expr();
+                row[n..]
+                    .iter()
+                    .map(|p| cancel(&simplify(&Expr::quotient(p.to_expr(), d.clone()))))
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, matrix::tests::matrix, parser::AlgomyKernel};
+
+    use super::{det, inverse, null_space, rank, row_reduce};
+
+    #[test]
+    fn test_det() {
+        let mut kernel = AlgomyKernel::new();
+        let m = matrix(&mut kernel, "{{1, 2}, {3, 4}}");
+        assert_eq!(det(&m), Some(Expr::int(-2)));
+        // Symbolic entries
+        let m = matrix(&mut kernel, "{{a, b}, {c, d}}");
+        assert_eq!(det(&m), Some(kernel.parse_eval_line("a*d - b*c")));
+        let m = matrix(&mut kernel, "{{2, 0, 1}, {1, 3, 2}, {1, 1, 2}}");
+        assert_eq!(det(&m), Some(Expr::int(6)));
+        // A singular matrix
+        let m = matrix(&mut kernel, "{{1, 2, 3}, {4, 5, 6}, {7, 8, 9}}");
+        assert_eq!(det(&m), Some(Expr::int(0)));
+        // Gaussian entries
+        let m = matrix(&mut kernel, "{{I, 1}, {1, I}}");
+        assert_eq!(det(&m), Some(Expr::int(-2)));
+        // Only square matrices have a determinant
+        let m = matrix(&mut kernel, "{{1, 2, 3}}");
+        assert_eq!(det(&m), None);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let mut kernel = AlgomyKernel::new();
+        let m = matrix(&mut kernel, "{{1, 2}, {3, 4}}");
+        assert_eq!(
+            inverse(&m),
+            Some(matrix(&mut kernel, "{{-2, 1}, {3/2, -1/2}}"))
+        );
+        let m = matrix(&mut kernel, "{{a, b}, {c, d}}");
+        assert_eq!(
+            inverse(&m),
+            Some(matrix(
+                &mut kernel,
+                "{{d/(a*d - b*c), -b/(a*d - b*c)}, {-c/(a*d - b*c), a/(a*d - b*c)}}"
+            ))
+        );
+        let m = matrix(&mut kernel, "{{2, 0}, {0, I}}");
+        assert_eq!(
+            inverse(&m),
+            Some(matrix(&mut kernel, "{{1/2, 0}, {0, -I}}"))
+        );
+        // Singular and non-square matrices have no inverse
+        let m = matrix(&mut kernel, "{{1, 2}, {2, 4}}");
+        assert_eq!(inverse(&m), None);
+        let m = matrix(&mut kernel, "{{1, 2, 3}}");
+        assert_eq!(inverse(&m), None);
+    }
+
+    #[test]
+    fn test_row_reduce() {
+        let mut kernel = AlgomyKernel::new();
+        let m = matrix(&mut kernel, "{{1, 2, 3}, {4, 5, 6}, {7, 8, 9}}");
+        assert_eq!(
+            row_reduce(&m),
+            matrix(&mut kernel, "{{1, 0, -1}, {0, 1, 2}, {0, 0, 0}}")
+        );
+        assert_eq!(rank(&m), 2);
+        // Rows are swapped to find a pivot
+        let m = matrix(&mut kernel, "{{0, 2, 4}, {1, 1, 1}}");
+        assert_eq!(
+            row_reduce(&m),
+            matrix(&mut kernel, "{{1, 0, -1}, {0, 1, 2}}")
+        );
+        assert_eq!(rank(&m), 2);
+        // Symbolic entries are assumed to be non-zero unless they simplify to zero
+        let m = matrix(&mut kernel, "{{a, b}, {c, d}}");
+        assert_eq!(row_reduce(&m), matrix(&mut kernel, "{{1, 0}, {0, 1}}"));
+        assert_eq!(rank(&m), 2);
+        let m = matrix(&mut kernel, "{{1, x}, {x, x^2}}");
+        assert_eq!(row_reduce(&m), matrix(&mut kernel, "{{1, x}, {0, 0}}"));
+        assert_eq!(rank(&m), 1);
+        let m = matrix(&mut kernel, "{{0, 0}, {0, 0}}");
+        assert_eq!(row_reduce(&m), m);
+        assert_eq!(rank(&m), 0);
+    }
+
+    #[test]
+    fn test_null_space() {
+        let mut kernel = AlgomyKernel::new();
+        let m = matrix(&mut kernel, "{{1, 2, 3}, {4, 5, 6}, {7, 8, 9}}");
+        assert_eq!(null_space(&m), matrix(&mut kernel, "{{1, -2, 1}}"));
+        let m = matrix(&mut kernel, "{{1, 2, 3}}");
+        assert_eq!(
+            null_space(&m),
+            matrix(&mut kernel, "{{-2, 1, 0}, {-3, 0, 1}}")
+        );
+        // A non-singular matrix has a trivial null space
+        let m = matrix(&mut kernel, "{{1, 0}, {0, 1}}");
+        assert_eq!(null_space(&m), Vec::<Vec<Expr>>::new());
+    }
+}
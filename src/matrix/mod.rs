@@ -0,0 +1,17 @@
+mod eigen;
+mod elimination;
+mod operations;
+
+pub use eigen::*;
+pub use elimination::*;
+pub use operations::*;
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    /// Evaluates `m` and returns the rows of the matrix.
+    pub fn matrix(kernel: &mut AlgomyKernel, m: &str) -> Vec<Vec<Expr>> {
+        kernel.parse_eval_line(m).as_matrix().unwrap()
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{
+    expression::{Expr, ExprKind},
+    simplify,
+};
+
+impl Expr {
+    /// Returns the rows of `self` if it is a matrix, i.e. a non-empty list of non-empty lists of
+    /// equal length.
+    pub fn as_matrix(&self) -> Option<Vec<Vec<Expr>>> {
+        if self.kind != ExprKind::List || self.operands.is_empty() {
+            return None;
+        }
+        let columns = self.operands[0].operands.len();
+        if columns > 0
+            && self
+                .operands
+                .iter()
+                .all(|row| row.kind == ExprKind::List && row.operands.len() == columns)
+        {
+            Some(
+                self.operands
+                    .iter()
+                    .map(|row| row.operands.clone())
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Returns the entries of `self` if it is a vector, i.e. a list of non-list expressions.
+    pub fn as_vector(&self) -> Option<Vec<Expr>> {
+        if self.kind == ExprKind::List && self.operands.iter().all(|u| u.kind != ExprKind::List) {
+            Some(self.operands.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Creates a matrix from its rows.
+    pub fn matrix(rows: Vec<Vec<Expr>>) -> Expr {
+        Expr::list(rows.into_iter().map(Expr::list).collect())
+    }
+}
+
+/// Computes the product of two vectors, a matrix and a vector, or two matrices. Returns `None`
+/// if the dimensions do not match.
+pub fn dot(u: &Expr, v: &Expr) -> Option<Expr> {
+    let inner = |a: &[Expr], b: &[Expr]| -> Expr {
+        simplify(&Expr::sum(
+            a.iter()
+                .zip(b.iter())
+                .map(|(x, y)| Expr::times(x.clone(), y.clone()))
+                .collect(),
+        ))
+    };
+    match (u.as_vector(), v.as_vector()) {
+        (Some(a), Some(b)) if a.len() == b.len() => Some(inner(&a, &b)),
+        (None, Some(b)) => {
+            let m = u.as_matrix()?;
+            (m[0].len() == b.len())
+                .then(|| Expr::list(m.iter().map(|row| inner(row, &b)).collect()))
+        }
+        (Some(a), None) => {
+            let columns = transpose(&v.as_matrix()?);
+            (columns[0].len() == a.len())
+                .then(|| Expr::list(columns.iter().map(|column| inner(&a, column)).collect()))
+        }
+        (None, None) => {
+            let m = u.as_matrix()?;
+            let columns = transpose(&v.as_matrix()?);
+            (m[0].len() == columns[0].len()).then(|| {
+                Expr::matrix(
+                    m.iter()
+                        .map(|row| columns.iter().map(|column| inner(row, column)).collect())
+                        .collect(),
+                )
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Transposes a matrix given by its rows.
+pub fn transpose(m: &[Vec<Expr>]) -> Vec<Vec<Expr>> {
+    (0..m[0].len())
+        .map(|j| m.iter().map(|row| row[j].clone()).collect())
+        .collect()
+}
+
+/// Returns the `n x n` identity matrix.
+pub fn identity_matrix(n: usize) -> Vec<Vec<Expr>> {
+    (0..n)
+        .map(|i| (0..n).map(|j| Expr::int((i == j) as i64)).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, matrix::tests::matrix, parser::AlgomyKernel};
+
+    use super::{dot, identity_matrix, transpose};
+
+    #[test]
+    fn test_dot() {
+        let mut kernel = AlgomyKernel::new();
+        // Vector . vector
+        let (u, v) = (
+            kernel.parse_eval_line("{1, 2, 3}"),
+            kernel.parse_eval_line("{4, 5, 6}"),
+        );
+        assert_eq!(dot(&u, &v), Some(Expr::int(32)));
+        let (u, v) = (
+            kernel.parse_eval_line("{a, b}"),
+            kernel.parse_eval_line("{c, d}"),
+        );
+        assert_eq!(dot(&u, &v), Some(kernel.parse_eval_line("a*c + b*d")));
+        // Matrix . vector and vector . matrix
+        let (m, v) = (
+            kernel.parse_eval_line("{{1, 2}, {3, 4}}"),
+            kernel.parse_eval_line("{x, y}"),
+        );
+        assert_eq!(
+            dot(&m, &v),
+            Some(kernel.parse_eval_line("{x + 2*y, 3*x + 4*y}"))
+        );
+        assert_eq!(
+            dot(&v, &m),
+            Some(kernel.parse_eval_line("{x + 3*y, 2*x + 4*y}"))
+        );
+        // Matrix . matrix
+        let (a, b) = (
+            kernel.parse_eval_line("{{1, 2}, {3, 4}}"),
+            kernel.parse_eval_line("{{0, 1}, {1, 0}}"),
+        );
+        assert_eq!(
+            dot(&a, &b),
+            Some(kernel.parse_eval_line("{{2, 1}, {4, 3}}"))
+        );
+        let (a, b) = (
+            kernel.parse_eval_line("{{1, 2, 3}}"),
+            kernel.parse_eval_line("{{1}, {2}, {3}}"),
+        );
+        assert_eq!(dot(&a, &b), Some(kernel.parse_eval_line("{{14}}")));
+        // Incompatible dimensions
+        let (u, v) = (
+            kernel.parse_eval_line("{1, 2}"),
+            kernel.parse_eval_line("{1, 2, 3}"),
+        );
+        assert_eq!(dot(&u, &v), None);
+        let u = kernel.parse_eval_line("{1, 2, 3}");
+        assert_eq!(dot(&m, &u), None);
+        let a = kernel.parse_eval_line("{{1, 2}}");
+        assert_eq!(dot(&a, &a), None);
+        // Dot of more than two arguments
+        assert_eq!(
+            kernel.parse_eval_line("Dot[{{1, 2}, {3, 4}}, {{1, 0}, {0, 1}}, {1, 1}]"),
+            kernel.parse_eval_line("{3, 7}")
+        );
+    }
+
+    #[test]
+    fn test_transpose() {
+        let mut kernel = AlgomyKernel::new();
+        let m = matrix(&mut kernel, "{{1, 2, 3}, {4, 5, 6}}");
+        assert_eq!(
+            transpose(&m),
+            matrix(&mut kernel, "{{1, 4}, {2, 5}, {3, 6}}")
+        );
+        assert_eq!(transpose(&transpose(&m)), m);
+        assert_eq!(
+            identity_matrix(3),
+            matrix(&mut kernel, "{{1, 0, 0}, {0, 1, 0}, {0, 0, 1}}")
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt::Display;
+
+use crate::context::with_context;
+
+/// A message emitted during evaluation, tagged `symbol::tag` like `Power::infy`. Unlike an
+/// [`Error`](crate::error::Error), a message does not stop the evaluation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    pub symbol: String,
+    pub tag: String,
+    pub text: String,
+}
+
+impl Message {
+    pub fn new(symbol: &str, tag: &str, text: &str) -> Self {
+        Self {
+            symbol: symbol.to_owned(),
+            tag: tag.to_owned(),
+            text: text.to_owned(),
+        }
+    }
+}
+
+impl Display for Message {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}::{}: {}", self.symbol, self.tag, self.text)
+    }
+}
+
+/// Emits the message `symbol::tag` into the current context, unless the same message was already
+/// emitted there.
+pub fn message(symbol: &str, tag: &str, text: &str) {
+    let message = Message::new(symbol, tag, text);
+    with_context(|context| {
+        if !context.messages.contains(&message) {
+            context.messages.push(message);
+        }
+    });
+}
+
+/// Emits `Power::infy` for a division by zero.
+pub fn infinite_expression() {
+    message("Power", "infy", "Infinite expression 1/0 encountered.");
+}
+
+/// Emits `Power::indet` for `0^0`.
+pub fn indeterminate_expression() {
+    message(
+        "Power",
+        "indet",
+        "Indeterminate expression 0^0 encountered.",
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::AlgomyKernel;
+
+    #[test]
+    fn test_messages() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("1/0", vec!["Power::infy"]),
+            ("1/0 + 2/0", vec!["Power::infy"]),
+            ("0^0", vec!["Power::indet"]),
+            ("{1.5/0, 0^0}", vec!["Power::infy", "Power::indet"]),
+            ("I/0", vec!["Power::infy"]),
+            ("Cot[0]", vec!["Power::infy"]),
+            ("Tan[Pi/2]", vec!["Power::infy"]),
+            ("Cot[Pi/2]", vec![]),
+            ("1/0.0", vec!["Power::infy"]),
+            ("0.0^-1", vec!["Power::infy"]),
+            ("0.0^0", vec!["Power::indet"]),
+            ("0.0^2", vec![]),
+            (
+                "PolynomialInverse[x^2 - 1, x - 1, x]",
+                vec!["PolynomialInverse::ninv"],
+            ),
+            ("PolynomialInverse[x, x^2 + 1, x]", vec![]),
+            ("Inverse[{{1, 2}, {2, 4}}]", vec!["Inverse::sing"]),
+            ("Inverse[{{1, 2}, {3, 4}}]", vec![]),
+            ("Integrate[1/x^2, {x, -1, 1}]", vec!["Integrate::idiv"]),
+            (
+                "LinearSolve[{{1, 1}, {2, 2}}, {1, 3}]",
+                vec!["LinearSolve::nosol"],
+            ),
+            ("x //. x -> x + 1", vec!["ReplaceRepeated::rrlim"]),
+            ("f[f[a]] //. f[x_] -> x", vec![]),
+            ("1/x", vec![]),
+        ];
+        for (input, expected) in cases {
+            kernel.parse_eval_line(input);
+            let tags: Vec<String> = kernel
+                .take_messages()
+                .iter()
+                .map(|m| format!("{}::{}", m.symbol, m.tag))
+                .collect();
+            assert_eq!(tags, expected, "{}", input);
+        }
+        assert_eq!(kernel.parse_eval_line("1/0").to_string(), "undefined");
+        assert_eq!(
+            kernel.take_messages()[0].to_string(),
+            "Power::infy: Infinite expression 1/0 encountered."
+        );
+        assert!(kernel.take_messages().is_empty());
+    }
+}
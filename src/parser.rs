@@ -1,5 +1,8 @@
-use crate::expression::{Expr, ExprKind};
+use crate::context::Context;
+use crate::error::{Error, ErrorKind, Span};
+use crate::expression::{Expr, ExprKind, Real};
 use crate::pest::Parser;
+use num::BigInt;
 use pest::iterators::Pair;
 use pest::{
     iterators::Pairs,
@@ -12,10 +15,19 @@ pub struct Assignment {
     pub val: Expr,
 }
 
+/// A definition `f[x_, ...] = rhs` of a function in terms of patterns for its arguments.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Definition {
+    pub lhs: Expr,
+    pub rhs: Expr,
+}
+
 pub enum Line {
     Expr(Expr),
     Assignment(Assignment),
     DelayedAssignment(Assignment),
+    Definition(Definition),
+    DelayedDefinition(Definition),
     None,
 }
 
@@ -24,198 +36,259 @@ pub enum Line {
 pub struct AlgomyKernel {
     pratt_parser: PrattParser<Rule>,
     pub assignments: Vec<Assignment>,
+    pub definitions: Vec<Definition>,
+    pub context: Context,
 }
 
 impl AlgomyKernel {
     pub fn new() -> Self {
         Self {
             pratt_parser: PrattParser::new()
-                .op(Op::infix(Rule::add, Assoc::Left)
-                    | Op::infix(Rule::sub, Assoc::Left)
-                    | Op::infix(Rule::or, Assoc::Left))
-                .op(Op::infix(Rule::mul, Assoc::Left)
-                    | Op::infix(Rule::div, Assoc::Left)
-                    | Op::infix(Rule::and, Assoc::Left))
+                .op(Op::infix(Rule::replace_all, Assoc::Left)
+                    | Op::infix(Rule::replace_repeated, Assoc::Left))
+                .op(Op::infix(Rule::rule, Assoc::Right)
+                    | Op::infix(Rule::rule_delayed, Assoc::Right))
+                .op(Op::infix(Rule::condition, Assoc::Left))
+                .op(Op::infix(Rule::or, Assoc::Left))
+                .op(Op::infix(Rule::and, Assoc::Left))
+                .op(Op::infix(Rule::eq, Assoc::Left)
+                    | Op::infix(Rule::neq, Assoc::Left)
+                    | Op::infix(Rule::lt, Assoc::Left)
+                    | Op::infix(Rule::le, Assoc::Left)
+                    | Op::infix(Rule::gt, Assoc::Left)
+                    | Op::infix(Rule::ge, Assoc::Left))
+                .op(Op::infix(Rule::add, Assoc::Left) | Op::infix(Rule::sub, Assoc::Left))
+                .op(Op::infix(Rule::mul, Assoc::Left) | Op::infix(Rule::div, Assoc::Left))
                 .op(Op::infix(Rule::pow, Assoc::Right) | Op::infix(Rule::setdiff, Assoc::Right))
                 .op(Op::prefix(Rule::not))
                 .op(Op::postfix(Rule::fac))
-                .op(Op::prefix(Rule::neg)),
+                .op(Op::prefix(Rule::neg))
+                .op(Op::postfix(Rule::part)),
             assignments: Vec::new(),
+            definitions: Vec::new(),
+            context: Context::default(),
         }
     }
 
-    /// Parse a program into a Vec of lines represented as Expr.
-    pub fn parse_program(&mut self, source: &str) -> Result<Vec<Line>, pest::error::Error<Rule>> {
+    /// Parse a program into a Vec of lines represented as Expr, with the span of each line.
+    pub fn parse_program(&mut self, source: &str) -> Result<Vec<(Line, Span)>, Error> {
         let mut ast = vec![];
         let pairs = AlgomyKernel::parse(Rule::program, source)?;
         for pair in pairs {
             match pair.as_rule() {
                 Rule::line => {
-                    let mut line_pairs = pair.into_inner();
-                    let pair = line_pairs.next().unwrap();
-
-                    match pair.as_rule() {
-                        Rule::expr => {
-                            let line = parse_expr(pair.into_inner(), &self.pratt_parser);
-                            ast.push(Line::Expr(line))
-                        }
-                        Rule::assignment => {
-                            let mut pairs = pair.into_inner();
-                            let var = parse_symbol(pairs.next().unwrap());
-                            let assign = pairs.next().unwrap();
-                            let expr = pairs.next().unwrap();
-                            let expr = parse_expr(expr.into_inner(), &self.pratt_parser);
-                            match assign.as_rule() {
-                                Rule::assign => {
-                                    ast.push(Line::Assignment(Assignment { var, val: expr }))
-                                }
-                                Rule::delayed_assign => {
-                                    ast.push(Line::DelayedAssignment(Assignment { var, val: expr }))
-                                }
-                                _ => unreachable!(),
-                            }
-                        }
-                        Rule::EOI => ast.push(Line::None),
-                        unknown => panic!("Unknown rule: {:?}", unknown),
-                    }
+                    let line_span = span(&pair);
+                    ast.push((self.parse_line_pair(pair)?, line_span));
                 }
                 Rule::EOI => {}
-                _ => unreachable!(),
+                unknown => return Err(unexpected(unknown, &pair)),
             }
         }
-        return Ok(ast);
+        Ok(ast)
     }
 
-    /// Parse a program into a Vec of lines represented as Expr.
-    pub fn parse_line(&mut self, source: &str) -> Result<Line, pest::error::Error<Rule>> {
-        let mut line_pairs = AlgomyKernel::parse(Rule::line, source)?;
-        let pair = line_pairs.next().unwrap();
+    /// Parse a single line, which may be empty.
+    pub fn parse_line(&mut self, source: &str) -> Result<Line, Error> {
+        let pair = AlgomyKernel::parse(Rule::input, source)?.next().unwrap();
+        match pair.as_rule() {
+            Rule::line => self.parse_line_pair(pair),
+            Rule::EOI => Ok(Line::None),
+            unknown => Err(unexpected(unknown, &pair)),
+        }
+    }
 
+    fn parse_line_pair(&self, pair: Pair<Rule>) -> Result<Line, Error> {
+        let pratt = &self.pratt_parser;
         let line_pair = pair.into_inner().next().unwrap();
         match line_pair.as_rule() {
-            Rule::expr => {
-                let line = parse_expr(line_pair.into_inner(), &self.pratt_parser);
-                Ok(Line::Expr(line))
-            }
+            Rule::expr => Ok(Line::Expr(parse_expr(line_pair.into_inner(), pratt)?)),
             Rule::assignment => {
                 let mut pairs = line_pair.into_inner();
                 let var = parse_symbol(pairs.next().unwrap());
                 let assign = pairs.next().unwrap();
-                let expr = pairs.next().unwrap();
-                let expr = parse_expr(expr.into_inner(), &self.pratt_parser);
+                let val = parse_expr(pairs.next().unwrap().into_inner(), pratt)?;
                 match assign.as_rule() {
-                    Rule::assign => Ok(Line::Assignment(Assignment { var, val: expr })),
-                    Rule::delayed_assign => {
-                        Ok(Line::DelayedAssignment(Assignment { var, val: expr }))
-                    }
-                    _ => unreachable!(),
+                    Rule::assign => Ok(Line::Assignment(Assignment { var, val })),
+                    Rule::delayed_assign => Ok(Line::DelayedAssignment(Assignment { var, val })),
+                    unknown => Err(unexpected(unknown, &assign)),
                 }
             }
-            Rule::EOI => Ok(Line::None),
-            unknown => panic!("Unknown rule: {:?}", unknown),
+            Rule::definition => {
+                let mut pairs = line_pair.into_inner();
+                let lhs = parse_func(pairs.next().unwrap().into_inner(), pratt)?;
+                let assign = pairs.next().unwrap();
+                let rhs = parse_expr(pairs.next().unwrap().into_inner(), pratt)?;
+                match assign.as_rule() {
+                    Rule::assign => Ok(Line::Definition(Definition { lhs, rhs })),
+                    Rule::delayed_assign => Ok(Line::DelayedDefinition(Definition { lhs, rhs })),
+                    unknown => Err(unexpected(unknown, &assign)),
+                }
+            }
+            unknown => Err(unexpected(unknown, &line_pair)),
         }
     }
 }
 
-fn parse_expr(pairs: Pairs<Rule>, pratt: &PrattParser<Rule>) -> Expr {
+/// Returns the span of `pair` in the source.
+fn span(pair: &Pair<Rule>) -> Span {
+    Span::new(pair.as_span().start(), pair.as_span().end())
+}
+
+/// Returns an error for a rule which was not expected at `pair`.
+fn unexpected(found: Rule, pair: &Pair<Rule>) -> Error {
+    Error::new(
+        ErrorKind::Syntax(format!("unexpected {:?}", found)),
+        Some(span(pair)),
+    )
+}
+
+/// An expression parsed by the Pratt parser, together with the right-hand side of the last
+/// relation if the expression is a relation or a chain of relations `a < b <= c` which may be
+/// continued. Parentheses end a chain.
+type Operand = (Expr, Option<Expr>);
+
+fn parse_expr(pairs: Pairs<Rule>, pratt: &PrattParser<Rule>) -> Result<Expr, Error> {
     pratt
-        .map_primary(|operand| match operand.as_rule() {
-            Rule::num => parse_num(operand),
-            Rule::symbol => parse_symbol(operand),
-            Rule::expr => parse_expr(operand.into_inner(), pratt),
-            Rule::func => parse_func(operand.into_inner(), pratt),
-            Rule::set => parse_set(operand.into_inner(), pratt),
-            _ => unreachable!(),
+        .map_primary(|operand| {
+            let expr = match operand.as_rule() {
+                Rule::num => parse_num(operand),
+                Rule::symbol => Ok(parse_symbol(operand)),
+                Rule::pattern => Ok(parse_pattern(operand)),
+                Rule::expr => parse_expr(operand.into_inner(), pratt),
+                Rule::func => parse_func(operand.into_inner(), pratt),
+                Rule::list => parse_list(operand.into_inner(), pratt),
+                unknown => Err(unexpected(unknown, &operand)),
+            };
+            Ok((expr?, None))
         })
-        .map_prefix(|op, rhs| match op.as_rule() {
-            // Change -a -> (-1)*a
-            Rule::neg => Expr {
-                kind: ExprKind::Product,
-                operands: vec![
-                    Expr {
-                        kind: ExprKind::Integer(-1),
-                        operands: vec![],
-                    },
-                    rhs,
-                ],
-            },
-            Rule::not => Expr {
-                kind: ExprKind::Not,
-                operands: vec![rhs],
-            },
-            _ => unreachable!(),
+        .map_prefix(|op, rhs: Result<Operand, Error>| {
+            let (rhs, _) = rhs?;
+            let expr = match op.as_rule() {
+                // Change -a -> (-1)*a
+                Rule::neg => Expr {
+                    kind: ExprKind::Product,
+                    operands: vec![Expr::int(-1), rhs],
+                },
+                Rule::not => Expr {
+                    kind: ExprKind::Not,
+                    operands: vec![rhs],
+                },
+                unknown => return Err(unexpected(unknown, &op)),
+            };
+            Ok((expr, None))
         })
-        .map_postfix(|lhs, op| match op.as_rule() {
-            Rule::fac => Expr {
-                kind: ExprKind::Factorial,
-                operands: vec![lhs],
-            },
-            _ => unreachable!(),
+        .map_postfix(|lhs, op| {
+            let (lhs, _) = lhs?;
+            let expr = match op.as_rule() {
+                Rule::fac => Expr {
+                    kind: ExprKind::Factorial,
+                    operands: vec![lhs],
+                },
+                // Change a[[i, j]] -> Part[a, i, j]
+                Rule::part => {
+                    let mut operands = vec![lhs];
+                    for pair in op.into_inner() {
+                        operands.push(parse_expr(pair.into_inner(), pratt)?);
+                    }
+                    Expr::function("Part", operands)
+                }
+                unknown => return Err(unexpected(unknown, &op)),
+            };
+            Ok((expr, None))
         })
-        .map_infix(|lhs, op, rhs| match op.as_rule() {
-            Rule::add => Expr {
-                kind: ExprKind::Sum,
-                operands: vec![lhs, rhs],
-            },
-            Rule::sub => Expr {
-                kind: ExprKind::Difference,
-                operands: vec![lhs, rhs],
-            },
-            Rule::mul => Expr {
-                kind: ExprKind::Product,
-                operands: vec![lhs, rhs],
-            },
-            Rule::div => Expr {
-                kind: ExprKind::Quotient,
-                operands: vec![lhs, rhs],
-            },
-            Rule::pow => Expr {
-                kind: ExprKind::Power,
-                operands: vec![lhs, rhs],
-            },
-            Rule::or => Expr {
-                kind: ExprKind::Or,
-                operands: vec![lhs, rhs],
-            },
-            Rule::and => Expr {
-                kind: ExprKind::And,
-                operands: vec![lhs, rhs],
-            },
-            Rule::setdiff => Expr {
-                kind: ExprKind::SetDifference,
-                operands: vec![lhs, rhs],
-            },
-            _ => unreachable!(),
+        .map_infix(|lhs, op, rhs| {
+            let ((lhs, chain), (rhs, _)) = (lhs?, rhs?);
+            let relation = match op.as_rule() {
+                Rule::eq => Some(ExprKind::Equal),
+                Rule::neq => Some(ExprKind::Unequal),
+                Rule::lt => Some(ExprKind::Less),
+                Rule::le => Some(ExprKind::LessEqual),
+                Rule::gt => Some(ExprKind::Greater),
+                Rule::ge => Some(ExprKind::GreaterEqual),
+                _ => None,
+            };
+            if let Some(kind) = relation {
+                // Change a < b <= c -> a < b && b <= c
+                return Ok(match chain {
+                    Some(last) => (
+                        Expr {
+                            kind: ExprKind::And,
+                            operands: vec![lhs, Expr::relation(kind, last, rhs.clone())],
+                        },
+                        Some(rhs),
+                    ),
+                    None => (Expr::relation(kind, lhs, rhs.clone()), Some(rhs)),
+                });
+            }
+            let expr = match op.as_rule() {
+                Rule::add => Expr {
+                    kind: ExprKind::Sum,
+                    operands: vec![lhs, rhs],
+                },
+                Rule::sub => Expr {
+                    kind: ExprKind::Difference,
+                    operands: vec![lhs, rhs],
+                },
+                Rule::mul => Expr {
+                    kind: ExprKind::Product,
+                    operands: vec![lhs, rhs],
+                },
+                Rule::div => Expr {
+                    kind: ExprKind::Quotient,
+                    operands: vec![lhs, rhs],
+                },
+                Rule::pow => Expr {
+                    kind: ExprKind::Power,
+                    operands: vec![lhs, rhs],
+                },
+                Rule::or => Expr {
+                    kind: ExprKind::Or,
+                    operands: vec![lhs, rhs],
+                },
+                Rule::and => Expr {
+                    kind: ExprKind::And,
+                    operands: vec![lhs, rhs],
+                },
+                Rule::setdiff => Expr {
+                    kind: ExprKind::SetDifference,
+                    operands: vec![lhs, rhs],
+                },
+                Rule::replace_all => Expr::function("ReplaceAll", vec![lhs, rhs]),
+                Rule::replace_repeated => Expr::function("ReplaceRepeated", vec![lhs, rhs]),
+                Rule::rule => Expr::rule(lhs, rhs),
+                Rule::rule_delayed => Expr::rule_delayed(lhs, rhs),
+                Rule::condition => Expr::function("Condition", vec![lhs, rhs]),
+                unknown => return Err(unexpected(unknown, &op)),
+            };
+            Ok((expr, None))
         })
         .parse(pairs)
+        .map(|(expr, _)| expr)
 }
 
-fn parse_num(pair: Pair<Rule>) -> Expr {
-    if let Ok(n) = pair.as_str().parse::<i32>() {
-        return Expr {
-            kind: ExprKind::Integer(n as i64),
-            operands: vec![],
-        };
+fn parse_num(pair: Pair<Rule>) -> Result<Expr, Error> {
+    if let Ok(n) = pair.as_str().parse::<BigInt>() {
+        return Ok(Expr::int(n));
+    };
+    if let Ok(r) = pair.as_str().parse::<Real>() {
+        return Ok(Expr::real(r));
     };
-    // if let Ok(n) = pair.as_str().parse::<i32>() {
-    //     return Expr {
-    //         kind: ExprKind::Float(n as i64),
-    //         operands: vec![],
-    //     };
-    // };
-    panic!("Invalid number: {:?}", pair.as_str());
+    Err(Error::new(
+        ErrorKind::InvalidNumber(pair.as_str().to_owned()),
+        Some(span(&pair)),
+    ))
 }
 
-fn parse_func(mut pairs: Pairs<Rule>, pratt: &PrattParser<Rule>) -> Expr {
+fn parse_func(mut pairs: Pairs<Rule>, pratt: &PrattParser<Rule>) -> Result<Expr, Error> {
     let func_name = pairs.next().unwrap().as_str();
     let mut operands = vec![];
     for pair in pairs {
-        let expr = parse_expr(pair.into_inner(), pratt);
+        let expr = parse_expr(pair.into_inner(), pratt)?;
         operands.push(expr);
     }
 
     // Check for reserved names
-    match func_name {
+    Ok(match func_name {
         "Union" => Expr {
             kind: ExprKind::Union,
             operands,
@@ -232,10 +305,65 @@ fn parse_func(mut pairs: Pairs<Rule>, pratt: &PrattParser<Rule>) -> Expr {
             kind: ExprKind::Member,
             operands,
         },
+        "List" => Expr::list(operands),
+        "Set" => Expr::set(operands),
+        "Rule" if operands.len() == 2 => Expr {
+            kind: ExprKind::Rule,
+            operands,
+        },
+        "RuleDelayed" if operands.len() == 2 => Expr {
+            kind: ExprKind::RuleDelayed,
+            operands,
+        },
+        "Equal" if operands.len() == 2 => Expr {
+            kind: ExprKind::Equal,
+            operands,
+        },
+        "Unequal" if operands.len() == 2 => Expr {
+            kind: ExprKind::Unequal,
+            operands,
+        },
+        "Less" if operands.len() == 2 => Expr {
+            kind: ExprKind::Less,
+            operands,
+        },
+        "LessEqual" if operands.len() == 2 => Expr {
+            kind: ExprKind::LessEqual,
+            operands,
+        },
+        "Greater" if operands.len() == 2 => Expr {
+            kind: ExprKind::Greater,
+            operands,
+        },
+        "GreaterEqual" if operands.len() == 2 => Expr {
+            kind: ExprKind::GreaterEqual,
+            operands,
+        },
         _ => Expr {
             kind: ExprKind::Func(func_name.to_owned()),
             operands,
         },
+    })
+}
+
+/// Parses a pattern `x_h` as `Pattern[x, Blank[h]]` and `x__h` as `Pattern[x, BlankSequence[h]]`.
+/// The name `x` and the head `h` are optional.
+fn parse_pattern(pair: Pair<Rule>) -> Expr {
+    let mut name = None;
+    let mut blank = None;
+    let mut head = vec![];
+    for pair in pair.into_inner() {
+        match pair.as_rule() {
+            Rule::symbol if blank.is_none() => name = Some(parse_symbol(pair)),
+            Rule::symbol => head.push(Expr::symbol(pair.as_str())),
+            Rule::blank => blank = Some("Blank"),
+            _ => blank = Some("BlankSequence"),
+        }
+    }
+    let blank = Expr::function(blank.unwrap(), head);
+    match name {
+        Some(name) => Expr::function("Pattern", vec![name, blank]),
+        None => blank,
     }
 }
 
@@ -246,6 +374,8 @@ fn parse_symbol(pair: Pair<Rule>) -> Expr {
         "I" => Expr::gaussian(Expr::int(0), Expr::int(1)),
         "True" => Expr::bool(true),
         "False" => Expr::bool(false),
+        "Pi" => Expr::pi(),
+        "E" => Expr::e(),
         _ => Expr {
             kind: ExprKind::Symbol(s.to_owned()),
             operands: vec![],
@@ -253,14 +383,11 @@ fn parse_symbol(pair: Pair<Rule>) -> Expr {
     }
 }
 
-fn parse_set(pairs: Pairs<Rule>, pratt: &PrattParser<Rule>) -> Expr {
+fn parse_list(pairs: Pairs<Rule>, pratt: &PrattParser<Rule>) -> Result<Expr, Error> {
     let mut operands = vec![];
     for pair in pairs {
-        let expr = parse_expr(pair.into_inner(), pratt);
+        let expr = parse_expr(pair.into_inner(), pratt)?;
         operands.push(expr);
     }
-    Expr {
-        kind: ExprKind::Set,
-        operands,
-    }
+    Ok(Expr::list(operands))
 }
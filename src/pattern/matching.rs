@@ -0,0 +1,371 @@
+use crate::{
+    expression::{Expr, ExprKind},
+    simplify,
+};
+
+/// The values of the pattern variables of a match, as pairs `(x, value)`. The variable of a
+/// sequence pattern `x__` is bound to `Sequence[values...]`.
+pub type Bindings = Vec<(Expr, Expr)>;
+
+/// A blank `_h` or `__h`, which matches one or a sequence of expressions with head `h`, or any
+/// expressions if there is no head.
+struct Blank<'a> {
+    sequence: bool,
+    head: Option<&'a str>,
+}
+
+impl Blank<'_> {
+    fn matches(&self, u: &Expr) -> bool {
+        self.head.is_none_or(|h| head(u) == h)
+    }
+}
+
+/// Matches `u` against the pattern `p`, and returns the values of the pattern variables of the
+/// first match. Sums and products are matched regardless of the order of their operands, and a
+/// blank can match the sum or product of several operands.
+pub fn match_pattern(p: &Expr, u: &Expr) -> Option<Bindings> {
+    matches(p, u, &Vec::new()).into_iter().next()
+}
+
+/// Returns all extensions of `bindings` for which `u` matches the pattern `p`.
+fn matches(p: &Expr, u: &Expr, bindings: &Bindings) -> Vec<Bindings> {
+    if let Some((x, blank)) = as_pattern(p) {
+        return match blank.matches(u) {
+            true => bind(x, u, bindings).into_iter().collect(),
+            false => vec![],
+        };
+    }
+    if let Some((q, test)) = as_condition(p) {
+        return matches(q, u, bindings)
+            .into_iter()
+            .filter(|b| apply_bindings(test, b) == Expr::bool(true))
+            .collect();
+    }
+    if !has_patterns(p) {
+        return match p == u {
+            true => vec![bindings.clone()],
+            false => vec![],
+        };
+    }
+    if p.kind != u.kind {
+        return vec![];
+    }
+    match p.kind {
+        ExprKind::Sum | ExprKind::Product => {
+            let mut ps: Vec<&Expr> = p.operands.iter().collect();
+            ps.sort_by_key(|p| pattern_rank(p));
+            match_orderless(&ps, &u.operands, &u.kind, bindings)
+        }
+        _ => match_sequence(&p.operands, &u.operands, bindings),
+    }
+}
+
+/// Matches the operands `us` in order against the patterns `ps`, where a sequence pattern
+/// matches one or more consecutive operands.
+fn match_sequence(ps: &[Expr], us: &[Expr], bindings: &Bindings) -> Vec<Bindings> {
+    let Some((p, rest)) = ps.split_first() else {
+        return match us.is_empty() {
+            true => vec![bindings.clone()],
+            false => vec![],
+        };
+    };
+    match as_pattern(p) {
+        Some((x, blank)) if blank.sequence => (1..=us.len())
+            .take_while(|&n| blank.matches(&us[n - 1]))
+            .filter_map(|n| Some((n, bind(x, &sequence(&us[..n]), bindings)?)))
+            .flat_map(|(n, b)| match_sequence(rest, &us[n..], &b))
+            .collect(),
+        _ => match us.split_first() {
+            Some((u, us)) => matches(p, u, bindings)
+                .iter()
+                .flat_map(|b| match_sequence(rest, us, b))
+                .collect(),
+            None => vec![],
+        },
+    }
+}
+
+/// Matches the operands `us` of a sum or product in any order against the patterns `ps`, which
+/// are sorted such that blanks come last. The last blank matches all remaining operands.
+fn match_orderless(
+    ps: &[&Expr],
+    us: &[Expr],
+    kind: &ExprKind,
+    bindings: &Bindings,
+) -> Vec<Bindings> {
+    let Some((p, rest)) = ps.split_first() else {
+        return match us.is_empty() {
+            true => vec![bindings.clone()],
+            false => vec![],
+        };
+    };
+    if us.is_empty() {
+        return vec![];
+    }
+    match as_pattern(p) {
+        Some((x, blank)) if rest.is_empty() => {
+            let value = match (blank.sequence, us) {
+                (true, _) if us.iter().all(|u| blank.matches(u)) => sequence(us),
+                (false, [u]) => u.clone(),
+                (false, _) => Expr {
+                    kind: kind.clone(),
+                    operands: us.to_vec(),
+                },
+                _ => return vec![],
+            };
+            match blank.sequence || blank.matches(&value) {
+                true => bind(x, &value, bindings).into_iter().collect(),
+                false => vec![],
+            }
+        }
+        _ => (0..us.len())
+            .flat_map(|i| {
+                let others: Vec<Expr> = us
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, u)| u.clone())
+                    .collect();
+                matches(p, &us[i], bindings)
+                    .iter()
+                    .flat_map(|b| match_orderless(rest, &others, kind, b))
+                    .collect::<Vec<_>>()
+            })
+            .collect(),
+    }
+}
+
+/// Binds the pattern variable `x` to `value`, unless `x` is already bound to a different value.
+fn bind(x: Option<&Expr>, value: &Expr, bindings: &Bindings) -> Option<Bindings> {
+    let mut bindings = bindings.clone();
+    if let Some(x) = x {
+        match bindings.iter().find(|(y, _)| y == x) {
+            Some((_, v)) if v != value => return None,
+            Some(_) => {}
+            None => bindings.push((x.clone(), value.clone())),
+        }
+    }
+    Some(bindings)
+}
+
+/// Sorts patterns of sums and products such that the most specific patterns are matched first.
+fn pattern_rank(p: &Expr) -> usize {
+    match as_pattern(p) {
+        None => 0,
+        Some((
+            _,
+            Blank {
+                sequence: false,
+                head: Some(_),
+            },
+        )) => 1,
+        Some((
+            _,
+            Blank {
+                sequence: false,
+                head: None,
+            },
+        )) => 2,
+        Some((_, Blank { sequence: true, .. })) => 3,
+    }
+}
+
+/// Substitutes the values of the pattern variables in `u` and simplifies the result. Sequences
+/// are spliced into the operands of the enclosing expression.
+pub fn apply_bindings(u: &Expr, bindings: &Bindings) -> Expr {
+    simplify(&substitute_bindings(u, bindings))
+}
+
+fn substitute_bindings(u: &Expr, bindings: &Bindings) -> Expr {
+    if let Some((_, value)) = bindings.iter().find(|(x, _)| x == u) {
+        return match value.operands.as_slice() {
+            [v] if is_sequence(value) => v.clone(),
+            _ => value.clone(),
+        };
+    }
+    let mut operands = Vec::new();
+    for v in &u.operands {
+        match bindings.iter().find(|(x, _)| x == v) {
+            Some((_, value)) if is_sequence(value) => {
+                operands.extend(value.operands.iter().cloned())
+            }
+            _ => operands.push(substitute_bindings(v, bindings)),
+        }
+    }
+    Expr {
+        kind: u.kind.clone(),
+        operands,
+    }
+}
+
+fn sequence(values: &[Expr]) -> Expr {
+    Expr::function("Sequence", values.to_vec())
+}
+
+fn is_sequence(u: &Expr) -> bool {
+    matches!(u.kind, ExprKind::Func(ref name) if name == "Sequence")
+}
+
+/// Returns the variable (if any) and the blank of the patterns `x_`, `x_h`, `x__`, `_` etc.
+fn as_pattern(p: &Expr) -> Option<(Option<&Expr>, Blank<'_>)> {
+    match p.kind {
+        ExprKind::Func(ref name) if name == "Pattern" && p.operands.len() == 2 => {
+            Some((Some(&p.operands[0]), as_blank(&p.operands[1])?))
+        }
+        _ => Some((None, as_blank(p)?)),
+    }
+}
+
+fn as_blank(p: &Expr) -> Option<Blank<'_>> {
+    let sequence = match p.kind {
+        ExprKind::Func(ref name) if name == "Blank" => false,
+        ExprKind::Func(ref name) if name == "BlankSequence" => true,
+        _ => return None,
+    };
+    let head = match p.operands.as_slice() {
+        [] => None,
+        [h] => match h.kind {
+            ExprKind::Symbol(ref h) => Some(h.as_str()),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(Blank { sequence, head })
+}
+
+/// Returns the pattern and the test of the condition `p /; test`.
+fn as_condition(p: &Expr) -> Option<(&Expr, &Expr)> {
+    match p.kind {
+        ExprKind::Func(ref name) if name == "Condition" && p.operands.len() == 2 => {
+            Some((&p.operands[0], &p.operands[1]))
+        }
+        _ => None,
+    }
+}
+
+/// Checks whether `u` contains a blank.
+pub fn has_patterns(u: &Expr) -> bool {
+    as_blank(u).is_some() || u.operands.iter().any(has_patterns)
+}
+
+/// Returns the variables `x` of the patterns `x_`, `x_h` and `x__` in `u`.
+pub fn pattern_variables(u: &Expr) -> Vec<Expr> {
+    match as_pattern(u) {
+        Some((x, _)) => x.into_iter().cloned().collect(),
+        None => u.operands.iter().flat_map(pattern_variables).collect(),
+    }
+}
+
+/// Returns the head of `u`, which is matched by the blank `_h`, e.g. `Integer` or `Plus`.
+pub fn head(u: &Expr) -> &str {
+    match u.kind {
+        ExprKind::Undefined => "Undefined",
+        ExprKind::Func(ref name) => name,
+        ExprKind::Symbol(_) => "Symbol",
+        ExprKind::Integer(_) => "Integer",
+        ExprKind::Fraction(_, _) => "Rational",
+        ExprKind::Real(_) => "Real",
+        ExprKind::Gaussian => "Complex",
+        ExprKind::Sum => "Plus",
+        ExprKind::Difference => "Subtract",
+        ExprKind::Product => "Times",
+        ExprKind::Quotient => "Divide",
+        ExprKind::Power => "Power",
+        ExprKind::Factorial => "Factorial",
+        ExprKind::Boolean(_) => "Boolean",
+        ExprKind::Not => "Not",
+        ExprKind::Or => "Or",
+        ExprKind::And => "And",
+        ExprKind::Equal => "Equal",
+        ExprKind::Unequal => "Unequal",
+        ExprKind::Less => "Less",
+        ExprKind::LessEqual => "LessEqual",
+        ExprKind::Greater => "Greater",
+        ExprKind::GreaterEqual => "GreaterEqual",
+        ExprKind::Rule => "Rule",
+        ExprKind::RuleDelayed => "RuleDelayed",
+        ExprKind::List => "List",
+        ExprKind::Set => "Set",
+        ExprKind::Union => "Union",
+        ExprKind::Intersection => "Intersection",
+        ExprKind::SetDifference => "Difference",
+        ExprKind::Member => "Member",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    use super::{has_patterns, head, match_pattern, pattern_variables};
+
+    /// The values of the pattern variables as `(x, value)` pairs, or `None` if there is no match
+    type Expected = Option<&'static [(&'static str, &'static str)]>;
+
+    #[test]
+    fn test_match_pattern() {
+        let mut kernel = AlgomyKernel::new();
+        let cases: [(&str, &str, Expected); 18] = [
+            ("x_", "f[a]", Some(&[("x", "f[a]")])),
+            ("_", "3", Some(&[])),
+            ("x_Integer", "3", Some(&[("x", "3")])),
+            ("x_Integer", "1/2", None),
+            ("x_Rational", "1/2", Some(&[("x", "1/2")])),
+            ("x_Symbol", "a", Some(&[("x", "a")])),
+            ("f[x_, y_]", "f[1, 2]", Some(&[("x", "1"), ("y", "2")])),
+            ("f[x_, y_]", "f[1, 2, 3]", None),
+            ("f[x_, x_]", "f[a, a]", Some(&[("x", "a")])),
+            ("f[x_, x_]", "f[a, b]", None),
+            ("f[x__]", "f[1, 2]", Some(&[("x", "Sequence[1, 2]")])),
+            (
+                "f[x_, y__]",
+                "f[1, 2, 3]",
+                Some(&[("x", "1"), ("y", "Sequence[2, 3]")]),
+            ),
+            ("f[x__Integer]", "f[1, a]", None),
+            ("x_ /; x > 0", "2", Some(&[("x", "2")])),
+            ("x_ /; x > 0", "-2", None),
+            ("a + x_", "a + b + c", Some(&[("x", "b + c")])),
+            ("a_*x^n_", "3*x^2", Some(&[("n", "2"), ("a", "3")])),
+            ("f[a]", "f[b]", None),
+        ];
+        for (p, u, expected) in cases {
+            let p = kernel.parse_eval_line(p);
+            let u = kernel.parse_eval_line(u);
+            let expected = expected.map(|bindings| {
+                bindings
+                    .iter()
+                    .map(|(x, v)| (Expr::symbol(x), kernel.parse_eval_line(v)))
+                    .collect()
+            });
+            assert_eq!(match_pattern(&p, &u), expected, "{} against {}", u, p);
+        }
+    }
+
+    #[test]
+    fn test_patterns() {
+        let mut kernel = AlgomyKernel::new();
+        let p = kernel.parse_eval_line("f[x_, g[y__], z_Integer /; z > 0]");
+        assert!(has_patterns(&p));
+        assert_eq!(
+            pattern_variables(&p),
+            [Expr::symbol("x"), Expr::symbol("y"), Expr::symbol("z")]
+        );
+        let u = kernel.parse_eval_line("f[x, g[y]]");
+        assert!(!has_patterns(&u));
+        assert!(pattern_variables(&u).is_empty());
+
+        let cases = [
+            ("3", "Integer"),
+            ("1/2", "Rational"),
+            ("x", "Symbol"),
+            ("a + b", "Plus"),
+            ("a*b", "Times"),
+            ("{1, 2}", "List"),
+            ("f[x]", "f"),
+        ];
+        for (u, expected) in cases {
+            assert_eq!(head(&kernel.parse_eval_line(u)), expected, "{}", u);
+        }
+    }
+}
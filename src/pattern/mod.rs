@@ -0,0 +1,5 @@
+mod matching;
+mod replace;
+
+pub use matching::*;
+pub use replace::*;
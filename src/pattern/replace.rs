@@ -0,0 +1,253 @@
+use crate::{
+    expression::{Expr, ExprKind},
+    message::message,
+    simplify,
+};
+
+use super::{apply_bindings, match_pattern, Bindings};
+
+/// Maximum number of passes of `ReplaceRepeated`, which stops rules that never reach a fixed
+/// point such as `x -> x + 1` with the message `ReplaceRepeated::rrlim`.
+const REPLACE_LIMIT: usize = 1024;
+
+/// A symbol which cannot occur in the input
+const REST: &str = "$rest";
+
+/// Evaluates `ReplaceAll[u, rules]` and `ReplaceRepeated[u, rules]`, where `rules` is a rule, a
+/// list of rules, or a list of lists of rules giving a list of results. Returns `None` if
+/// `rules` is not of this form.
+pub fn evaluate_replace(name: &str, u: &Expr, rules: &Expr) -> Option<Expr> {
+    let replace = |rules: &[Expr]| match name {
+        "ReplaceRepeated" => replace_repeated(u, rules),
+        _ => replace_all(u, rules),
+    };
+    match rules.kind {
+        ExprKind::Rule | ExprKind::RuleDelayed => Some(replace(std::slice::from_ref(rules))),
+        ExprKind::List if rules.operands.iter().all(|r| r.is_rule()) => {
+            Some(replace(&rules.operands))
+        }
+        ExprKind::List
+            if rules
+                .operands
+                .iter()
+                .all(|r| r.kind == ExprKind::List && r.operands.iter().all(|r| r.is_rule())) =>
+        {
+            Some(Expr::list(
+                rules
+                    .operands
+                    .iter()
+                    .map(|r| replace(&r.operands))
+                    .collect(),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Replaces every sub-expression of `u` which matches the left-hand side of one of the rules
+/// `lhs -> rhs` or `lhs :> rhs` by the corresponding right-hand side. The first matching rule is used, and the
+/// result of a replacement is not searched again. A rule for a sum or product also replaces
+/// part of the operands of a larger sum or product.
+pub fn replace_all(u: &Expr, rules: &[Expr]) -> Expr {
+    for rule in rules {
+        if let Some(bindings) = match_pattern(&rule.operands[0], u) {
+            return apply_bindings(&rule.operands[1], &bindings);
+        }
+    }
+    for rule in rules {
+        if let Some(bindings) = match_part(&rule.operands[0], u) {
+            let rest = Expr::symbol(REST);
+            return apply_bindings(
+                &Expr {
+                    kind: u.kind.clone(),
+                    operands: vec![rule.operands[1].clone(), rest],
+                },
+                &bindings,
+            );
+        }
+    }
+    if u.is_atomic() {
+        u.clone()
+    } else {
+        simplify(&u.map(|v| replace_all(v, rules)))
+    }
+}
+
+/// Matches a sum or product `lhs` against part of the operands of `u`, and binds the other
+/// operands to the variable [`REST`].
+fn match_part(lhs: &Expr, u: &Expr) -> Option<Bindings> {
+    if !matches!(u.kind, ExprKind::Sum | ExprKind::Product)
+        || lhs.kind != u.kind
+        || lhs.operands.len() >= u.operands.len()
+    {
+        return None;
+    }
+    let rest = Expr::function(
+        "Pattern",
+        vec![Expr::symbol(REST), Expr::function("BlankSequence", vec![])],
+    );
+    let mut pattern = lhs.clone();
+    pattern.operands.push(rest);
+    match_pattern(&pattern, u)
+}
+
+/// Applies [`replace_all`] until the result no longer changes, or at most [`REPLACE_LIMIT`]
+/// times.
+pub fn replace_repeated(u: &Expr, rules: &[Expr]) -> Expr {
+    let mut v = u.clone();
+    for _ in 0..REPLACE_LIMIT {
+        let w = replace_all(&v, rules);
+        if w == v {
+            return v;
+        }
+        v = w;
+    }
+    message(
+        "ReplaceRepeated",
+        "rrlim",
+        &format!("Exiting after {} scanned {} times.", u, REPLACE_LIMIT),
+    );
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        context::Context,
+        expression::{Expr, ExprKind},
+        parser::AlgomyKernel,
+    };
+
+    use super::{evaluate_replace, replace_repeated};
+
+    #[test]
+    fn test_replace() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("ReplaceAll", "x^2 + y", "x -> 3", Some("9 + y")),
+            ("ReplaceAll", "x + y", "{x -> 1, y -> 2}", Some("3")),
+            ("ReplaceAll", "{x, y}", "{x -> y, y -> x}", Some("{y, x}")),
+            ("ReplaceAll", "x", "{{x -> 1}, {x -> 2}}", Some("{1, 2}")),
+            (
+                "ReplaceAll",
+                "f[a] + f[b]",
+                "f[x_] -> x^2",
+                Some("a^2 + b^2"),
+            ),
+            (
+                "ReplaceAll",
+                "{1, 1/2, x}",
+                "x_Integer -> 0",
+                Some("{0, 1/2, x}"),
+            ),
+            (
+                "ReplaceAll",
+                "{a, 2, b}",
+                "x_Symbol -> s[x]",
+                Some("{s[a], 2, s[b]}"),
+            ),
+            (
+                "ReplaceAll",
+                "f[1, 2, 3]",
+                "f[x__] -> g[x, x]",
+                Some("g[1, 2, 3, 1, 2, 3]"),
+            ),
+            (
+                "ReplaceAll",
+                "f[1, 2, 3]",
+                "f[x_, y__] -> {x, h[y]}",
+                Some("{1, h[2, 3]}"),
+            ),
+            (
+                "ReplaceAll",
+                "{1, -2, 3}",
+                "x_ /; x < 0 -> 0",
+                Some("{1, 0, 3}"),
+            ),
+            ("ReplaceAll", "a + b + c", "a + x_ -> x", Some("b + c")),
+            ("ReplaceAll", "c + b*a", "a*b -> d", Some("c + d")),
+            ("ReplaceAll", "3*x^2 + 5", "a_*x^n_ -> a*n", Some("11")),
+            ("ReplaceAll", "x*y*z", "x*z -> w", Some("w*y")),
+            ("ReplaceAll", "f[a, b]", "f[x_, x_] -> 0", Some("f[a, b]")),
+            ("ReplaceAll", "f[a, a]", "f[x_, x_] -> 0", Some("0")),
+            ("ReplaceAll", "f[f[f[a]]]", "f[x_] -> x", Some("f[f[a]]")),
+            ("ReplaceAll", "x", "1 -> 2", Some("x")),
+            ("ReplaceAll", "f[y^2]", "f[x_] -> D[x, y]", Some("0")),
+            ("ReplaceAll", "f[y^2]", "f[x_] :> D[x, y]", Some("2*y")),
+            (
+                "ReplaceAll",
+                "{f[1], f[2]}",
+                "{f[1] :> a, f[x_] :> x + 1}",
+                Some("{a, 3}"),
+            ),
+            ("ReplaceAll", "x", "{x -> 1, 2}", None),
+            ("ReplaceAll", "x", "y", None),
+            (
+                "ReplaceRepeated",
+                "Log[a*b]",
+                "Log[x_*y_] -> Log[x] + Log[y]",
+                Some("Log[a] + Log[b]"),
+            ),
+            (
+                "ReplaceRepeated",
+                "Log[a*b*c]",
+                "Log[x_*y_] -> Log[x] + Log[y]",
+                Some("Log[a] + Log[b] + Log[c]"),
+            ),
+            ("ReplaceRepeated", "f[f[f[a]]]", "f[x_] -> x", Some("a")),
+            (
+                "ReplaceRepeated",
+                "x",
+                "{{x -> y, y -> z}, {x -> 1}}",
+                Some("{z, 1}"),
+            ),
+        ];
+        for (name, u, rules, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            let rules = kernel.parse_eval_line(rules);
+            assert_eq!(
+                evaluate_replace(name, &u, &rules),
+                expected.map(|e| kernel.parse_eval_line(e)),
+                "{}[{}, {}]",
+                name,
+                u,
+                rules
+            );
+        }
+
+        // Rules which never reach a fixed point stop at the limit
+        let u = kernel.parse_eval_line("x");
+        let rule = kernel.parse_eval_line("x -> x + 1");
+        let mut context = Context::default();
+        let result = context.enter(|| replace_repeated(&u, &[rule]));
+        assert_eq!(result, kernel.parse_eval_line("x + 1024"));
+        assert_eq!(
+            context
+                .messages
+                .iter()
+                .map(|m| m.to_string())
+                .collect::<Vec<_>>(),
+            ["ReplaceRepeated::rrlim: Exiting after x scanned 1024 times."]
+        );
+
+        // The operator forms
+        assert_eq!(
+            kernel.parse_eval_line("x^2 + y /. x -> 3"),
+            kernel.parse_eval_line("ReplaceAll[x^2 + y, x -> 3]")
+        );
+        assert_eq!(
+            kernel.parse_eval_line("f[f[a]] //. f[x_] -> x"),
+            kernel.parse_eval_line("ReplaceRepeated[f[f[a]], f[x_] -> x]")
+        );
+
+        // The right-hand side of a delayed rule is not evaluated
+        let one_plus_one = Expr {
+            kind: ExprKind::Sum,
+            operands: vec![Expr::int(1), Expr::int(1)],
+        };
+        assert_eq!(
+            kernel.parse_eval_line("RuleDelayed[x, 1 + 1]").operands,
+            [Expr::symbol("x"), one_plus_one]
+        );
+    }
+}
@@ -1,16 +1,21 @@
-use crate::{evaluate::evaluate_quotient, expression::Expr, simplify};
+use crate::{expression::Expr, message::infinite_expression, simplify};
 
 /// Single variable polynomial division. Takes two polynomials and a variable on which they are defined.
 /// Returns the quotient and remainder of the division as `(quotient, remainder)`.
+/// Division by the zero polynomial emits `Power::infy` and returns `Undefined` for both.
 pub fn polynomial_division(u: &Expr, v: &Expr, x: &Expr) -> (Expr, Expr) {
+    if v.is_zero() {
+        infinite_expression();
+        return (Expr::undefined(), Expr::undefined());
+    }
     let mut q = Expr::int(0);
     let mut r = u.clone();
     let mut m = r.degree_gpe(x);
     let n = v.degree_gpe(x);
     let lcv = v.leading_coefficient_gpe(x);
-    while m >= n {
+    while m >= n && !r.is_zero() {
         let lcr = r.leading_coefficient_gpe(x);
-        let s = evaluate_quotient(&lcr, &lcv);
+        let s = simplify(&Expr::quotient(lcr.clone(), lcv.clone()));
         q = simplify(&Expr::plus(
             q.clone(),
             Expr::times(s.clone(), Expr::power(x.clone(), Expr::int(m - n))),
@@ -32,7 +37,7 @@ pub fn polynomial_division(u: &Expr, v: &Expr, x: &Expr) -> (Expr, Expr) {
         .algebraic_expand();
         m = r.degree_gpe(x);
     }
-    return (q, r);
+    (q, r)
 }
 
 pub fn polynomial_quotient(u: &Expr, v: &Expr, x: &Expr) -> Expr {
@@ -44,3 +49,41 @@ pub fn polynomial_remainder(u: &Expr, v: &Expr, x: &Expr) -> Expr {
     let (_, r) = polynomial_division(u, v, x);
     r
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{context::Context, expression::Expr, parser::AlgomyKernel};
+
+    use super::polynomial_division;
+
+    #[test]
+    fn test_polynomial_division() {
+        let mut kernel = AlgomyKernel::new();
+        let x = Expr::symbol("x");
+        let u = kernel.parse_eval_line("x^3 + 2*x + 1");
+        let v = kernel.parse_eval_line("x - 1");
+        assert_eq!(
+            polynomial_division(&u, &v, &x),
+            (
+                kernel.parse_eval_line("x^2 + x + 3"),
+                kernel.parse_eval_line("4")
+            )
+        );
+
+        // Division by the zero polynomial is undefined
+        for u in ["x^2 + 1", "0"] {
+            let u = kernel.parse_eval_line(u);
+            let mut context = Context::default();
+            let result = context.enter(|| polynomial_division(&u, &Expr::int(0), &x));
+            assert_eq!(result, (Expr::undefined(), Expr::undefined()));
+            assert_eq!(
+                context
+                    .messages
+                    .iter()
+                    .map(|m| m.to_string())
+                    .collect::<Vec<_>>(),
+                ["Power::infy: Infinite expression 1/0 encountered."]
+            );
+        }
+    }
+}
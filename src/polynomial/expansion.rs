@@ -1,9 +1,9 @@
-use crate::expression::{Expr, ExprKind};
+use crate::expression::Expr;
 
 use super::polynomial_division;
 
 pub fn polynomial_expansion(u: &Expr, v: &Expr, x: &Expr, t: &Expr) -> Expr {
-    if let ExprKind::Integer(0) = u.kind {
+    if u.is_zero() {
         u.clone()
     } else {
         let (q, r) = polynomial_division(u, v, x);
@@ -0,0 +1,417 @@
+use num::{BigInt, BigRational, Integer, One, Signed, ToPrimitive, Zero};
+
+use crate::{expression::Expr, simplify};
+
+use super::{
+    integer::{self, trim},
+    rational_coefficients, square_free_factor, Polynomial,
+};
+
+/// Number of primes tried when looking for a modular factorization with few factors.
+const PRIME_CANDIDATES: usize = 5;
+
+/// Factors a polynomial in `x` with rational coefficients into irreducible factors over the
+/// integers. Returns `u` unchanged if it is not such a polynomial.
+pub fn factor(u: &Expr, x: &Expr) -> Expr {
+    let u = u.algebraic_expand();
+    let Some(coeffs) = rational_coefficients(&u, x) else {
+        return u;
+    };
+    if coeffs.len() <= 2 {
+        return u;
+    }
+    let mut result = vec![Expr::from_rational(coeffs[coeffs.len() - 1].clone())];
+    let (_, square_free) = square_free_factor(&u, x);
+    for (a, multiplicity) in square_free {
+        let (_, f) = integer::from_rationals(&rational_coefficients(&a, x).unwrap());
+        for g in factor_square_free(&f) {
+            // Divide out the leading coefficient of the factor, which is part of the content
+            let lc = integer::leading_coefficient(&g);
+            let m = Expr::int(multiplicity as i64);
+            result.push(Expr::power(
+                Expr::int(lc),
+                Expr::times(Expr::int(-1), m.clone()),
+            ));
+            result.push(Expr::power(integer::to_expr(&g, x), m));
+        }
+    }
+    simplify(&Expr::product(result))
+}
+
+/// Factors `u` over the integers. Common monomial factors and numerical content are pulled
+/// out, and the remaining polynomial is factored if it is univariate. Rational functions are
+/// factored in their numerator and denominator.
+pub fn factor_expr(u: &Expr) -> Expr {
+    let n = u.numerator();
+    let d = u.denominator();
+    if !d.is_one() {
+        return simplify(&Expr::quotient(factor_expr(&n), factor_expr(&d)));
+    }
+    let p = Polynomial::from_expr(u);
+    if p.is_zero() || !p.terms.values().all(|c| c.is_rne()) {
+        return u.clone();
+    }
+
+    // Common monomial factor: the minimal exponent of each variable
+    let mut common = vec![u32::MAX; p.vars.len()];
+    for exps in p.terms.keys() {
+        for (c, e) in common.iter_mut().zip(exps.iter()) {
+            *c = (*c).min(*e);
+        }
+    }
+    let monomial = Polynomial::monomial(Expr::int(1), common.clone(), &p.vars);
+    let mut rest = Polynomial::zero(&p.vars);
+    for (exps, c) in p.terms.iter() {
+        let exps = exps.iter().zip(common.iter()).map(|(e, c)| e - c).collect();
+        rest.terms.insert(exps, c.clone());
+    }
+    let rest = rest.to_expr();
+    let vars = Polynomial::from_expr(&rest).variables();
+    let rest = match &vars[..] {
+        [x] => factor(&rest, x),
+        _ => {
+            // Pull out the numerical content
+            let coeffs: Vec<BigRational> = Polynomial::from_expr(&rest)
+                .terms
+                .values()
+                .map(|c| c.rational_rne())
+                .collect();
+            let (c, _) = integer::from_rationals(&coeffs);
+            let c = if c.is_zero() { BigRational::one() } else { c };
+            let c = Expr::from_rational(c);
+            Expr::times(c.clone(), simplify(&Expr::quotient(rest, c)).expand())
+        }
+    };
+    simplify(&Expr::times(monomial.to_expr(), rest))
+}
+
+/// Factors a primitive, square-free integer polynomial with positive leading coefficient into
+/// irreducible factors with the Zassenhaus algorithm.
+fn factor_square_free(f: &[BigInt]) -> Vec<Vec<BigInt>> {
+    if integer::degree(f) <= 1 {
+        return vec![f.to_vec()];
+    }
+    // x is a factor if the constant coefficient vanishes
+    if f[0].is_zero() {
+        let mut factors = vec![vec![BigInt::zero(), BigInt::one()]];
+        factors.extend(factor_square_free(&f[1..]));
+        return factors;
+    }
+
+    let lc = integer::leading_coefficient(f);
+    let (p, modular) = choose_prime(f);
+    if modular.len() == 1 {
+        return vec![f.to_vec()];
+    }
+
+    // Lift the modular factorization to p^k > 2 * |lc| * B, with B the Mignotte bound
+    let n = integer::degree(f);
+    let max = f.iter().map(|c| c.abs()).max().unwrap();
+    let bound: BigInt = 2
+        * lc.abs()
+        * ((n + 1) as f64).sqrt().ceil().to_u64().unwrap()
+        * max
+        * (BigInt::one() << n);
+    let mut k = 1;
+    let mut modulus = p.clone();
+    while modulus <= bound {
+        modulus *= &p;
+        k += 1;
+    }
+    let lifted = hensel_lift(f, &modular, &p, k);
+    recombine(f, lifted, &modulus)
+}
+
+/// Returns the first small prime `p` for which `f` stays square-free modulo `p`, among the
+/// first few such primes the one with the fewest modular factors, and the factorization.
+fn choose_prime(f: &[BigInt]) -> (BigInt, Vec<Vec<BigInt>>) {
+    let lc = integer::leading_coefficient(f);
+    let df = integer::derivative(f);
+    let mut best: Option<(BigInt, Vec<Vec<BigInt>>)> = None;
+    let mut tried = 0;
+    let mut p = BigInt::from(3);
+    while tried < PRIME_CANDIDATES {
+        if is_prime(&p) && !lc.is_multiple_of(&p) {
+            let fp = integer::reduce(f, &p);
+            if integer::gcd_mod(&fp, &df, &p).len() == 1 {
+                let factors = factor_mod(&integer::monic_mod(&fp, &p), &p);
+                if best.as_ref().is_none_or(|(_, b)| factors.len() < b.len()) {
+                    best = Some((p.clone(), factors));
+                }
+                tried += 1;
+            }
+        }
+        p += 2;
+    }
+    best.unwrap()
+}
+
+fn is_prime(n: &BigInt) -> bool {
+    let mut d = BigInt::from(2);
+    while &d * &d <= *n {
+        if n.is_multiple_of(&d) {
+            return false;
+        }
+        d += 1;
+    }
+    *n >= BigInt::from(2)
+}
+
+/// Factors a monic square-free polynomial modulo an odd prime `p` into monic irreducible factors.
+fn factor_mod(f: &[BigInt], p: &BigInt) -> Vec<Vec<BigInt>> {
+    let mut rng = 0x2545_f491_4f6c_dd1du64;
+    distinct_degree_factor(f, p)
+        .into_iter()
+        .flat_map(|(g, d)| equal_degree_factor(&g, d, p, &mut rng))
+        .collect()
+}
+
+/// Splits a monic square-free polynomial modulo `p` into products of irreducible factors
+/// of equal degree `d`.
+fn distinct_degree_factor(f: &[BigInt], p: &BigInt) -> Vec<(Vec<BigInt>, usize)> {
+    let x = vec![BigInt::zero(), BigInt::one()];
+    let mut factors = Vec::new();
+    let mut f = f.to_vec();
+    let mut h = x.clone();
+    let mut d = 1;
+    while integer::degree(&f) >= 2 * d {
+        // h = x^(p^d) mod f
+        h = integer::pow_mod(&h, p, &f, p);
+        let g = integer::gcd_mod(&integer::sub(&h, &x), &f, p);
+        if integer::degree(&g) > 0 {
+            f = integer::divrem_mod(&f, &g, p).0;
+            h = integer::rem_mod(&h, &f, p);
+            factors.push((g, d));
+        }
+        d += 1;
+    }
+    if integer::degree(&f) > 0 {
+        let d = integer::degree(&f);
+        factors.push((f, d));
+    }
+    factors
+}
+
+/// Splits a product of irreducible factors of degree `d` modulo an odd prime `p` with the
+/// Cantor-Zassenhaus algorithm.
+fn equal_degree_factor(f: &[BigInt], d: usize, p: &BigInt, rng: &mut u64) -> Vec<Vec<BigInt>> {
+    let n = integer::degree(f);
+    if n == d {
+        return vec![f.to_vec()];
+    }
+    let e: BigInt = (num::pow(p.clone(), d) - 1) / 2;
+    loop {
+        // Random polynomial of degree < n
+        let a: Vec<BigInt> = trim(
+            (0..n)
+                .map(|_| {
+                    *rng ^= *rng << 13;
+                    *rng ^= *rng >> 7;
+                    *rng ^= *rng << 17;
+                    BigInt::from(*rng).mod_floor(p)
+                })
+                .collect(),
+        );
+        if a.len() <= 1 {
+            continue;
+        }
+        let b = integer::sub(&integer::pow_mod(&a, &e, f, p), &[BigInt::one()]);
+        let g = integer::gcd_mod(&b, f, p);
+        if !g.is_empty() && integer::degree(&g) > 0 && integer::degree(&g) < n {
+            let h = integer::divrem_mod(f, &g, p).0;
+            let mut factors = equal_degree_factor(&g, d, p, rng);
+            factors.extend(equal_degree_factor(&h, d, p, rng));
+            return factors;
+        }
+    }
+}
+
+/// Lifts the factorization `f = lc(f) * g_1 * ... * g_r` modulo `p` with monic `g_i` to a
+/// factorization modulo `p^k` with linear Hensel lifting.
+fn hensel_lift(f: &[BigInt], factors: &[Vec<BigInt>], p: &BigInt, k: usize) -> Vec<Vec<BigInt>> {
+    let lc = integer::leading_coefficient(f);
+    let lc_inv = integer::inverse_mod(&lc, p);
+
+    // a_i with sum(a_i * prod_{j != i} g_j) = 1 modulo p
+    let cofactors: Vec<Vec<BigInt>> = (0..factors.len())
+        .map(|i| {
+            let others = factors
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold(vec![BigInt::one()], |acc, (_, g)| {
+                    integer::mul_mod(&acc, g, p)
+                });
+            let (_, _, t) = integer::ext_gcd_mod(&factors[i], &others, p);
+            t
+        })
+        .collect();
+
+    let mut lifted = factors.to_vec();
+    let mut q = p.clone();
+    for _ in 1..k {
+        let product = lifted
+            .iter()
+            .fold(vec![lc.clone()], |acc, g| integer::mul(&acc, g));
+        let e: Vec<BigInt> = integer::sub(f, &product).iter().map(|c| c / &q).collect();
+        let e = integer::reduce(&e, p);
+        for (g, a) in lifted.iter_mut().zip(cofactors.iter()) {
+            let delta = integer::rem_mod(
+                &integer::scale(&integer::mul(&e, a), &lc_inv),
+                &integer::reduce(g, p),
+                p,
+            );
+            *g = integer::add(g, &integer::scale(&delta, &q));
+        }
+        q *= p;
+    }
+    lifted
+}
+
+/// Combines the lifted modular factors into the true factors of `f` over the integers.
+fn recombine(f: &[BigInt], mut lifted: Vec<Vec<BigInt>>, modulus: &BigInt) -> Vec<Vec<BigInt>> {
+    let mut f = f.to_vec();
+    let mut factors = Vec::new();
+    let mut size = 1;
+    while 2 * size <= lifted.len() {
+        let mut found = false;
+        for subset in subsets(lifted.len(), size) {
+            let lc = integer::leading_coefficient(&f);
+            let g = subset
+                .iter()
+                .fold(vec![lc], |acc, i| integer::mul(&acc, &lifted[*i]));
+            let g = integer::primitive_part(&integer::symmetric(&g, modulus));
+            if let Some(q) = integer::exact_division(&f, &g) {
+                factors.push(g);
+                f = q;
+                lifted = lifted
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, _)| !subset.contains(i))
+                    .map(|(_, g)| g)
+                    .collect();
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            size += 1;
+        }
+    }
+    if integer::degree(&f) > 0 {
+        factors.push(integer::primitive_part(&f));
+    }
+    factors
+}
+
+/// Returns all subsets of `{0, ..., n-1}` with `k` elements.
+fn subsets(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    } else if n < k {
+        return Vec::new();
+    }
+    let mut result = subsets(n - 1, k);
+    for mut s in subsets(n - 1, k - 1) {
+        s.push(n - 1);
+        result.push(s);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigInt;
+
+    use crate::{expression::Expr, parser::AlgomyKernel, polynomial::integer};
+
+    use super::{factor, factor_expr, factor_mod, factor_square_free, is_prime};
+
+    fn ints(coeffs: &[i64]) -> Vec<BigInt> {
+        coeffs.iter().map(|&c| BigInt::from(c)).collect()
+    }
+
+    #[test]
+    fn test_factor() {
+        let mut kernel = AlgomyKernel::new();
+        let x = Expr::symbol("x");
+        let cases = [
+            ("x^2 - 1", "(x - 1)*(x + 1)"),
+            ("2*x^2 + 4*x + 2", "2*(x + 1)^2"),
+            ("x^4 - 1", "(x - 1)*(x + 1)*(x^2 + 1)"),
+            ("6*x^2 + 5*x + 1", "(2*x + 1)*(3*x + 1)"),
+            ("x^3/2 - x/2", "x*(x - 1)*(x + 1)/2"),
+            ("x^4 + 4", "(x^2 - 2*x + 2)*(x^2 + 2*x + 2)"),
+            ("x^4 + 1", "x^4 + 1"),
+            (
+                "(x^3 + 2*x + 5)^2*(x - 3)*(2*x^5 - x + 3)",
+                "(x - 3)*(x^3 + 2*x + 5)^2*(2*x^5 - x + 3)",
+            ),
+            ("2*x + 4", "2*x + 4"),
+            ("a*x^2 - a", "a*x^2 - a"),
+            // Irreducible, but factors into many quadratics modulo every prime
+            (
+                "x^8 - 40*x^6 + 352*x^4 - 960*x^2 + 576",
+                "x^8 - 40*x^6 + 352*x^4 - 960*x^2 + 576",
+            ),
+        ];
+        for (u, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            assert_eq!(factor(&u, &x), kernel.parse_eval_line(expected), "{}", u);
+        }
+    }
+
+    #[test]
+    fn test_factor_expr() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("x^2*y + x*y^2", "x*y*(x + y)"),
+            ("2*x*y + 4*y^2", "2*y*(x + 2*y)"),
+            ("(x^2 - 1)/(x^2 + 2*x + 1)", "(x - 1)/(x + 1)"),
+            ("y^3 - y", "y*(y - 1)*(y + 1)"),
+            ("Sin[x]^2 - 1", "(Sin[x] - 1)*(Sin[x] + 1)"),
+            ("1.5*x + 3", "1.5*x + 3"),
+            ("0", "0"),
+        ];
+        for (u, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            assert_eq!(factor_expr(&u), kernel.parse_eval_line(expected), "{}", u);
+        }
+        assert_eq!(
+            kernel.parse_eval_line("Factor[y^2 - 4, y]"),
+            kernel.parse_eval_line("(y - 2)*(y + 2)")
+        );
+    }
+
+    #[test]
+    fn test_factor_square_free() {
+        // Coefficients from the constant term up
+        let cases: [(&[i64], &[&[i64]]); 4] = [
+            (&[-1, 0, 1], &[&[-1, 1], &[1, 1]]),
+            (&[0, -1, 0, 1], &[&[0, 1], &[-1, 1], &[1, 1]]),
+            (&[1, 5, 6], &[&[1, 2], &[1, 3]]),
+            (&[1, 0, 0, 0, 1], &[&[1, 0, 0, 0, 1]]),
+        ];
+        for (f, expected) in cases {
+            let mut factors = factor_square_free(&ints(f));
+            factors.sort();
+            let mut expected: Vec<Vec<BigInt>> = expected.iter().map(|g| ints(g)).collect();
+            expected.sort();
+            assert_eq!(factors, expected, "{:?}", f);
+        }
+
+        // The modular factors multiply to f modulo p
+        let p = BigInt::from(7);
+        let f = ints(&[1, 0, 0, 0, 1]);
+        let factors = factor_mod(&f, &p);
+        assert!(factors.len() > 1);
+        let product = factors
+            .iter()
+            .fold(ints(&[1]), |acc, g| integer::mul_mod(&acc, g, &p));
+        assert_eq!(product, f);
+
+        assert!(is_prime(&BigInt::from(13)));
+        assert!(!is_prime(&BigInt::from(15)));
+        assert!(!is_prime(&BigInt::from(1)));
+    }
+}
@@ -1,18 +1,18 @@
 use crate::{
-    expression::{Expr, ExprKind},
-    simplify,
+    expression::Expr,
+    simplify::{simplify, simplify_rne},
 };
 
-use super::polynomial_remainder;
+use super::{polynomial_division, polynomial_remainder, Polynomial};
 
 pub fn polynomial_gcd(u: &Expr, v: &Expr, x: &Expr) -> Expr {
-    if let (ExprKind::Integer(0), ExprKind::Integer(0)) = (&u.kind, &v.kind) {
+    if u.is_zero() && v.is_zero() {
         Expr::int(0)
     } else {
         let mut a = u.clone();
         let mut b = v.clone();
         loop {
-            if let ExprKind::Integer(0) = b.kind {
+            if b.is_zero() {
                 return simplify(
                     &Expr::times(
                         Expr::quotient(Expr::int(1), a.leading_coefficient_gpe(x)),
@@ -27,3 +27,245 @@ pub fn polynomial_gcd(u: &Expr, v: &Expr, x: &Expr) -> Expr {
         }
     }
 }
+
+/// Extended Euclidean algorithm for single variable polynomials. Returns `(g, s, t)` where `g`
+/// is the monic greatest common divisor of `u` and `v`, and `s*u + t*v = g`.
+pub fn polynomial_extended_gcd(u: &Expr, v: &Expr, x: &Expr) -> (Expr, Expr, Expr) {
+    if u.is_zero() && v.is_zero() {
+        return (Expr::int(0), Expr::int(0), Expr::int(0));
+    }
+    let mut a = u.clone();
+    let mut b = v.clone();
+    let (mut s_prev, mut s) = (Expr::int(1), Expr::int(0));
+    let (mut t_prev, mut t) = (Expr::int(0), Expr::int(1));
+    while !b.is_zero() {
+        let (q, r) = polynomial_division(&a, &b, x);
+        let s_next = Expr::minus(s_prev, Expr::times(q.clone(), s.clone())).algebraic_expand();
+        let t_next = Expr::minus(t_prev, Expr::times(q, t.clone())).algebraic_expand();
+        (s_prev, s) = (s, s_next);
+        (t_prev, t) = (t, t_next);
+        a = b;
+        b = r;
+    }
+    let c = a.leading_coefficient_gpe(x);
+    let normalize = |w: Expr| simplify(&Expr::quotient(w, c.clone())).algebraic_expand();
+    (normalize(a), normalize(s_prev), normalize(t_prev))
+}
+
+/// Computes the inverse of `u` modulo `m`, i.e. the polynomial `s` with `deg(s) < deg(m)` and
+/// `s*u = 1 (mod m)`. Returns `None` if `u` and `m` are not coprime.
+pub fn polynomial_inverse(u: &Expr, m: &Expr, x: &Expr) -> Option<Expr> {
+    let (g, s, _) = polynomial_extended_gcd(u, m, x);
+    if !g.is_one() {
+        return None;
+    }
+    Some(polynomial_remainder(&s, m, x))
+}
+
+/// Computes the monic greatest common divisor of two multivariate polynomials with rational
+/// coefficients in `vars`, which are ordered from most to least significant. Returns `None`
+/// if the coefficients are not rational numbers. Generalized variables not in `vars` are
+/// treated as additional, least significant variables.
+pub fn polynomial_gcd_multivariate(u: &Expr, v: &Expr, vars: &[Expr]) -> Option<Expr> {
+    let (p, q) = Polynomial::from_expr(u).unify(&Polynomial::from_expr(v));
+    if !p.terms.values().chain(q.terms.values()).all(|c| c.is_rne()) {
+        return None;
+    }
+    let mut order: Vec<Expr> = vars
+        .iter()
+        .filter(|x| p.vars.contains(x))
+        .cloned()
+        .collect();
+    order.extend(p.vars.iter().filter(|x| !vars.contains(x)).cloned());
+    let g = multivariate_gcd(&p.with_vars(&order)?, &q.with_vars(&order)?);
+    Some(g.to_expr())
+}
+
+/// Computes the monic greatest common divisor of two polynomials with rational coefficients
+/// in the same variables.
+pub fn multivariate_gcd(u: &Polynomial, v: &Polynomial) -> Polynomial {
+    let g = recursive_gcd(u, v, 0);
+    match g.leading_term() {
+        Some((_, c)) => g.scale(&simplify_rne(&Expr::quotient(Expr::int(1), c.clone()))),
+        None => g,
+    }
+}
+
+/// Computes a greatest common divisor of `u` and `v`, viewed as polynomials in `vars[k]` with
+/// coefficients in the less significant variables, with primitive polynomial remainder sequences.
+fn recursive_gcd(u: &Polynomial, v: &Polynomial, k: usize) -> Polynomial {
+    if u.is_zero() {
+        return v.clone();
+    }
+    if v.is_zero() {
+        return u.clone();
+    }
+    if k == u.vars.len() {
+        return Polynomial::constant(Expr::int(1), &u.vars);
+    }
+    let x = &u.vars[k];
+    let (cu, cv) = (content(u, k), content(v, k));
+    let c = recursive_gcd(&cu, &cv, k + 1);
+    let mut a = exact_quotient(u, &cu);
+    let mut b = exact_quotient(v, &cv);
+    if a.degree(x) < b.degree(x) {
+        (a, b) = (b, a);
+    }
+    while !b.is_zero() {
+        let r = pseudo_remainder(&a, &b, x);
+        a = b;
+        b = if r.is_zero() {
+            r
+        } else {
+            let cr = content(&r, k);
+            exact_quotient(&r, &cr)
+        };
+    }
+    c.mul(&a)
+}
+
+/// Computes the greatest common divisor of the coefficients of `u` as a polynomial in `vars[k]`.
+fn content(u: &Polynomial, k: usize) -> Polynomial {
+    let x = &u.vars[k];
+    (0..=u.degree(x))
+        .map(|n| u.coefficient(x, n))
+        .filter(|c| !c.is_zero())
+        .fold(Polynomial::zero(&u.vars), |g, c| {
+            recursive_gcd(&g, &c, k + 1)
+        })
+}
+
+/// Divides `u` by a divisor `v`.
+fn exact_quotient(u: &Polynomial, v: &Polynomial) -> Polynomial {
+    u.divide(std::slice::from_ref(v)).0.remove(0)
+}
+
+/// Computes a pseudo-remainder of `u` by `v` in the variable `x`: a polynomial `r` of lower
+/// degree in `x` than `v`, such that `c*u = q*v + r` for some `c` free of `x`.
+fn pseudo_remainder(u: &Polynomial, v: &Polynomial, x: &Expr) -> Polynomial {
+    let n = v.degree(x);
+    let lcv = v.coefficient(x, n);
+    let i = v.vars.iter().position(|w| w == x).unwrap();
+    let mut r = u.clone();
+    while !r.is_zero() && r.degree(x) >= n {
+        let m = r.degree(x);
+        let mut exps = vec![0; r.vars.len()];
+        exps[i] = m - n;
+        let t = r
+            .coefficient(x, m)
+            .mul(&Polynomial::monomial(Expr::int(1), exps, &r.vars));
+        r = lcv.mul(&r).sub(&t.mul(v));
+    }
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, parser::AlgomyKernel, polynomial::Polynomial};
+
+    use super::{
+        multivariate_gcd, polynomial_extended_gcd, polynomial_gcd_multivariate, polynomial_inverse,
+    };
+
+    #[test]
+    fn test_extended_gcd() {
+        let mut kernel = AlgomyKernel::new();
+        let x = Expr::symbol("x");
+        let cases = [
+            ("x^2 - 1", "x^2 - 3*x + 2", "x - 1", "1/3", "-1/3"),
+            ("x - 1", "x + 1", "1", "-1/2", "1/2"),
+            ("2*x + 2", "0", "x + 1", "1/2", "0"),
+            (
+                "x^3 + 1",
+                "x^2 + 1",
+                "1",
+                "1/2*x + 1/2",
+                "-1/2*x^2 - 1/2*x + 1/2",
+            ),
+        ];
+        for (u, v, g, s, t) in cases {
+            let (u, v) = (kernel.parse_eval_line(u), kernel.parse_eval_line(v));
+            let result = polynomial_extended_gcd(&u, &v, &x);
+            assert_eq!(
+                result,
+                (
+                    kernel.parse_eval_line(g),
+                    kernel.parse_eval_line(s),
+                    kernel.parse_eval_line(t)
+                ),
+                "{} {}",
+                u,
+                v
+            );
+            // s*u + t*v = g
+            let (g, s, t) = result;
+            assert_eq!(
+                Expr::plus(Expr::times(s, u), Expr::times(t, v)).algebraic_expand(),
+                g
+            );
+        }
+
+        let cases = [
+            ("x", "x^2 + 1", Some("-x")),
+            ("x + 1", "x^2 - 2", Some("x - 1")),
+            ("x - 1", "x^2 - 1", None),
+        ];
+        for (u, m, expected) in cases {
+            let (u, m) = (kernel.parse_eval_line(u), kernel.parse_eval_line(m));
+            assert_eq!(
+                polynomial_inverse(&u, &m, &x),
+                expected.map(|e| kernel.parse_eval_line(e)),
+                "{} {}",
+                u,
+                m
+            );
+        }
+    }
+
+    #[test]
+    fn test_multivariate_gcd() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("x^2 - y^2", "x^2 + 2*x*y + y^2", "{x, y}", Some("x + y")),
+            ("x^2*y + x*y^2", "x*y^2 + y^3", "{x, y}", Some("x*y + y^2")),
+            ("2*x*y + 2*x", "4*y^2 - 4", "{x, y}", Some("y + 1")),
+            (
+                "(x + y + z)*(x - z)^2",
+                "(x + y + z)*(x*y - z)",
+                "{x, y, z}",
+                Some("x + y + z"),
+            ),
+            ("x^2 + y", "x + y^2", "{x, y}", Some("1")),
+            ("a*x + a*y", "b*x + b*y", "{x, y}", Some("x + y")),
+            ("x^2 - y^2", "0", "{x, y}", Some("x^2 - y^2")),
+            ("1/2*x*y", "x^2", "{x, y}", Some("x")),
+            ("x + 1.5", "x", "{x}", None),
+        ];
+        for (u, v, vars, expected) in cases {
+            let u = kernel.parse_eval_line(u).algebraic_expand();
+            let v = kernel.parse_eval_line(v).algebraic_expand();
+            let vars = kernel.parse_eval_line(vars);
+            assert_eq!(
+                polynomial_gcd_multivariate(&u, &v, &vars.operands),
+                expected.map(|e| kernel.parse_eval_line(e)),
+                "{} {}",
+                u,
+                v
+            );
+        }
+
+        // The gcd divides both polynomials
+        let u = kernel.parse_eval_line("x^3*y - x*y^3");
+        let v = kernel.parse_eval_line("x^2*y + 2*x*y^2 + y^3");
+        let (p, q) = Polynomial::from_expr(&u).unify(&Polynomial::from_expr(&v));
+        let g = multivariate_gcd(&p, &q);
+        assert_eq!(g.to_expr(), kernel.parse_eval_line("x*y + y^2"));
+        for p in [p, q] {
+            assert!(
+                p.divide(std::slice::from_ref(&g)).1.is_zero(),
+                "{}",
+                p.to_expr()
+            );
+        }
+    }
+}
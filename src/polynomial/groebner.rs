@@ -0,0 +1,213 @@
+use crate::{expression::Expr, simplify::simplify_rne};
+
+use super::{MonomialOrder, Polynomial};
+
+/// Converts expressions to polynomials with rational coefficients in the same variables, where
+/// `vars` are the most significant variables in the given order, followed by any other
+/// generalized variables. Returns `None` if a coefficient is not a rational number.
+pub fn polynomials_in(us: &[Expr], vars: &[Expr]) -> Option<Vec<Polynomial>> {
+    let ps: Vec<Polynomial> = us
+        .iter()
+        .map(|u| Polynomial::from_expr(&u.algebraic_expand()))
+        .collect();
+    if !ps.iter().all(|p| p.terms.values().all(|c| c.is_rne())) {
+        return None;
+    }
+    let all = ps
+        .iter()
+        .fold(Polynomial::zero(vars), |acc, p| acc.unify(p).0)
+        .vars;
+    let mut order: Vec<Expr> = vars.to_vec();
+    order.extend(all.into_iter().filter(|x| !vars.contains(x)));
+    ps.iter().map(|p| p.with_vars(&order)).collect()
+}
+
+/// Computes the reduced Gröbner basis of the ideal generated by `ps` with Buchberger's
+/// algorithm. The basis consists of monic polynomials, sorted by increasing leading term.
+pub fn groebner_basis(ps: &[Polynomial], order: MonomialOrder) -> Vec<Polynomial> {
+    let mut basis: Vec<Polynomial> = ps
+        .iter()
+        .filter(|p| !p.is_zero())
+        .map(|p| monic(p, order))
+        .collect();
+    let mut pairs: Vec<(usize, usize)> = (0..basis.len())
+        .flat_map(|j| (0..j).map(move |i| (i, j)))
+        .collect();
+    while let Some((i, j)) = pairs.pop() {
+        let a = leading_exponents(&basis[i], order);
+        let b = leading_exponents(&basis[j], order);
+        // Buchberger's first criterion: coprime leading monomials reduce to zero
+        if a.iter().zip(b.iter()).all(|(x, y)| *x == 0 || *y == 0) {
+            continue;
+        }
+        let s = s_polynomial(&basis[i], &basis[j], order);
+        let (_, r) = s.divide_by(&basis, order);
+        if !r.is_zero() {
+            let k = basis.len();
+            basis.push(monic(&r, order));
+            pairs.extend((0..k).map(|i| (i, k)));
+        }
+    }
+
+    // Minimize: drop polynomials whose leading monomial is divisible by another one
+    let mut minimal: Vec<Polynomial> = Vec::new();
+    for (i, p) in basis.iter().enumerate() {
+        let e = leading_exponents(p, order);
+        let redundant = basis.iter().enumerate().any(|(j, q)| {
+            let f = leading_exponents(q, order);
+            j != i && divides(&f, &e) && (f != e || j < i)
+        });
+        if !redundant {
+            minimal.push(p.clone());
+        }
+    }
+
+    // Reduce every polynomial by the others
+    let mut reduced: Vec<Polynomial> = (0..minimal.len())
+        .map(|i| {
+            let others: Vec<Polynomial> = minimal
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, q)| q.clone())
+                .collect();
+            minimal[i].divide_by(&others, order).1
+        })
+        .collect();
+    reduced
+        .sort_by(|p, q| order.compare(&leading_exponents(p, order), &leading_exponents(q, order)));
+    reduced
+}
+
+/// Computes the S-polynomial `m/lt(p) * p - m/lt(q) * q` of two monic polynomials, where `m`
+/// is the least common multiple of their leading monomials.
+fn s_polynomial(p: &Polynomial, q: &Polynomial, order: MonomialOrder) -> Polynomial {
+    let a = leading_exponents(p, order);
+    let b = leading_exponents(q, order);
+    let lcm: Vec<u32> = a.iter().zip(b.iter()).map(|(x, y)| *x.max(y)).collect();
+    let shift = |e: &[u32]| lcm.iter().zip(e.iter()).map(|(l, x)| l - x).collect();
+    let mp = Polynomial::monomial(Expr::int(1), shift(&a), &p.vars);
+    let mq = Polynomial::monomial(Expr::int(1), shift(&b), &q.vars);
+    mp.mul(p).sub(&mq.mul(q))
+}
+
+fn leading_exponents(p: &Polynomial, order: MonomialOrder) -> Vec<u32> {
+    p.leading_term_by(order).unwrap().0.clone()
+}
+
+/// Checks whether the monomial with exponents `a` divides the one with exponents `b`.
+fn divides(a: &[u32], b: &[u32]) -> bool {
+    a.iter().zip(b.iter()).all(|(x, y)| x <= y)
+}
+
+/// Divides a non-zero polynomial by its leading coefficient.
+fn monic(p: &Polynomial, order: MonomialOrder) -> Polynomial {
+    let (_, c) = p.leading_term_by(order).unwrap();
+    p.scale(&simplify_rne(&Expr::quotient(Expr::int(1), c.clone())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Expr,
+        parser::AlgomyKernel,
+        polynomial::MonomialOrder::{self, GrevLex, Lex},
+    };
+
+    use super::{groebner_basis, polynomials_in};
+
+    #[test]
+    fn test_groebner_basis() {
+        let mut kernel = AlgomyKernel::new();
+        let cases: [(&str, &str, MonomialOrder, &str); 7] = [
+            (
+                "{x^2 + y^2 - 1, x - y}",
+                "{x, y}",
+                Lex,
+                "{y^2 - 1/2, x - y}",
+            ),
+            ("{x*y - 1, x^2 - y}", "{x, y}", Lex, "{y^3 - 1, x - y^2}"),
+            ("{x*y - 1, x^2 - y}", "{y, x}", Lex, "{x^3 - 1, y - x^2}"),
+            (
+                "{x^2 + y + z - 1, x + y^2 + z - 1, x + y + z^2 - 1}",
+                "{x, y, z}",
+                GrevLex,
+                "{x + y + z^2 - 1, x + y^2 + z - 1, x^2 + y + z - 1}",
+            ),
+            (
+                "{x*y - 1, x^2 - y}",
+                "{x, y}",
+                GrevLex,
+                "{y^2 - x, x*y - 1, x^2 - y}",
+            ),
+            ("{x^2 - 1, x^2 - 2}", "{x}", Lex, "{1}"),
+            ("{2*x + 4*y}", "{x, y}", Lex, "{x + 2*y}"),
+        ];
+        for (us, vars, order, expected) in cases {
+            let us = kernel.parse_eval_line(us);
+            let vars = kernel.parse_eval_line(vars);
+            let ps = polynomials_in(&us.operands, &vars.operands).unwrap();
+            let basis = groebner_basis(&ps, order)
+                .iter()
+                .map(|p| p.to_expr())
+                .collect();
+            assert_eq!(
+                Expr::list(basis),
+                kernel.parse_eval_line(expected),
+                "{}",
+                us
+            );
+        }
+
+        // Polynomials with coefficients that are not rational numbers
+        let vars = [Expr::symbol("x")];
+        assert!(polynomials_in(&[kernel.parse_eval_line("1.5*x")], &vars).is_none());
+    }
+
+    #[test]
+    fn test_polynomial_reduce() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            (
+                "x^2*y + x*y^2 + y^2",
+                "{x*y - 1, y^2 - 1}",
+                "{x, y}",
+                "{x + y, 1}",
+                "x + y + 1",
+            ),
+            ("x^3 - 1", "{x - 1}", "{x}", "{x^2 + x + 1}", "0"),
+            ("x^2 + y", "{x*y - 1}", "{x, y}", "{0}", "x^2 + y"),
+        ];
+        for (u, gs, vars, quotients, remainder) in cases {
+            let mut us = vec![kernel.parse_eval_line(u)];
+            us.extend(kernel.parse_eval_line(gs).operands);
+            let vars = kernel.parse_eval_line(vars);
+            let ps = polynomials_in(&us, &vars.operands).unwrap();
+            let (qs, r) = ps[0].divide_by(&ps[1..], Lex);
+            assert_eq!(
+                Expr::list(qs.iter().map(|q| q.to_expr()).collect()),
+                kernel.parse_eval_line(quotients),
+                "{}",
+                u
+            );
+            assert_eq!(r.to_expr(), kernel.parse_eval_line(remainder), "{}", u);
+        }
+
+        // The order is given as third argument or as option
+        assert_eq!(
+            kernel.parse_eval_line(
+                "GroebnerBasis[{x*y - 1, x^2 - y}, {x, y}, MonomialOrder -> GrevLex]"
+            ),
+            kernel.parse_eval_line("{y^2 - x, x*y - 1, x^2 - y}")
+        );
+        assert_eq!(
+            kernel.parse_eval_line("GroebnerBasis[{x*y - 1, x^2 - y}, {x, y}, grevlex]"),
+            kernel.parse_eval_line("{y^2 - x, x*y - 1, x^2 - y}")
+        );
+        // Unknown orders are a type error
+        let line = kernel
+            .parse_line("GroebnerBasis[{x, y}, {x, y}, Foo]")
+            .unwrap();
+        assert!(kernel.evaluate_line(line).is_err());
+    }
+}
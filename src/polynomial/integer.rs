@@ -0,0 +1,251 @@
+use num::{BigInt, BigRational, Integer, One, Signed, Zero};
+
+use crate::{expression::Expr, simplify};
+
+// Dense univariate polynomials with integer coefficients `[a_0, a_1, ..., a_n]`.
+// The zero polynomial is the empty vector.
+
+/// Removes leading zero coefficients.
+pub fn trim(mut f: Vec<BigInt>) -> Vec<BigInt> {
+    while f.last().is_some_and(|c| c.is_zero()) {
+        f.pop();
+    }
+    f
+}
+
+/// Returns the degree of a non-zero polynomial.
+pub fn degree(f: &[BigInt]) -> usize {
+    f.len() - 1
+}
+
+pub fn leading_coefficient(f: &[BigInt]) -> BigInt {
+    f.last().cloned().unwrap_or_else(BigInt::zero)
+}
+
+pub fn add(f: &[BigInt], g: &[BigInt]) -> Vec<BigInt> {
+    let n = f.len().max(g.len());
+    let zero = BigInt::zero();
+    trim(
+        (0..n)
+            .map(|i| f.get(i).unwrap_or(&zero) + g.get(i).unwrap_or(&zero))
+            .collect(),
+    )
+}
+
+pub fn sub(f: &[BigInt], g: &[BigInt]) -> Vec<BigInt> {
+    add(f, &scale(g, &BigInt::from(-1)))
+}
+
+pub fn scale(f: &[BigInt], c: &BigInt) -> Vec<BigInt> {
+    trim(f.iter().map(|a| a * c).collect())
+}
+
+pub fn mul(f: &[BigInt], g: &[BigInt]) -> Vec<BigInt> {
+    if f.is_empty() || g.is_empty() {
+        return Vec::new();
+    }
+    let mut h = vec![BigInt::zero(); f.len() + g.len() - 1];
+    for (i, a) in f.iter().enumerate() {
+        for (j, b) in g.iter().enumerate() {
+            h[i + j] += a * b;
+        }
+    }
+    trim(h)
+}
+
+pub fn derivative(f: &[BigInt]) -> Vec<BigInt> {
+    trim(
+        f.iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, a)| a * BigInt::from(i))
+            .collect(),
+    )
+}
+
+/// Returns the greatest common divisor of the coefficients.
+pub fn content(f: &[BigInt]) -> BigInt {
+    f.iter().fold(BigInt::zero(), |c, a| c.gcd(a))
+}
+
+/// Divides out the content, such that the leading coefficient is positive.
+pub fn primitive_part(f: &[BigInt]) -> Vec<BigInt> {
+    let mut c = content(f);
+    if c.is_zero() {
+        return Vec::new();
+    }
+    if leading_coefficient(f).is_negative() {
+        c = -c;
+    }
+    f.iter().map(|a| a / &c).collect()
+}
+
+/// Divides `f` by `g` over the integers, if the division is exact.
+pub fn exact_division(f: &[BigInt], g: &[BigInt]) -> Option<Vec<BigInt>> {
+    let mut r = f.to_vec();
+    if r.len() < g.len() {
+        return if r.is_empty() { Some(r) } else { None };
+    }
+    let lc = leading_coefficient(g);
+    let mut q = vec![BigInt::zero(); r.len() - g.len() + 1];
+    while r.len() >= g.len() {
+        let (c, rem) = leading_coefficient(&r).div_rem(&lc);
+        if !rem.is_zero() {
+            return None;
+        }
+        let shift = r.len() - g.len();
+        for (i, b) in g.iter().enumerate() {
+            r[shift + i] -= &c * b;
+        }
+        q[shift] = c;
+        r = trim(r);
+    }
+    if r.is_empty() {
+        Some(trim(q))
+    } else {
+        None
+    }
+}
+
+/// Writes a polynomial with rational coefficients as `c * f`, where `f` is a primitive integer
+/// polynomial with a positive leading coefficient.
+pub fn from_rationals(coeffs: &[BigRational]) -> (BigRational, Vec<BigInt>) {
+    let lcm = coeffs
+        .iter()
+        .fold(BigInt::one(), |acc, c| acc.lcm(c.denom()));
+    let f: Vec<BigInt> = trim(coeffs.iter().map(|c| (c * &lcm).to_integer()).collect());
+    let g = primitive_part(&f);
+    let c = match g.last() {
+        Some(lc) => BigRational::new(leading_coefficient(&f), lcm * lc),
+        None => BigRational::zero(),
+    };
+    (c, g)
+}
+
+/// Converts a polynomial to an expression in the variable `x`.
+pub fn to_expr(f: &[BigInt], x: &Expr) -> Expr {
+    simplify(&Expr::sum(
+        f.iter()
+            .enumerate()
+            .filter(|(_, c)| !c.is_zero())
+            .map(|(k, c)| {
+                Expr::times(
+                    Expr::int(c.clone()),
+                    Expr::power(x.clone(), Expr::int(k as i64)),
+                )
+            })
+            .collect(),
+    ))
+}
+
+// Arithmetic modulo `m`. Unless stated otherwise, results have coefficients in `[0, m)`.
+
+/// Reduces the coefficients modulo `m`.
+pub fn reduce(f: &[BigInt], m: &BigInt) -> Vec<BigInt> {
+    trim(f.iter().map(|a| a.mod_floor(m)).collect())
+}
+
+/// Reduces the coefficients modulo `m` to the symmetric range `(-m/2, m/2]`.
+pub fn symmetric(f: &[BigInt], m: &BigInt) -> Vec<BigInt> {
+    let half: BigInt = m / 2;
+    trim(
+        f.iter()
+            .map(|a| {
+                let a = a.mod_floor(m);
+                if a > half {
+                    a - m
+                } else {
+                    a
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Returns the inverse of `a` modulo `m`.
+pub fn inverse_mod(a: &BigInt, m: &BigInt) -> BigInt {
+    let e = a.mod_floor(m).extended_gcd(m);
+    e.x.mod_floor(m)
+}
+
+pub fn mul_mod(f: &[BigInt], g: &[BigInt], m: &BigInt) -> Vec<BigInt> {
+    reduce(&mul(f, g), m)
+}
+
+/// Divides `f` by `g` modulo `m`, where the leading coefficient of `g` is invertible.
+pub fn divrem_mod(f: &[BigInt], g: &[BigInt], m: &BigInt) -> (Vec<BigInt>, Vec<BigInt>) {
+    let mut r = reduce(f, m);
+    if r.len() < g.len() {
+        return (Vec::new(), r);
+    }
+    let inv = inverse_mod(&leading_coefficient(g), m);
+    let mut q = vec![BigInt::zero(); r.len() - g.len() + 1];
+    while r.len() >= g.len() {
+        let c = (leading_coefficient(&r) * &inv).mod_floor(m);
+        let shift = r.len() - g.len();
+        for (i, b) in g.iter().enumerate() {
+            r[shift + i] = (&r[shift + i] - &c * b).mod_floor(m);
+        }
+        q[shift] = c;
+        r = trim(r);
+    }
+    (trim(q), r)
+}
+
+pub fn rem_mod(f: &[BigInt], g: &[BigInt], m: &BigInt) -> Vec<BigInt> {
+    divrem_mod(f, g, m).1
+}
+
+/// Makes a non-zero polynomial monic modulo the prime `p`.
+pub fn monic_mod(f: &[BigInt], p: &BigInt) -> Vec<BigInt> {
+    let inv = inverse_mod(&leading_coefficient(f), p);
+    reduce(&scale(f, &inv), p)
+}
+
+/// Computes the monic greatest common divisor modulo the prime `p` together with the Bezout
+/// coefficients `(g, s, t)`, such that `s*f + t*h = g`.
+pub fn ext_gcd_mod(
+    f: &[BigInt],
+    h: &[BigInt],
+    p: &BigInt,
+) -> (Vec<BigInt>, Vec<BigInt>, Vec<BigInt>) {
+    let (mut r0, mut r1) = (reduce(f, p), reduce(h, p));
+    let (mut s0, mut s1) = (vec![BigInt::one()], Vec::new());
+    let (mut t0, mut t1) = (Vec::new(), vec![BigInt::one()]);
+    while !r1.is_empty() {
+        let (q, r) = divrem_mod(&r0, &r1, p);
+        let s = reduce(&sub(&s0, &mul(&q, &s1)), p);
+        let t = reduce(&sub(&t0, &mul(&q, &t1)), p);
+        (r0, r1) = (r1, r);
+        (s0, s1) = (s1, s);
+        (t0, t1) = (t1, t);
+    }
+    if r0.is_empty() {
+        return (r0, s0, t0);
+    }
+    let inv = inverse_mod(&leading_coefficient(&r0), p);
+    (
+        reduce(&scale(&r0, &inv), p),
+        reduce(&scale(&s0, &inv), p),
+        reduce(&scale(&t0, &inv), p),
+    )
+}
+
+pub fn gcd_mod(f: &[BigInt], h: &[BigInt], p: &BigInt) -> Vec<BigInt> {
+    ext_gcd_mod(f, h, p).0
+}
+
+/// Computes `f^e mod g` modulo `m`.
+pub fn pow_mod(f: &[BigInt], e: &BigInt, g: &[BigInt], m: &BigInt) -> Vec<BigInt> {
+    let mut result = vec![BigInt::one()];
+    let mut base = rem_mod(f, g, m);
+    let mut e = e.clone();
+    while e.is_positive() {
+        if e.is_odd() {
+            result = rem_mod(&mul(&result, &base), g, m);
+        }
+        base = rem_mod(&mul(&base, &base), g, m);
+        e >>= 1;
+    }
+    result
+}
@@ -7,12 +7,28 @@ use self::monomial::Monomial;
 
 mod division;
 mod expansion;
-mod monomial;
+mod factor;
 mod gcd;
+mod groebner;
+mod integer;
+mod monomial;
+mod multivariate;
+mod rational;
+mod resultant;
+mod roots;
+mod square_free;
 
 pub use division::*;
 pub use expansion::*;
+pub use factor::*;
 pub use gcd::*;
+pub use groebner::*;
+pub use monomial::MonomialOrder;
+pub use multivariate::*;
+pub use rational::*;
+pub use resultant::*;
+pub use roots::*;
+pub use square_free::*;
 
 impl Expr {
     /// Takes an expression and returns all the monomials in it.
@@ -37,28 +53,28 @@ impl Expr {
         simplify(&monomials.iter().map(|m| m.coefficient(var, exp)).sum())
     }
 
+    /// Checks if `self` is a general polynomial expression in `var`, i.e. all monomials
+    /// consist of non-negative powers of `var` and factors free of `var`.
+    pub fn is_polynomial_gpe(&self, var: &Expr) -> bool {
+        self.as_gpe().iter().all(|m| {
+            m.coeffs.iter().all(|c| c.is_free_of(var))
+                && m.vars
+                    .iter()
+                    .all(|(v, e)| if v == var { *e >= 0 } else { v.is_free_of(var) })
+        })
+    }
+
     /// Takes an expression and a variable and returns the leading coefficient of the variable in the expression
     pub fn leading_coefficient_gpe(&self, var: &Expr) -> Expr {
         let degree = self.degree_gpe(var);
         self.coefficient_gpe(var, degree)
     }
 
-    /// Takes an expression and returns all generalized variables in the monomials of the expression.
+    /// Takes an expression and returns all generalized variables of its expanded form.
     /// The result is a set of all generalized variables
     pub fn variables(&self) -> Expr {
-        let monomials = self.as_gpe();
-        let vars: Vec<Expr> = monomials
-            .iter()
-            .map(|m| {
-                m.vars
-                    .iter()
-                    .filter(|(v, _)| !v.is_grne())
-                    .map(|(v, _)| v.clone())
-                    .collect::<Vec<_>>()
-            })
-            .flatten()
-            .collect();
-        Expr::set(vars)
+        let vars = Polynomial::from_expr(self).variables();
+        Expr::set(vars.into_iter().filter(|v| !v.is_constant()).collect())
     }
 }
 
@@ -131,4 +147,25 @@ mod tests {
         let expr = kernel.parse_eval_line("x^2*y^2*z^5 + 5");
         assert_eq!(expr.degree_gpe(&Expr::symbol("a")), 0);
     }
+
+    #[test]
+    fn test_coefficient_gpe() {
+        let mut kernel = AlgomyKernel::new();
+        let x = Expr::symbol("x");
+        // The coefficient of a monomial contains the other variables
+        let expr = kernel.parse_eval_line("3*x*y + 5*x^2*y*z + x - 2");
+        assert_eq!(
+            expr.coefficient_gpe(&x, 1),
+            kernel.parse_eval_line("3*y + 1")
+        );
+        assert_eq!(expr.coefficient_gpe(&x, 2), kernel.parse_eval_line("5*y*z"));
+        assert_eq!(expr.coefficient_gpe(&x, 0), kernel.parse_eval_line("-2"));
+        assert_eq!(expr.coefficient_gpe(&x, 3), Expr::int(0));
+
+        let expr = kernel.parse_eval_line("a*x^2*y^3");
+        assert_eq!(
+            expr.coefficient_gpe(&Expr::symbol("y"), 3),
+            kernel.parse_eval_line("a*x^2")
+        );
+    }
 }
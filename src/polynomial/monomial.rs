@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 use crate::{
     expression::{Expr, ExprKind},
     simplify,
@@ -23,24 +25,17 @@ impl Monomial {
             vars: vec![(var, 1)],
         }
     }
-
-    pub fn new_monomial(coeff: Expr, var: Expr, exp: i64) -> Self {
-        Self {
-            coeffs: vec![coeff],
-            vars: vec![(var, exp)],
-        }
-    }
 }
 
 impl Expr {
     pub fn as_monomial(&self) -> Monomial {
-        if self.is_grne() {
+        if self.is_grne() || self.is_real_number() {
             Monomial::new_coeff(self.clone())
         } else {
             match self.kind {
                 ExprKind::Symbol(_) => Monomial::new_var(self.clone()),
                 ExprKind::Power => {
-                    if let ExprKind::Integer(exp) = self.operands[1].kind {
+                    if let Some(exp) = self.operands[1].as_i64() {
                         Monomial {
                             coeffs: Vec::new(),
                             vars: vec![(self.operands[0].clone(), exp)],
@@ -51,8 +46,11 @@ impl Expr {
                 }
                 ExprKind::Product => {
                     // Split product into coefficients and variables
-                    let (coeffs, vars): (Vec<_>, Vec<_>) =
-                        self.operands.iter().cloned().partition(|x| x.is_grne());
+                    let (coeffs, vars): (Vec<_>, Vec<_>) = self
+                        .operands
+                        .iter()
+                        .cloned()
+                        .partition(|x| x.is_grne() || x.is_real_number());
                     let vars = vars
                         .iter()
                         .map(|v| {
@@ -65,7 +63,10 @@ impl Expr {
                         .collect();
                     Monomial { coeffs, vars }
                 }
-                ExprKind::Integer(_) | ExprKind::Fraction(_, _) | ExprKind::Gaussian => {
+                ExprKind::Integer(_)
+                | ExprKind::Fraction(_, _)
+                | ExprKind::Real(_)
+                | ExprKind::Gaussian => {
                     unreachable!()
                 }
                 _ => Monomial::new_var(self.clone()),
@@ -75,6 +76,27 @@ impl Expr {
 }
 
 impl Monomial {
+    /// Returns the exponents of `vars` in the monomial.
+    pub fn exponents(&self, vars: &[Expr]) -> Vec<u32> {
+        vars.iter().map(|v| self.degree(v).max(0) as u32).collect()
+    }
+
+    /// Compares the monomial parts in `vars` of two monomials in the given order.
+    pub fn compare(&self, other: &Monomial, vars: &[Expr], order: MonomialOrder) -> Ordering {
+        order.compare(&self.exponents(vars), &other.exponents(vars))
+    }
+
+    /// Returns the coefficient of the monomial with respect to `vars`, which consists of the
+    /// numerical coefficients and all other variables.
+    pub fn coefficient_in(&self, vars: &[Expr]) -> Expr {
+        let rest = self
+            .vars
+            .iter()
+            .filter(|(v, _)| !vars.contains(v))
+            .map(|(v, e)| Expr::power(v.clone(), Expr::int(*e)));
+        simplify(&self.coeffs.iter().cloned().chain(rest).product())
+    }
+
     pub fn degree(&self, var: &Expr) -> i64 {
         self.vars
             .iter()
@@ -83,11 +105,257 @@ impl Monomial {
         // TODO: degree(0) = -inf
     }
 
+    /// Returns the coefficient of `var^exp` in the monomial, which consists of the numerical
+    /// coefficients and all other variables.
     pub fn coefficient(&self, var: &Expr, exp: i64) -> Expr {
-        if self.vars.len() == 1 && self.vars[0].0 == *var && self.vars[0].1 == exp {
-            simplify(&self.coeffs.iter().cloned().product())
+        if self.degree(var) == exp {
+            let rest = self
+                .vars
+                .iter()
+                .filter(|(v, _)| v != var)
+                .map(|(v, e)| Expr::power(v.clone(), Expr::int(*e)));
+            simplify(&self.coeffs.iter().cloned().chain(rest).product())
         } else {
             Expr::int(0)
         }
     }
 }
+
+/// An admissible ordering of the monomials of a multivariate polynomial, given by their
+/// exponent vectors with respect to an ordered list of variables.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MonomialOrder {
+    /// Lexicographic order: the exponents of the first variable are compared first.
+    Lex,
+    /// Graded lexicographic order: the total degree is compared first, ties are broken
+    /// lexicographically.
+    GrLex,
+    /// Graded reverse lexicographic order: the total degree is compared first, ties are broken
+    /// by the smallest exponent of the last variable.
+    GrevLex,
+}
+
+impl MonomialOrder {
+    /// Parses the name of an ordering regardless of case, e.g. `Lex`, `grevlex` or
+    /// `DegreeReverseLexicographic`.
+    pub fn from_expr(u: &Expr) -> Option<MonomialOrder> {
+        match u.kind {
+            ExprKind::Symbol(ref s) => match s.to_lowercase().as_str() {
+                "lex" | "lexicographic" => Some(MonomialOrder::Lex),
+                "grlex" | "deglex" | "degreelexicographic" => Some(MonomialOrder::GrLex),
+                "grevlex" | "degrevlex" | "degreereverselexicographic" => {
+                    Some(MonomialOrder::GrevLex)
+                }
+                _ => None,
+            },
+            // The option `MonomialOrder -> order`
+            ExprKind::Rule if u.operands[0] == Expr::symbol("MonomialOrder") => {
+                MonomialOrder::from_expr(&u.operands[1])
+            }
+            _ => None,
+        }
+    }
+
+    pub fn compare(&self, a: &[u32], b: &[u32]) -> Ordering {
+        let degree = |e: &[u32]| e.iter().sum::<u32>();
+        match self {
+            MonomialOrder::Lex => a.cmp(b),
+            MonomialOrder::GrLex => degree(a).cmp(&degree(b)).then_with(|| a.cmp(b)),
+            MonomialOrder::GrevLex => degree(a)
+                .cmp(&degree(b))
+                .then_with(|| b.iter().rev().cmp(a.iter().rev())),
+        }
+    }
+}
+
+impl Expr {
+    /// Returns the terms `(exponents, coefficient)` of `self` as a polynomial in `vars`, with
+    /// coefficients free of `vars`, sorted in decreasing order. Returns `None` if `self` is not
+    /// a polynomial in `vars`.
+    pub fn monomial_list(
+        &self,
+        vars: &[Expr],
+        order: MonomialOrder,
+    ) -> Option<Vec<(Vec<u32>, Expr)>> {
+        let u = self.algebraic_expand();
+        if !vars.iter().all(|x| u.is_polynomial_gpe(x)) {
+            return None;
+        }
+        let mut monomials = u.as_gpe();
+        monomials.sort_by(|a, b| b.compare(a, vars, order));
+        // Collect the coefficients of equal monomials
+        let mut terms: Vec<(Vec<u32>, Expr)> = Vec::new();
+        for m in monomials {
+            let (exps, c) = (m.exponents(vars), m.coefficient_in(vars));
+            match terms.last_mut() {
+                Some((e, d)) if *e == exps => *d = simplify(&Expr::plus(d.clone(), c)),
+                _ => terms.push((exps, c)),
+            }
+        }
+        terms.retain(|(_, c)| !c.is_zero());
+        Some(terms)
+    }
+
+    /// Returns the monomial `vars^exps`.
+    pub fn monomial(vars: &[Expr], exps: &[u32]) -> Expr {
+        simplify(&Expr::product(
+            vars.iter()
+                .zip(exps.iter())
+                .map(|(v, e)| Expr::power(v.clone(), Expr::int(*e as i64)))
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    use super::MonomialOrder::{self, GrLex, GrevLex, Lex};
+
+    #[test]
+    fn test_monomial_orders() {
+        let cases = [
+            (Lex, [1, 2, 0], [1, 1, 3], Ordering::Greater),
+            (GrLex, [1, 2, 0], [1, 1, 3], Ordering::Less),
+            (GrLex, [2, 0, 1], [1, 2, 0], Ordering::Greater),
+            (GrevLex, [2, 0, 1], [1, 2, 0], Ordering::Less),
+            (GrevLex, [1, 1, 1], [1, 1, 1], Ordering::Equal),
+        ];
+        for (order, a, b, expected) in cases {
+            assert_eq!(
+                order.compare(&a, &b),
+                expected,
+                "{:?} {:?} {:?}",
+                order,
+                a,
+                b
+            );
+        }
+
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("Lex", Some(Lex)),
+            ("DegreeLexicographic", Some(GrLex)),
+            ("GrevLex", Some(GrevLex)),
+            ("lex", Some(Lex)),
+            ("grlex", Some(GrLex)),
+            ("grevlex", Some(GrevLex)),
+            ("MonomialOrder -> DegreeReverseLexicographic", Some(GrevLex)),
+            ("Foo", None),
+            ("Method -> Lex", None),
+        ];
+        for (input, expected) in cases {
+            let u = kernel.parse_eval_line(input);
+            assert_eq!(MonomialOrder::from_expr(&u), expected, "{}", input);
+        }
+    }
+
+    /// The terms `(exponents, coefficient)`, or `None` if the input is not a polynomial
+    type Terms = Option<&'static [([u32; 3], &'static str)]>;
+
+    #[test]
+    fn test_monomial_list() {
+        let mut kernel = AlgomyKernel::new();
+        let u = "4*x*y^2*z + 4*z^2 - 5*x^3 + 7*x^2*z^2";
+        let cases: [(&str, &str, MonomialOrder, Terms); 6] = [
+            (
+                u,
+                "{x, y, z}",
+                Lex,
+                Some(&[
+                    ([3, 0, 0], "-5"),
+                    ([2, 0, 2], "7"),
+                    ([1, 2, 1], "4"),
+                    ([0, 0, 2], "4"),
+                ]),
+            ),
+            (
+                u,
+                "{x, y, z}",
+                GrLex,
+                Some(&[
+                    ([2, 0, 2], "7"),
+                    ([1, 2, 1], "4"),
+                    ([3, 0, 0], "-5"),
+                    ([0, 0, 2], "4"),
+                ]),
+            ),
+            (
+                u,
+                "{x, y, z}",
+                GrevLex,
+                Some(&[
+                    ([1, 2, 1], "4"),
+                    ([2, 0, 2], "7"),
+                    ([3, 0, 0], "-5"),
+                    ([0, 0, 2], "4"),
+                ]),
+            ),
+            (
+                "a*x^2*z + b*x^2*z + c*x*y",
+                "{x, y, z}",
+                Lex,
+                Some(&[([2, 0, 1], "a + b"), ([1, 1, 0], "c")]),
+            ),
+            ("0", "{x, y, z}", Lex, Some(&[])),
+            ("x/y + z", "{x, y, z}", Lex, None),
+        ];
+        for (u, vars, order, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            let vars = kernel.parse_eval_line(vars);
+            let expected = expected.map(|terms| {
+                terms
+                    .iter()
+                    .map(|(exps, c)| (exps.to_vec(), kernel.parse_eval_line(c)))
+                    .collect()
+            });
+            assert_eq!(
+                u.monomial_list(&vars.operands, order),
+                expected,
+                "{} in {:?}",
+                u,
+                order
+            );
+        }
+
+        assert_eq!(
+            Expr::monomial(&[Expr::symbol("x"), Expr::symbol("y")], &[2, 0]),
+            kernel.parse_eval_line("x^2")
+        );
+    }
+
+    #[test]
+    fn test_leading_terms() {
+        let mut kernel = AlgomyKernel::new();
+        let u = "4*x*y^2*z + 4*z^2 - 5*x^3 + 7*x^2*z^2";
+        let cases = [
+            (
+                format!("MonomialList[{}, {{x, y, z}}, GrLex]", u),
+                "{7*x^2*z^2, 4*x*y^2*z, -5*x^3, 4*z^2}",
+            ),
+            (
+                format!("LeadingTerm[{}, {{x, y, z}}, GrLex]", u),
+                "7*x^2*z^2",
+            ),
+            (format!("LeadingMonomial[{}, {{x, y, z}}]", u), "x^3"),
+            (
+                format!("LeadingCoefficient[{}, {{x, y, z}}, GrevLex]", u),
+                "4",
+            ),
+            ("LeadingTerm[(x + 1)^3, x]".to_owned(), "x^3"),
+            ("MonomialList[0, {x}]".to_owned(), "{0}"),
+            ("LeadingTerm[0, {x}]".to_owned(), "0"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                kernel.parse_eval_line(&input),
+                kernel.parse_eval_line(expected),
+                "{}",
+                input
+            );
+        }
+    }
+}
@@ -0,0 +1,448 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    expression::{Expr, ExprKind},
+    simplify,
+};
+
+use super::MonomialOrder;
+
+/// A sparse multivariate polynomial with numerical coefficients in the generalized variables
+/// `vars`. Every term is stored as a map from its exponent vector to its coefficient.
+/// Terms are ordered lexicographically, with `vars[0]` as the most significant variable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polynomial {
+    pub vars: Vec<Expr>,
+    pub terms: BTreeMap<Vec<u32>, Expr>,
+}
+
+fn add_coeff(a: &Expr, b: &Expr) -> Expr {
+    simplify(&Expr::plus(a.clone(), b.clone()))
+}
+
+fn mul_coeff(a: &Expr, b: &Expr) -> Expr {
+    simplify(&Expr::times(a.clone(), b.clone()))
+}
+
+fn div_coeff(a: &Expr, b: &Expr) -> Expr {
+    simplify(&Expr::quotient(a.clone(), b.clone()))
+}
+
+fn is_number(u: &Expr) -> bool {
+    u.is_real_number() || u.kind == ExprKind::Gaussian
+}
+
+/// Returns the exponent of `u` if it is a power with a non-negative integer exponent.
+fn natural_exponent(u: &Expr) -> Option<u32> {
+    match u.kind {
+        ExprKind::Power => u.operands[1].as_i64().and_then(|n| u32::try_from(n).ok()),
+        _ => None,
+    }
+}
+
+/// Collects the generalized variables of `u`, i.e. the parts of `u` which are not sums,
+/// products, natural powers or numbers.
+fn collect_variables(u: &Expr, vars: &mut Vec<Expr>) {
+    match u.kind {
+        ExprKind::Sum | ExprKind::Product => {
+            for v in u.operands.iter() {
+                collect_variables(v, vars);
+            }
+        }
+        _ if is_number(u) => {}
+        _ => match natural_exponent(u) {
+            Some(_) => collect_variables(&u.operands[0], vars),
+            None => {
+                if !vars.contains(u) {
+                    vars.push(u.clone());
+                }
+            }
+        },
+    }
+}
+
+/// Returns the union of the variables `a` and `b`, which is sorted unless `a` contains `b`.
+fn merge_variables(a: &[Expr], b: &[Expr]) -> Vec<Expr> {
+    // Keep the order of `a` if it contains all variables, which may be a custom order
+    if b.iter().all(|v| a.contains(v)) {
+        return a.to_vec();
+    }
+    let mut vars = a.to_vec();
+    for v in b.iter() {
+        if !vars.contains(v) {
+            vars.push(v.clone());
+        }
+    }
+    vars.sort();
+    vars
+}
+
+impl Polynomial {
+    pub fn zero(vars: &[Expr]) -> Polynomial {
+        Polynomial {
+            vars: vars.to_vec(),
+            terms: BTreeMap::new(),
+        }
+    }
+
+    pub fn constant(c: Expr, vars: &[Expr]) -> Polynomial {
+        Polynomial::monomial(c, vec![0; vars.len()], vars)
+    }
+
+    /// Creates the polynomial `c * vars^exps`.
+    pub fn monomial(c: Expr, exps: Vec<u32>, vars: &[Expr]) -> Polynomial {
+        let mut p = Polynomial::zero(vars);
+        if !c.is_zero() {
+            p.terms.insert(exps, c);
+        }
+        p
+    }
+
+    /// Converts an (automatically simplified) expression to a polynomial in all of its
+    /// generalized variables, which are sorted in increasing order.
+    pub fn from_expr(u: &Expr) -> Polynomial {
+        let mut vars = Vec::new();
+        collect_variables(u, &mut vars);
+        vars.sort();
+        Polynomial::from_expr_with_vars(u, &vars).expect("all variables of u are collected")
+    }
+
+    /// Converts an expression to a polynomial in `vars`. Returns `None` if `u` has a
+    /// generalized variable which is not in `vars`.
+    pub fn from_expr_with_vars(u: &Expr, vars: &[Expr]) -> Option<Polynomial> {
+        if let Some(i) = vars.iter().position(|v| v == u) {
+            let mut exps = vec![0; vars.len()];
+            exps[i] = 1;
+            return Some(Polynomial::monomial(Expr::int(1), exps, vars));
+        }
+        match u.kind {
+            ExprKind::Sum => u.operands.iter().try_fold(Polynomial::zero(vars), |p, v| {
+                Some(p.add(&Polynomial::from_expr_with_vars(v, vars)?))
+            }),
+            ExprKind::Product => u
+                .operands
+                .iter()
+                .try_fold(Polynomial::constant(Expr::int(1), vars), |p, v| {
+                    Some(p.mul(&Polynomial::from_expr_with_vars(v, vars)?))
+                }),
+            _ if is_number(u) => Some(Polynomial::constant(u.clone(), vars)),
+            _ => {
+                let n = natural_exponent(u)?;
+                Some(Polynomial::from_expr_with_vars(&u.operands[0], vars)?.pow(n))
+            }
+        }
+    }
+
+    /// Converts the polynomial back to an expression.
+    pub fn to_expr(&self) -> Expr {
+        let terms = self
+            .terms
+            .iter()
+            .map(|(exps, c)| {
+                let mut factors = vec![c.clone()];
+                factors.extend(
+                    self.vars
+                        .iter()
+                        .zip(exps.iter())
+                        .filter(|(_, e)| **e > 0)
+                        .map(|(v, e)| Expr::power(v.clone(), Expr::int(*e))),
+                );
+                Expr::product(factors)
+            })
+            .collect();
+        simplify(&Expr::sum(terms))
+    }
+
+    /// Returns the same polynomial in the variables `vars`. Returns `None` if `vars` is not a
+    /// superset of `self.vars`.
+    pub fn with_vars(&self, vars: &[Expr]) -> Option<Polynomial> {
+        let positions: Vec<usize> = self
+            .vars
+            .iter()
+            .map(|v| vars.iter().position(|w| w == v))
+            .collect::<Option<_>>()?;
+        let mut p = Polynomial::zero(vars);
+        for (exps, c) in self.terms.iter() {
+            let mut new_exps = vec![0; vars.len()];
+            for (i, e) in positions.iter().zip(exps.iter()) {
+                new_exps[*i] = *e;
+            }
+            p.terms.insert(new_exps, c.clone());
+        }
+        Some(p)
+    }
+
+    /// Returns `self` and `other` as polynomials in the union of their variables.
+    pub fn unify(&self, other: &Polynomial) -> (Polynomial, Polynomial) {
+        if self.vars == other.vars {
+            return (self.clone(), other.clone());
+        }
+        let vars = merge_variables(&self.vars, &other.vars);
+        (self.embed(&vars), other.embed(&vars))
+    }
+
+    /// Returns the same polynomial in the merged variables `vars`, which contain `self.vars`.
+    fn embed(&self, vars: &[Expr]) -> Polynomial {
+        self.with_vars(vars)
+            .expect("merged variables contain the variables of both polynomials")
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Adds `c * vars^exps` to the polynomial.
+    fn add_term(&mut self, exps: Vec<u32>, c: &Expr) {
+        let sum = match self.terms.get(&exps) {
+            Some(d) => add_coeff(d, c),
+            None => c.clone(),
+        };
+        if sum.is_zero() {
+            self.terms.remove(&exps);
+        } else {
+            self.terms.insert(exps, sum);
+        }
+    }
+
+    pub fn add(&self, other: &Polynomial) -> Polynomial {
+        let (mut p, q) = self.unify(other);
+        for (exps, c) in q.terms {
+            p.add_term(exps, &c);
+        }
+        p
+    }
+
+    pub fn neg(&self) -> Polynomial {
+        self.scale(&Expr::int(-1))
+    }
+
+    pub fn sub(&self, other: &Polynomial) -> Polynomial {
+        self.add(&other.neg())
+    }
+
+    /// Multiplies all coefficients by the number `c`.
+    pub fn scale(&self, c: &Expr) -> Polynomial {
+        if c.is_zero() {
+            return Polynomial::zero(&self.vars);
+        }
+        Polynomial {
+            vars: self.vars.clone(),
+            terms: self
+                .terms
+                .iter()
+                .map(|(exps, d)| (exps.clone(), mul_coeff(d, c)))
+                .collect(),
+        }
+    }
+
+    pub fn mul(&self, other: &Polynomial) -> Polynomial {
+        let (p, q) = self.unify(other);
+        let mut r = Polynomial::zero(&p.vars);
+        for (e1, c1) in p.terms.iter() {
+            for (e2, c2) in q.terms.iter() {
+                let exps = e1.iter().zip(e2.iter()).map(|(a, b)| a + b).collect();
+                r.add_term(exps, &mul_coeff(c1, c2));
+            }
+        }
+        r
+    }
+
+    pub fn pow(&self, n: u32) -> Polynomial {
+        let mut result = Polynomial::constant(Expr::int(1), &self.vars);
+        let mut base = self.clone();
+        let mut n = n;
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.mul(&base);
+            }
+            n >>= 1;
+            if n > 0 {
+                base = base.mul(&base);
+            }
+        }
+        result
+    }
+
+    /// Returns the exponent vector and coefficient of the leading term in lexicographic order.
+    pub fn leading_term(&self) -> Option<(&Vec<u32>, &Expr)> {
+        self.terms.iter().next_back()
+    }
+
+    /// Returns the exponent vector and coefficient of the leading term in the given order.
+    pub fn leading_term_by(&self, order: MonomialOrder) -> Option<(&Vec<u32>, &Expr)> {
+        self.terms
+            .iter()
+            .max_by(|(a, _), (b, _)| order.compare(a, b))
+    }
+
+    /// Returns the degree of the variable `var` in the polynomial.
+    pub fn degree(&self, var: &Expr) -> u32 {
+        match self.vars.iter().position(|v| v == var) {
+            Some(i) => self.terms.keys().map(|exps| exps[i]).max().unwrap_or(0),
+            None => 0,
+        }
+    }
+
+    /// Returns the coefficient of `var^n`, which may contain the other variables.
+    pub fn coefficient(&self, var: &Expr, n: u32) -> Polynomial {
+        let mut p = Polynomial::zero(&self.vars);
+        match self.vars.iter().position(|v| v == var) {
+            Some(i) => {
+                for (exps, c) in self.terms.iter().filter(|(exps, _)| exps[i] == n) {
+                    let mut exps = exps.clone();
+                    exps[i] = 0;
+                    p.terms.insert(exps, c.clone());
+                }
+            }
+            None if n == 0 => p = self.clone(),
+            None => {}
+        }
+        p
+    }
+
+    /// Returns the variables which occur in the polynomial.
+    pub fn variables(&self) -> Vec<Expr> {
+        self.vars
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.terms.keys().any(|exps| exps[*i] > 0))
+            .map(|(_, v)| v.clone())
+            .collect()
+    }
+
+    /// Divides `self` by `divisors` with respect to the lexicographic monomial order.
+    /// Returns the quotients and the remainder `r`, such that
+    /// `self = q_1 * divisors_1 + ... + q_n * divisors_n + r`, where no term of `r`
+    /// is divisible by a leading term of the divisors.
+    pub fn divide(&self, divisors: &[Polynomial]) -> (Vec<Polynomial>, Polynomial) {
+        self.divide_by(divisors, MonomialOrder::Lex)
+    }
+
+    /// Divides `self` by `divisors` with respect to the given monomial order, see [`Polynomial::divide`].
+    pub fn divide_by(
+        &self,
+        divisors: &[Polynomial],
+        order: MonomialOrder,
+    ) -> (Vec<Polynomial>, Polynomial) {
+        let vars = divisors
+            .iter()
+            .fold(self.vars.clone(), |vars, d| merge_variables(&vars, &d.vars));
+        let divisors: Vec<Polynomial> = divisors.iter().map(|d| d.embed(&vars)).collect();
+        let mut p = self.embed(&vars);
+        let mut quotients = vec![Polynomial::zero(&vars); divisors.len()];
+        let mut r = Polynomial::zero(&vars);
+        while let Some((exps, c)) = p.leading_term_by(order) {
+            let (exps, c) = (exps.clone(), c.clone());
+            let division = divisors.iter().enumerate().find_map(|(i, d)| {
+                let (dexps, dc) = d.leading_term_by(order)?;
+                let qexps: Option<Vec<u32>> = exps
+                    .iter()
+                    .zip(dexps.iter())
+                    .map(|(a, b)| a.checked_sub(*b))
+                    .collect();
+                qexps.map(|qexps| (i, Polynomial::monomial(div_coeff(&c, dc), qexps, &vars)))
+            });
+            match division {
+                Some((i, t)) => {
+                    p = p.sub(&t.mul(&divisors[i]));
+                    quotients[i] = quotients[i].add(&t);
+                }
+                None => {
+                    p.terms.remove(&exps);
+                    r.terms.insert(exps, c);
+                }
+            }
+        }
+        (quotients, r)
+    }
+}
+
+impl Expr {
+    /// Expands `self` by converting it to a sparse multivariate polynomial in its
+    /// generalized variables.
+    pub fn expand(&self) -> Expr {
+        Polynomial::from_expr(self).to_expr()
+    }
+
+    /// Returns the coefficient of `var^n` in the expanded form of `self`.
+    pub fn coefficient(&self, var: &Expr, n: u32) -> Expr {
+        let p = Polynomial::from_expr(self);
+        if p.vars.contains(var) || self.is_free_of(var) {
+            p.coefficient(var, n).to_expr()
+        } else {
+            // `var` is not a generalized variable of the expanded form, e.g. `x + 1` in `(x + 1)^2`
+            self.coefficient_gpe(var, n as i64)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    use super::Polynomial;
+
+    #[test]
+    fn test_polynomial_arithmetic() {
+        let mut kernel = AlgomyKernel::new();
+        let u = kernel.parse_eval_line("(x+2)^5*(x+3)^3*(x+4)^2");
+        assert_eq!(u.expand(), u.algebraic_expand());
+
+        let u = kernel.parse_eval_line("(x*(y+1)^3+1)*(x*(y+1)^2+1)");
+        assert_eq!(u.expand(), u.algebraic_expand());
+
+        let u = kernel.parse_eval_line("(a + Sin[x])^2 + 2*I*a/b");
+        assert_eq!(u.expand(), u.algebraic_expand());
+
+        let p = Polynomial::from_expr(&kernel.parse_eval_line("x^2*y + x*y^2 + y^2"));
+        let q = Polynomial::from_expr(&kernel.parse_eval_line("x*y - 1"));
+        let r = Polynomial::from_expr(&kernel.parse_eval_line("y^2 - 1"));
+        let (quotients, remainder) = p.divide(&[q.clone(), r.clone()]);
+        assert_eq!(quotients[0].to_expr(), kernel.parse_eval_line("x + y"));
+        assert_eq!(quotients[1].to_expr(), kernel.parse_eval_line("1"));
+        assert_eq!(remainder.to_expr(), kernel.parse_eval_line("x + y + 1"));
+        assert_eq!(
+            quotients[0]
+                .mul(&q)
+                .add(&quotients[1].mul(&r))
+                .add(&remainder),
+            p
+        );
+        assert_eq!(p.degree(&Expr::symbol("y")), 2);
+        assert_eq!(p.variables(), vec![Expr::symbol("x"), Expr::symbol("y")]);
+
+        let (x, y) = (Expr::symbol("x"), Expr::symbol("y"));
+        assert_eq!(
+            Polynomial::from_expr_with_vars(
+                &kernel.parse_eval_line("x*y + 1"),
+                std::slice::from_ref(&x)
+            ),
+            None
+        );
+        assert_eq!(q.with_vars(std::slice::from_ref(&x)), None);
+        let s = q.with_vars(&[y.clone(), x.clone()]).unwrap();
+        assert_eq!(s.vars, vec![y, x]);
+        assert_eq!(s.to_expr(), q.to_expr());
+    }
+
+    #[test]
+    fn test_coefficient() {
+        let mut kernel = AlgomyKernel::new();
+        assert_eq!(
+            kernel.parse_eval_line("Coefficient[(x + y)^3, x, 1]"),
+            kernel.parse_eval_line("3*y^2")
+        );
+        assert_eq!(
+            kernel.parse_eval_line("Coefficient[a*x^2 + b*x + c, x, 0]"),
+            kernel.parse_eval_line("c")
+        );
+        assert_eq!(
+            kernel.parse_eval_line("Variables[(x + 1)^2 + y*Sin[z]]"),
+            Expr::set(vec![
+                Expr::symbol("x"),
+                Expr::symbol("y"),
+                kernel.parse_eval_line("Sin[z]")
+            ])
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use num::{BigRational, One, Zero};
+
+use crate::{
+    expression::{Expr, ExprKind},
+    simplify,
+};
+
+use super::{
+    factor, integer, multivariate_gcd, polynomial_division, polynomial_extended_gcd,
+    polynomial_gcd, polynomial_quotient, polynomial_remainder, rational_coefficients, Polynomial,
+};
+
+/// Combines the terms of `u` over a common denominator and cancels the common factors of the
+/// resulting numerator and denominator.
+pub fn together(u: &Expr) -> Expr {
+    cancel(&rationalize(u))
+}
+
+/// Writes `u` as a single quotient, by recursively combining sums of quotients.
+fn rationalize(u: &Expr) -> Expr {
+    match u.kind {
+        ExprKind::Power => simplify(&Expr::power(rationalize(&u.base()), u.exponent())),
+        ExprKind::Product => simplify(&Expr::product(u.operands.iter().map(rationalize).collect())),
+        ExprKind::Sum => u
+            .operands
+            .iter()
+            .map(rationalize)
+            .reduce(|a, b| rationalize_sum(&a, &b))
+            .unwrap(),
+        _ => u.clone(),
+    }
+}
+
+/// Rewrites `m/r + n/s` as `(m*s + n*r)/(r*s)`, or `(m + n)/r` if the denominators are equal.
+fn rationalize_sum(u: &Expr, v: &Expr) -> Expr {
+    let (m, r) = (u.numerator(), u.denominator());
+    let (n, s) = (v.numerator(), v.denominator());
+    if r.is_one() && s.is_one() {
+        simplify(&Expr::plus(u.clone(), v.clone()))
+    } else if r == s {
+        simplify(&Expr::quotient(Expr::plus(m, n).algebraic_expand(), r))
+    } else {
+        let numerator = Expr::plus(Expr::times(m, s.clone()), Expr::times(n, r.clone()));
+        simplify(&Expr::quotient(
+            numerator.algebraic_expand(),
+            Expr::times(r, s),
+        ))
+    }
+}
+
+/// Cancels the greatest common divisor of the numerator and denominator of `u`, which must be
+/// (multivariate) polynomials with rational coefficients. The numerical content is pulled out in front.
+/// Sums are cancelled term by term.
+pub fn cancel(u: &Expr) -> Expr {
+    if u.kind == ExprKind::Sum {
+        return simplify(&Expr::sum(u.operands.iter().map(cancel).collect()));
+    }
+    let n = u.numerator().algebraic_expand();
+    let d = u.denominator().algebraic_expand();
+    if d.is_one() || n.is_zero() {
+        return u.clone();
+    }
+    let (pn, pd) = Polynomial::from_expr(&n).unify(&Polynomial::from_expr(&d));
+    if !pn
+        .terms
+        .values()
+        .chain(pd.terms.values())
+        .all(|c| c.is_rne())
+    {
+        return u.clone();
+    }
+
+    let g = multivariate_gcd(&pn, &pd);
+    let pn = pn.divide(std::slice::from_ref(&g)).0.remove(0);
+    let pd = pd.divide(std::slice::from_ref(&g)).0.remove(0);
+    let (cn, pn) = primitive(&pn);
+    let (cd, pd) = primitive(&pd);
+    simplify(&Expr::product(vec![
+        Expr::from_rational(cn / cd),
+        pn.to_expr(),
+        Expr::power(pd.to_expr(), Expr::int(-1)),
+    ]))
+}
+
+/// Writes a polynomial with rational coefficients as `c * p`, where `p` has coprime integer
+/// coefficients and a positive leading coefficient.
+fn primitive(u: &Polynomial) -> (BigRational, Polynomial) {
+    let coeffs: Vec<BigRational> = u.terms.values().map(|c| c.rational_rne()).collect();
+    let (c, _) = integer::from_rationals(&coeffs);
+    let c = if c.is_zero() { BigRational::one() } else { c };
+    (c.clone(), u.scale(&Expr::from_rational(c.recip())))
+}
+
+/// Computes the partial fraction decomposition of a rational function in `x` with rational
+/// coefficients. The denominator is factored over the integers, split into its coprime prime
+/// power factors with the extended Euclidean algorithm, and every part is expanded in powers
+/// of its factor. Returns `u` unchanged if it is not such a rational function.
+pub fn apart(u: &Expr, x: &Expr) -> Expr {
+    let n = u.numerator().algebraic_expand();
+    let d = u.denominator().algebraic_expand();
+    if rational_coefficients(&n, x).is_none() || rational_coefficients(&d, x).is_none() {
+        return u.clone();
+    }
+    if d.degree_gpe(x) <= 0 {
+        return simplify(&Expr::quotient(n, d)).algebraic_expand();
+    }
+    let g = polynomial_gcd(&n, &d, x);
+    let n = polynomial_quotient(&n, &g, x);
+    let d = polynomial_quotient(&d, &g, x);
+    let (q, mut r) = polynomial_division(&n, &d, x);
+
+    // Split the denominator into its constant factor and irreducible factors with multiplicities
+    let factored = factor(&d, x);
+    let operands = match factored.kind {
+        ExprKind::Product => factored.operands.clone(),
+        _ => vec![factored],
+    };
+    let mut constant = Expr::int(1);
+    let mut factors = Vec::new();
+    for v in operands {
+        if v.is_free_of(x) {
+            constant = simplify(&Expr::times(constant, v));
+        } else {
+            match v.exponent().as_i64() {
+                Some(m) if v.kind == ExprKind::Power => factors.push((v.base(), m)),
+                _ => factors.push((v, 1)),
+            }
+        }
+    }
+    r = simplify(&Expr::quotient(r, constant)).algebraic_expand();
+
+    let mut terms = vec![q];
+    for i in 0..factors.len() {
+        let (f, m) = &factors[i];
+        let power = simplify(&Expr::power(f.clone(), Expr::int(*m))).algebraic_expand();
+        // Numerator belonging to f^m: r*t mod f^m, where s*f^m + t*rest = 1
+        let a = if i + 1 == factors.len() {
+            r.clone()
+        } else {
+            let rest = simplify(&Expr::product(
+                factors[i + 1..]
+                    .iter()
+                    .map(|(f, m)| Expr::power(f.clone(), Expr::int(*m)))
+                    .collect(),
+            ))
+            .algebraic_expand();
+            let (_, _, t) = polynomial_extended_gcd(&power, &rest, x);
+            let a = polynomial_remainder(
+                &simplify(&Expr::times(r.clone(), t)).algebraic_expand(),
+                &power,
+                x,
+            );
+            r = polynomial_quotient(
+                &simplify(&Expr::minus(r.clone(), Expr::times(a.clone(), rest))).algebraic_expand(),
+                &power,
+                x,
+            );
+            a
+        };
+        // Expand the numerator in powers of f: a = c_0 + c_1*f + ... + c_(m-1)*f^(m-1)
+        let mut a = a;
+        for j in 0..*m {
+            let (quotient, c) = polynomial_division(&a, f, x);
+            terms.push(Expr::quotient(c, Expr::power(f.clone(), Expr::int(m - j))));
+            a = quotient;
+        }
+    }
+    simplify(&Expr::sum(terms))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    use super::{apart, cancel, together};
+
+    #[test]
+    fn test_together_cancel() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("1/x + 1/y", "(x + y)/(x*y)"),
+            ("1/x + 1/x^2", "(x + 1)/x^2"),
+            ("1/(x + 1) + 1/(x - 1)", "2*x/(x^2 - 1)"),
+            ("x/(x^2 - 1) - 1/(x - 1)", "-1/(x^2 - 1)"),
+            ("a/b + c", "(a + b*c)/b"),
+            ("1/(x - y) - 1/(x + y)", "2*y/(x^2 - y^2)"),
+            ("x + 1", "x + 1"),
+        ];
+        for (u, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            assert_eq!(together(&u), kernel.parse_eval_line(expected), "{}", u);
+        }
+
+        let cases = [
+            ("(x^2 - 1)/(x - 1)", "x + 1"),
+            ("(2*x^2 - 2)/(4*x - 4)", "(x + 1)/2"),
+            ("(x^2 + 2*x + 1)/(x^2 - 1)", "(x + 1)/(x - 1)"),
+            ("(x^2*y + x*y^2)/(x*y)", "x + y"),
+            ("(x^2 - y^2)/(x^2 + 2*x*y + y^2)", "(x - y)/(x + y)"),
+            ("(x^2 - 1)/(x - 1) + (x^2 - 4)/(x + 2)", "2*x - 1"),
+            ("0/(x - 1)", "0"),
+        ];
+        for (u, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            assert_eq!(cancel(&u), kernel.parse_eval_line(expected), "{}", u);
+        }
+
+        // Quotients of expressions which are not polynomials are left alone
+        let u = kernel.parse_eval_line("Sin[x]/x");
+        assert_eq!(cancel(&u), u);
+        let u = kernel.parse_eval_line("(x + 1)/(1.5*x)");
+        assert_eq!(cancel(&u), u);
+    }
+
+    #[test]
+    fn test_apart() {
+        let mut kernel = AlgomyKernel::new();
+        let x = Expr::symbol("x");
+        let cases = [
+            ("1/(x^2 - 1)", "1/(2*(x - 1)) - 1/(2*(x + 1))"),
+            ("(x^3 + 1)/(x^2 - 4)", "x + 9/(4*(x - 2)) + 7/(4*(x + 2))"),
+            ("1/(x^2*(x + 1))", "1/x^2 - 1/x + 1/(x + 1)"),
+            (
+                "(x + 2)/((x^2 + 1)*(x - 1)^2)",
+                "3/(2*(x - 1)^2) - 1/(x - 1) + (x - 1/2)/(x^2 + 1)",
+            ),
+            ("x^2/(x - 1)", "x + 1 + 1/(x - 1)"),
+            ("(x^2 - 1)/(x + 1)", "x - 1"),
+            ("(x + 1)^2/2", "1/2*x^2 + x + 1/2"),
+        ];
+        for (u, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            let result = apart(&u, &x);
+            assert_eq!(result, kernel.parse_eval_line(expected), "{}", u);
+            // The partial fractions add up to u
+            assert_eq!(together(&result), together(&u), "{}", u);
+        }
+
+        // Rational functions with coefficients that are not rational numbers are left alone
+        let u = kernel.parse_eval_line("1/(x^2 - a)");
+        assert_eq!(apart(&u, &x), u);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{calculus::derivative, expression::Expr, simplify};
+
+use super::Polynomial;
+
+/// Computes the resultant of two polynomials in `x` as the determinant of their Sylvester
+/// matrix. The coefficients may contain other variables, which makes the resultant useful for
+/// eliminating `x` from a system of polynomial equations. Returns `None` if `u` or `v` is not
+/// a polynomial in `x`.
+pub fn resultant(u: &Expr, v: &Expr, x: &Expr) -> Option<Expr> {
+    let u = u.algebraic_expand();
+    let v = v.algebraic_expand();
+    if !u.is_polynomial_gpe(x) || !v.is_polynomial_gpe(x) {
+        return None;
+    }
+    if u.is_zero() || v.is_zero() {
+        return Some(Expr::int(0));
+    }
+    let m = u.degree_gpe(x) as usize;
+    let n = v.degree_gpe(x) as usize;
+    if m == 0 && n == 0 {
+        return Some(Expr::int(1));
+    }
+
+    // Coefficients from the leading coefficient down to the constant term
+    let coefficients = |w: &Expr, d: usize| -> Vec<Expr> {
+        (0..=d)
+            .rev()
+            .map(|k| w.coefficient_gpe(x, k as i64).algebraic_expand())
+            .collect()
+    };
+    let (a, b) = (coefficients(&u, m), coefficients(&v, n));
+    let vars = a
+        .iter()
+        .chain(b.iter())
+        .fold(Polynomial::zero(&[]), |acc, c| {
+            acc.unify(&Polynomial::from_expr(c)).0
+        })
+        .vars;
+
+    let size = m + n;
+    let zero = Polynomial::zero(&vars);
+    let mut matrix = vec![vec![zero; size]; size];
+    for i in 0..n {
+        for (j, c) in a.iter().enumerate() {
+            matrix[i][i + j] = Polynomial::from_expr_with_vars(c, &vars)?;
+        }
+    }
+    for i in 0..m {
+        for (j, c) in b.iter().enumerate() {
+            matrix[n + i][i + j] = Polynomial::from_expr_with_vars(c, &vars)?;
+        }
+    }
+    Some(determinant(matrix, &vars).to_expr())
+}
+
+/// Computes the discriminant `(-1)^(n(n-1)/2) * Resultant[u, u', x] / lc(u)` of a polynomial
+/// in `x` of degree `n`, which vanishes exactly when `u` has a repeated root. Returns `None`
+/// if `u` is not a polynomial in `x`.
+pub fn discriminant(u: &Expr, x: &Expr) -> Option<Expr> {
+    let u = u.algebraic_expand();
+    if !u.is_polynomial_gpe(x) {
+        return None;
+    }
+    let n = u.degree_gpe(x);
+    if n <= 0 {
+        return Some(Expr::int(0));
+    }
+    if n == 1 {
+        return Some(Expr::int(1));
+    }
+    let r = resultant(&u, &derivative(&u, x), x)?;
+    let sign = if (n * (n - 1) / 2) % 2 == 0 { 1 } else { -1 };
+    Some(
+        simplify(&Expr::quotient(
+            Expr::times(Expr::int(sign), r),
+            u.leading_coefficient_gpe(x),
+        ))
+        .algebraic_expand(),
+    )
+}
+
+/// Computes the determinant of a square matrix of polynomials with the fraction-free
+/// elimination of Bareiss, in which every division is exact.
+pub fn determinant(mut matrix: Vec<Vec<Polynomial>>, vars: &[Expr]) -> Polynomial {
+    let size = matrix.len();
+    let mut sign = 1;
+    let mut previous = Polynomial::constant(Expr::int(1), vars);
+    for k in 0..size {
+        // Find a non-zero pivot
+        let Some(pivot) = (k..size).find(|&i| !matrix[i][k].is_zero()) else {
+            return Polynomial::zero(vars);
+        };
+        if pivot != k {
+            matrix.swap(pivot, k);
+            sign = -sign;
+        }
+        for i in k + 1..size {
+            for j in k + 1..size {
+                let p = matrix[k][k]
+                    .mul(&matrix[i][j])
+                    .sub(&matrix[i][k].mul(&matrix[k][j]));
+                matrix[i][j] = p.divide(std::slice::from_ref(&previous)).0.remove(0);
+            }
+        }
+        previous = matrix[k][k].clone();
+    }
+    matrix[size - 1][size - 1].scale(&Expr::int(sign))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    use super::{discriminant, resultant};
+
+    #[test]
+    fn test_resultant() {
+        let mut kernel = AlgomyKernel::new();
+        let x = Expr::symbol("x");
+        let cases = [
+            ("x^2 - 1", "x - 1", "0"),
+            ("x^2 - 1", "x - 2", "3"),
+            ("x^2 + 1", "x^2 - 2", "9"),
+            ("2*x^3 + x", "3*x^2 + 1", "1"),
+            ("x^2 + y^2 - 1", "x - y", "2*y^2 - 1"),
+            ("a*x + b", "c*x + d", "a*d - b*c"),
+            ("5", "x^3 + x", "125"),
+            // The coefficients y and -y cancel in their sum
+            ("y*x + 1", "-y*x + 1", "2*y"),
+        ];
+        for (u, v, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            let v = kernel.parse_eval_line(v);
+            assert_eq!(
+                resultant(&u, &v, &x),
+                Some(kernel.parse_eval_line(expected)),
+                "{} {}",
+                u,
+                v
+            );
+        }
+        let u = kernel.parse_eval_line("Sin[x] + 1");
+        assert_eq!(resultant(&u, &x, &x), None);
+
+        let cases = [
+            ("a*x^2 + b*x + c", "b^2 - 4*a*c"),
+            ("x^3 + p*x + q", "-4*p^3 - 27*q^2"),
+            ("(x - 1)^2*(x + 3)", "0"),
+            ("x^2 - 2", "8"),
+        ];
+        for (u, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            assert_eq!(
+                discriminant(&u, &x),
+                Some(kernel.parse_eval_line(expected)),
+                "{}",
+                u
+            );
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use num::{BigInt, BigRational, Integer, One, Signed, Zero};
+
+use crate::expression::Expr;
+
+/// Largest factor tried when searching for divisors of an integer.
+const TRIAL_DIVISION_LIMIT: u64 = 1_000_000;
+
+/// Returns the coefficients `[a_0, a_1, ..., a_n]` of `u` as a polynomial in `x`,
+/// if `u` is a polynomial in `x` with rational coefficients.
+pub fn rational_coefficients(u: &Expr, x: &Expr) -> Option<Vec<BigRational>> {
+    let u = u.algebraic_expand();
+    if !u.is_polynomial_gpe(x) {
+        return None;
+    }
+    (0..=u.degree_gpe(x))
+        .map(|k| {
+            let c = u.coefficient_gpe(x, k);
+            if c.is_rne() {
+                Some(c.rational_rne())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Evaluates a polynomial given by its coefficients `[a_0, ..., a_n]` at `r`.
+pub fn evaluate_coefficients(coeffs: &[BigRational], r: &BigRational) -> BigRational {
+    coeffs
+        .iter()
+        .rev()
+        .fold(BigRational::zero(), |acc, c| acc * r + c)
+}
+
+/// Divides a polynomial given by its coefficients by `x - r`, discarding the remainder.
+fn deflate(coeffs: &[BigRational], r: &BigRational) -> Vec<BigRational> {
+    let n = coeffs.len() - 1;
+    let mut q = vec![BigRational::zero(); n];
+    let mut carry = BigRational::zero();
+    for k in (0..n).rev() {
+        carry = &coeffs[k + 1] + carry * r;
+        q[k] = carry.clone();
+    }
+    q
+}
+
+/// Removes the zero leading coefficients of a polynomial given by its coefficients.
+fn trim(mut coeffs: Vec<BigRational>) -> Vec<BigRational> {
+    while coeffs.last().is_some_and(|c| c.is_zero()) {
+        coeffs.pop();
+    }
+    coeffs
+}
+
+/// Divides two polynomials given by their coefficients, where `v` is non-zero and has no zero
+/// leading coefficients. Returns the quotient and remainder.
+fn divide_coefficients(
+    u: &[BigRational],
+    v: &[BigRational],
+) -> (Vec<BigRational>, Vec<BigRational>) {
+    let mut r = trim(u.to_vec());
+    let n = v.len() - 1;
+    let mut q = vec![BigRational::zero(); r.len().saturating_sub(n)];
+    while r.len() > n {
+        let m = r.len() - 1;
+        let c = &r[m] / &v[n];
+        for (k, vk) in v.iter().enumerate() {
+            r[m - n + k] -= &c * vk;
+        }
+        q[m - n] = c;
+        r.pop();
+        r = trim(r);
+    }
+    (q, r)
+}
+
+/// Returns the Sturm sequence `p, p', -rem(p, p'), ...` of a non-constant polynomial, which
+/// ends with a greatest common divisor of `p` and `p'`.
+fn sturm_sequence(p: Vec<BigRational>) -> Vec<Vec<BigRational>> {
+    let derivative = p
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(k, c)| c * BigInt::from(k))
+        .collect();
+    let mut sequence = vec![p, derivative];
+    loop {
+        let n = sequence.len();
+        let (_, r) = divide_coefficients(&sequence[n - 2], &sequence[n - 1]);
+        if r.is_empty() {
+            return sequence;
+        }
+        sequence.push(r.into_iter().map(|c| -c).collect());
+    }
+}
+
+/// Counts the sign changes in the values of the polynomials of a Sturm sequence at `r`,
+/// skipping zeros.
+fn sign_changes(sequence: &[Vec<BigRational>], r: &BigRational) -> usize {
+    let values: Vec<BigRational> = sequence
+        .iter()
+        .map(|p| evaluate_coefficients(p, r))
+        .filter(|v| !v.is_zero())
+        .collect();
+    values
+        .windows(2)
+        .filter(|w| w[0].is_positive() != w[1].is_positive())
+        .count()
+}
+
+/// Counts the distinct real roots in the interval `[lo, hi]` of a non-zero polynomial given by
+/// its coefficients `[a_0, ..., a_n]`, using the Sturm sequence of its square-free part.
+pub fn count_real_roots(coeffs: &[BigRational], lo: &BigRational, hi: &BigRational) -> usize {
+    let p = trim(coeffs.to_vec());
+    if p.len() <= 1 || lo > hi {
+        return 0;
+    }
+    let g = sturm_sequence(p.clone()).pop().unwrap();
+    let (p, _) = divide_coefficients(&p, &g);
+    if p.len() <= 1 {
+        return 0;
+    }
+    let sequence = sturm_sequence(p);
+    // The difference counts the roots in (lo, hi]
+    let at_lo = evaluate_coefficients(&sequence[0], lo).is_zero();
+    sign_changes(&sequence, lo) - sign_changes(&sequence, hi) + usize::from(at_lo)
+}
+
+/// Returns all positive divisors of `n`.
+fn divisors(n: &BigInt) -> Vec<BigInt> {
+    // Factor n by trial division. A cofactor without small factors is treated as a prime.
+    let mut n = n.abs();
+    let mut factors: Vec<(BigInt, u32)> = Vec::new();
+    let mut p = BigInt::from(2);
+    while &p * &p <= n && p <= BigInt::from(TRIAL_DIVISION_LIMIT) {
+        let mut e = 0;
+        while n.is_multiple_of(&p) {
+            n /= &p;
+            e += 1;
+        }
+        if e > 0 {
+            factors.push((p.clone(), e));
+        }
+        p += 1;
+    }
+    if !n.is_one() {
+        factors.push((n, 1));
+    }
+
+    let mut divisors = vec![BigInt::one()];
+    for (p, e) in factors {
+        let mut next = Vec::new();
+        for d in divisors.iter() {
+            let mut pk = d.clone();
+            next.push(pk.clone());
+            for _ in 0..e {
+                pk *= &p;
+                next.push(pk.clone());
+            }
+        }
+        divisors = next;
+    }
+    divisors.sort();
+    divisors
+}
+
+/// Finds all rational roots of a polynomial given by its rational coefficients `[a_0, ..., a_n]`,
+/// together with their multiplicities. The roots are sorted in increasing order.
+pub fn rational_roots(coeffs: &[BigRational]) -> Vec<(BigRational, usize)> {
+    let mut coeffs = coeffs.to_vec();
+    while coeffs.last().is_some_and(|c| c.is_zero()) {
+        coeffs.pop();
+    }
+    let mut roots = Vec::new();
+    if coeffs.len() <= 1 {
+        return roots;
+    }
+
+    // Roots at zero
+    let zeros = coeffs.iter().take_while(|c| c.is_zero()).count();
+    if zeros > 0 {
+        roots.push((BigRational::zero(), zeros));
+        coeffs.drain(..zeros);
+    }
+
+    // Clear denominators, such that every rational root is p/q with p | a_0 and q | a_n
+    let lcm = coeffs
+        .iter()
+        .fold(BigInt::one(), |acc, c| acc.lcm(c.denom()));
+    let a0 = (&coeffs[0] * &lcm).to_integer();
+    let an = (&coeffs[coeffs.len() - 1] * &lcm).to_integer();
+    let ps = divisors(&a0);
+    let qs = divisors(&an);
+
+    let mut candidates: Vec<BigRational> = Vec::new();
+    for p in ps.iter() {
+        for q in qs.iter() {
+            let r = BigRational::new(p.clone(), q.clone());
+            candidates.push(-r.clone());
+            candidates.push(r);
+        }
+    }
+    candidates.sort();
+    candidates.dedup();
+
+    for r in candidates {
+        let mut multiplicity = 0;
+        while coeffs.len() > 1 && evaluate_coefficients(&coeffs, &r).is_zero() {
+            coeffs = deflate(&coeffs, &r);
+            multiplicity += 1;
+        }
+        if multiplicity > 0 {
+            roots.push((r, multiplicity));
+        }
+        if coeffs.len() <= 1 {
+            break;
+        }
+    }
+    roots.sort();
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigInt, BigRational};
+
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    use super::{count_real_roots, rational_coefficients, rational_roots};
+
+    fn rat(n: i64, d: i64) -> BigRational {
+        BigRational::new(BigInt::from(n), BigInt::from(d))
+    }
+
+    #[test]
+    fn test_rational_roots() {
+        let mut kernel = AlgomyKernel::new();
+        let x = Expr::symbol("x");
+
+        let u = kernel.parse_eval_line("Expand[(x-1)^2*(2*x+3)*x*(x^2+1)]");
+        let coeffs = rational_coefficients(&u, &x).unwrap();
+        assert_eq!(
+            rational_roots(&coeffs),
+            vec![(rat(-3, 2), 1), (rat(0, 1), 1), (rat(1, 1), 2)]
+        );
+
+        let u = kernel.parse_eval_line("x^2 - 2");
+        let coeffs = rational_coefficients(&u, &x).unwrap();
+        assert_eq!(rational_roots(&coeffs), vec![]);
+
+        let u = kernel.parse_eval_line("a*x + 1");
+        assert_eq!(rational_coefficients(&u, &x), None);
+    }
+
+    #[test]
+    fn test_count_real_roots() {
+        let mut kernel = AlgomyKernel::new();
+        let x = Expr::symbol("x");
+        let cases = [
+            ("x^2 - 2", (-2, 2), 2),
+            ("x^2 - 2", (0, 2), 1),
+            ("x^2 - 2", (-1, 1), 0),
+            ("x^2 + 1", (-10, 10), 0),
+            // Roots at the ends of the interval
+            ("x^2 - 1", (-1, 1), 2),
+            ("x^2 - 1", (1, 2), 1),
+            // Repeated roots are counted once
+            ("(x - 1)^3*(x^2 - 3)^2", (0, 2), 2),
+            ("x^5 - 3*x + 1", (-2, 2), 3),
+            ("7", (0, 1), 0),
+        ];
+        for (u, (lo, hi), expected) in cases {
+            let u = kernel.parse_eval_line(&format!("Expand[{}]", u));
+            let coeffs = rational_coefficients(&u, &x).unwrap();
+            assert_eq!(
+                count_real_roots(&coeffs, &rat(lo, 1), &rat(hi, 1)),
+                expected,
+                "{}",
+                u
+            );
+        }
+    }
+}
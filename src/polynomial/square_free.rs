@@ -0,0 +1,128 @@
+use crate::{calculus::derivative, expression::Expr, simplify};
+
+use super::{polynomial_gcd, polynomial_quotient};
+
+/// Computes the square-free factorization `u = c * a_1 * a_2^2 * ... * a_n^n` of a polynomial
+/// in `x` with Yun's algorithm, where the `a_i` are monic, square-free and pairwise coprime.
+/// Returns the constant `c` and the non-constant factors `a_i` with their multiplicities.
+pub fn square_free_factor(u: &Expr, x: &Expr) -> (Expr, Vec<(Expr, usize)>) {
+    let u = u.algebraic_expand();
+    let mut factors = Vec::new();
+    if u.degree_gpe(x) <= 0 {
+        return (u, factors);
+    }
+    let du = derivative(&u, x);
+    let g = polynomial_gcd(&u, &du, x);
+    let mut b = polynomial_quotient(&u, &g, x);
+    let c = polynomial_quotient(&du, &g, x);
+    let mut d = simplify(&Expr::minus(c, derivative(&b, x))).algebraic_expand();
+    let mut i = 1;
+    while b.degree_gpe(x) > 0 {
+        let a = polynomial_gcd(&b, &d, x);
+        b = polynomial_quotient(&b, &a, x);
+        let c = polynomial_quotient(&d, &a, x);
+        d = simplify(&Expr::minus(c, derivative(&b, x))).algebraic_expand();
+        if a.degree_gpe(x) > 0 {
+            factors.push((a, i));
+        }
+        i += 1;
+    }
+    (u.leading_coefficient_gpe(x), factors)
+}
+
+/// Writes a polynomial in `x` as the product of its square-free factors. Returns `u` unchanged
+/// if it is not a polynomial in `x`.
+pub fn square_free_factor_expr(u: &Expr, x: &Expr) -> Expr {
+    if !u.is_polynomial_gpe(x) {
+        return u.clone();
+    }
+    let (c, factors) = square_free_factor(u, x);
+    let mut result = vec![c];
+    for (a, multiplicity) in factors {
+        result.push(Expr::power(a, Expr::int(multiplicity as i64)));
+    }
+    simplify(&Expr::product(result))
+}
+
+/// Checks whether a polynomial in `x` has no repeated factors, i.e. whether it is coprime with
+/// its derivative.
+pub fn is_square_free(u: &Expr, x: &Expr) -> bool {
+    let u = u.algebraic_expand();
+    if u.is_zero() {
+        return false;
+    }
+    polynomial_gcd(&u, &derivative(&u, x), x).degree_gpe(x) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    use super::{is_square_free, square_free_factor, square_free_factor_expr};
+
+    /// The square-free factors with their multiplicities
+    type Factors = &'static [(&'static str, usize)];
+
+    #[test]
+    fn test_square_free_factor() {
+        let mut kernel = AlgomyKernel::new();
+        let x = Expr::symbol("x");
+        let cases: [(&str, &str, Factors); 5] = [
+            ("x^2 + 2*x + 1", "1", &[("x + 1", 2)]),
+            ("2*x^3 - 2*x", "2", &[("x^3 - x", 1)]),
+            (
+                "(x - 1)*(x + 2)^2*(x^2 + 1)^3",
+                "1",
+                &[("x - 1", 1), ("x + 2", 2), ("x^2 + 1", 3)],
+            ),
+            ("3*(x - 1)^3", "3", &[("x - 1", 3)]),
+            ("5", "5", &[]),
+        ];
+        for (u, c, factors) in cases {
+            let u = kernel.parse_eval_line(u);
+            let factors = factors
+                .iter()
+                .map(|(a, i)| (kernel.parse_eval_line(a).algebraic_expand(), *i))
+                .collect();
+            assert_eq!(
+                square_free_factor(&u, &x),
+                (kernel.parse_eval_line(c), factors),
+                "{}",
+                u
+            );
+        }
+
+        let cases = [
+            ("Expand[(x - 1)*(x + 2)^2]", "(x - 1)*(x + 2)^2"),
+            ("2*x^2 + 4*x + 2", "2*(x + 1)^2"),
+            ("Sin[x]^2", "Sin[x]^2"),
+        ];
+        for (u, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            assert_eq!(
+                square_free_factor_expr(&u, &x),
+                kernel.parse_eval_line(expected),
+                "{}",
+                u
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_square_free() {
+        let mut kernel = AlgomyKernel::new();
+        let x = Expr::symbol("x");
+        let cases = [
+            ("x^2 - 1", true),
+            ("x^3 - 3*x + 2", false),
+            ("(x^2 + 1)^2", false),
+            ("x^2 + 1", true),
+            ("3", true),
+            ("0", false),
+        ];
+        for (u, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            assert_eq!(is_square_free(&u, &x), expected, "{}", u);
+        }
+    }
+}
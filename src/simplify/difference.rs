@@ -1,3 +1,5 @@
+use num::Zero;
+
 use crate::expression::{Expr, ExprKind};
 
 use super::{product::simplify_product, sum::simplify_sum};
@@ -6,7 +8,9 @@ pub fn simplify_difference(expr: &Expr) -> Expr {
     let u = &expr.operands[0];
     let v = &expr.operands[1];
     match (&u.kind, &v.kind) {
-        (ExprKind::Integer(0), _) => simplify_product(&Expr::times(Expr::int(-1), v.clone())),
+        (ExprKind::Integer(n), _) if n.is_zero() => {
+            simplify_product(&Expr::times(Expr::int(-1), v.clone()))
+        }
         _ => simplify_sum(&Expr::plus(
             u.clone(),
             // Not sure if this simplify is necessary
@@ -15,4 +19,4 @@ pub fn simplify_difference(expr: &Expr) -> Expr {
     }
 }
 
-// TODO: Write tests
\ No newline at end of file
+// TODO: Write tests
@@ -1,16 +1,22 @@
+use num::{BigInt, One, Signed};
+
 use crate::expression::{Expr, ExprKind};
 
 pub fn simplify_factorial(expr: &Expr) -> Expr {
     let n = &expr.operands[0];
     match &n.kind {
-        ExprKind::Integer(n) if *n >= 0 => Expr::int((1..=*n).product()),
+        ExprKind::Integer(n) if !n.is_negative() => {
+            Expr::int(num::range_inclusive(BigInt::one(), n.clone()).product::<BigInt>())
+        }
         _ => expr.clone(),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{expression::Expr, simplify::factorial::simplify_factorial};
+    use num::BigInt;
+
+    use crate::{expression::Expr, parser::AlgomyKernel, simplify::factorial::simplify_factorial};
 
     #[test]
     fn test_simplify_factorial() {
@@ -22,6 +28,20 @@ mod tests {
             simplify_factorial(&Expr::factorial(Expr::int(5))),
             Expr::int(120)
         );
+        assert_eq!(
+            simplify_factorial(&Expr::factorial(Expr::int(30))),
+            Expr::int(
+                "265252859812191058636308480000000"
+                    .parse::<BigInt>()
+                    .unwrap()
+            )
+        );
+        let mut kernel = AlgomyKernel::new();
+        assert_eq!(
+            kernel.parse_eval_line("30!"),
+            simplify_factorial(&Expr::factorial(Expr::int(30)))
+        );
+        assert_eq!(kernel.parse_eval_line("30!/28!"), Expr::int(870));
         assert_eq!(
             simplify_factorial(&Expr::factorial(Expr::int(-5))),
             Expr::factorial(Expr::int(-5))
@@ -1,3 +1,5 @@
+use num::{ToPrimitive, Zero};
+
 use crate::{
     evaluate::{
         evaluate_difference_gaussian, evaluate_power_gaussian, evaluate_product,
@@ -10,15 +12,16 @@ use super::rational_number::simplify_rational_number;
 
 pub fn simplify_gaussian_number(u: &Expr) -> Expr {
     match u.kind {
-        ExprKind::Integer(_) => u.clone(),
+        ExprKind::Integer(_) | ExprKind::Real(_) => u.clone(),
         ExprKind::Fraction(_, _) => simplify_rational_number(u),
         ExprKind::Gaussian => {
-            let r = simplify_rational_number(&u.operands[0]);
-            let i = simplify_rational_number(&u.operands[1]);
-            match (&r.kind, &i.kind) {
+            let r = simplify_gaussian_number(&u.operands[0]);
+            let i = simplify_gaussian_number(&u.operands[1]);
+            if i.is_zero() {
                 // r + 0i -> r
-                (_, ExprKind::Integer(0)) => r.clone(),
-                _ => Expr::gaussian(r, i),
+                r
+            } else {
+                Expr::gaussian(r, i)
             }
         }
         _ => u.clone(),
@@ -37,9 +40,9 @@ pub fn simplify_grne(u: &Expr) -> Expr {
 fn simplify_grne_recursive(u: &Expr) -> Expr {
     match u.kind {
         ExprKind::Integer(_) => u.clone(),
-        ExprKind::Fraction(_, 0) => Expr::undefined(),
+        ExprKind::Fraction(_, ref d) if d.is_zero() => Expr::undefined(),
         ExprKind::Fraction(_, _) => u.clone(),
-        ExprKind::Gaussian => u.clone(),
+        ExprKind::Real(_) | ExprKind::Gaussian => u.clone(),
         _ => {
             if u.num_operands() == 1 {
                 let v = simplify_grne_recursive(&u.operands[0]);
@@ -78,7 +81,10 @@ fn simplify_grne_recursive(u: &Expr) -> Expr {
                             Expr::undefined()
                         } else {
                             // Since u is an RNE, u.operands[1] is an integer
-                            evaluate_power_gaussian(&v, u.operands[1].numerator_rne())
+                            match u.operands[1].numerator_rne().to_i64() {
+                                Some(n) => evaluate_power_gaussian(&v, n),
+                                None => Expr::undefined(),
+                            }
                         }
                     }
                     _ => unreachable!(),
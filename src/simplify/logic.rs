@@ -70,8 +70,16 @@ fn simplify_or_recursive(l: &[Expr]) -> Vec<Expr> {
             (ExprKind::Or, ExprKind::Or) => {
                 merge_nary(&u1.operands, &u2.operands, simplify_or_recursive)
             }
-            (ExprKind::Or, _) => merge_nary(&u1.operands, &[u2.clone()], simplify_or_recursive),
-            (_, ExprKind::Or) => merge_nary(&[u1.clone()], &u2.operands, simplify_or_recursive),
+            (ExprKind::Or, _) => merge_nary(
+                &u1.operands,
+                std::slice::from_ref(u2),
+                simplify_or_recursive,
+            ),
+            (_, ExprKind::Or) => merge_nary(
+                std::slice::from_ref(u1),
+                &u2.operands,
+                simplify_or_recursive,
+            ),
             _ => unreachable!(),
         }
     } else {
@@ -142,8 +150,16 @@ fn simplify_and_recursive(l: &[Expr]) -> Vec<Expr> {
             (ExprKind::And, ExprKind::And) => {
                 merge_nary(&u1.operands, &u2.operands, simplify_and_recursive)
             }
-            (ExprKind::And, _) => merge_nary(&u1.operands, &[u2.clone()], simplify_and_recursive),
-            (_, ExprKind::And) => merge_nary(&[u1.clone()], &u2.operands, simplify_and_recursive),
+            (ExprKind::And, _) => merge_nary(
+                &u1.operands,
+                std::slice::from_ref(u2),
+                simplify_and_recursive,
+            ),
+            (_, ExprKind::And) => merge_nary(
+                std::slice::from_ref(u1),
+                &u2.operands,
+                simplify_and_recursive,
+            ),
             _ => unreachable!(),
         }
     } else {
@@ -11,6 +11,7 @@ mod power;
 mod product;
 mod quotient;
 mod rational_number;
+mod relation;
 mod set;
 mod sum;
 
@@ -21,18 +22,29 @@ use power::simplify_power;
 use product::simplify_product;
 use quotient::simplify_quotient;
 use rational_number::simplify_rational_number;
+pub use rational_number::simplify_rne;
+use relation::simplify_relation;
 use sum::simplify_sum;
 
 use self::{
     logic::{simplify_and, simplify_not, simplify_or},
-    set::{simplify_intersection, simplify_set, simplify_union, simplify_member, simplify_set_difference},
+    set::{
+        simplify_intersection, simplify_member, simplify_set, simplify_set_difference,
+        simplify_union,
+    },
 };
 
 pub fn simplify(expr: &Expr) -> Expr {
     match &expr.kind {
-        ExprKind::Integer(_) | ExprKind::Symbol(_) | ExprKind::Boolean(_) => expr.clone(),
+        ExprKind::Integer(_) | ExprKind::Real(_) | ExprKind::Symbol(_) | ExprKind::Boolean(_) => {
+            expr.clone()
+        }
         ExprKind::Fraction(_, _) => simplify_rational_number(expr),
         ExprKind::Gaussian => simplify_gaussian_number(expr),
+        // The right-hand side of a delayed rule is only evaluated when the rule is applied
+        ExprKind::RuleDelayed => {
+            Expr::rule_delayed(simplify(&expr.operands[0]), expr.operands[1].clone())
+        }
         kind => {
             let expr = expr.map(simplify);
             match kind {
@@ -48,6 +60,15 @@ pub fn simplify(expr: &Expr) -> Expr {
                 ExprKind::Or => simplify_or(&expr),
                 ExprKind::And => simplify_and(&expr),
 
+                ExprKind::Equal
+                | ExprKind::Unequal
+                | ExprKind::Less
+                | ExprKind::LessEqual
+                | ExprKind::Greater
+                | ExprKind::GreaterEqual => simplify_relation(&expr),
+
+                ExprKind::Rule | ExprKind::List => expr,
+
                 ExprKind::Set => simplify_set(&expr),
                 ExprKind::Union => simplify_union(&expr),
                 ExprKind::Intersection => simplify_intersection(&expr),
@@ -60,7 +81,7 @@ pub fn simplify(expr: &Expr) -> Expr {
     }
 }
 
-pub(self) fn merge_nary<F>(p: &[Expr], q: &[Expr], simplify_fn: F) -> Vec<Expr>
+fn merge_nary<F>(p: &[Expr], q: &[Expr], simplify_fn: F) -> Vec<Expr>
 where
     F: Fn(&[Expr]) -> Vec<Expr>,
 {
@@ -71,7 +92,7 @@ where
     } else {
         let p1 = &p[0];
         let q1 = &q[0];
-        let h = simplify_fn(&vec![p1.clone(), q1.clone()]);
+        let h = simplify_fn(&[p1.clone(), q1.clone()]);
         match &h[..] {
             [] => merge_nary(&p[1..], &q[1..], simplify_fn),
             [h1] => {
@@ -82,11 +103,11 @@ where
             [a, b] => {
                 if a == p1 && b == q1 {
                     let mut r = vec![p1.clone()];
-                    r.append(&mut merge_nary(&p[1..], &q, simplify_fn));
+                    r.append(&mut merge_nary(&p[1..], q, simplify_fn));
                     r
                 } else if a == q1 && b == p1 {
                     let mut r = vec![q1.clone()];
-                    r.append(&mut merge_nary(&p, &q[1..], simplify_fn));
+                    r.append(&mut merge_nary(p, &q[1..], simplify_fn));
                     r
                 } else {
                     unreachable!()
@@ -1,40 +1,87 @@
-use crate::expression::{Expr, ExprKind};
+use num::{traits::Pow, BigInt, One, ToPrimitive, Zero};
 
-use super::{product::simplify_product, gaussian_number::simplify_grne};
+use crate::{
+    evaluate::evaluate_real_power,
+    expression::{Expr, ExprKind},
+    message::{indeterminate_expression, infinite_expression},
+};
+
+use super::{
+    gaussian_number::simplify_grne, product::simplify_product,
+    rational_number::simplify_rational_number,
+};
 
 pub fn simplify_power(u: &Expr) -> Expr {
     let v = &u.operands[0];
     let w = &u.operands[1];
     match (&v.kind, &w.kind) {
         (ExprKind::Undefined, _) | (_, ExprKind::Undefined) => Expr::undefined(),
-        (ExprKind::Integer(0), _) => {
+        (ExprKind::Integer(n), _) if n.is_zero() => {
             if w.is_positive_num() {
                 Expr::int(0)
             } else {
+                if w.is_zero() {
+                    indeterminate_expression();
+                } else if w.is_real_number() {
+                    infinite_expression();
+                }
                 Expr::undefined()
             }
         }
-        (ExprKind::Integer(1), _) => Expr::int(1),
-        (_, ExprKind::Integer(n)) => simplify_integer_power(&v, *n),
+        (ExprKind::Integer(n), _) if n.is_one() => Expr::int(1),
+        (_, ExprKind::Integer(n)) => match n.to_i64() {
+            Some(n) => simplify_integer_power(v, n),
+            None => u.clone(),
+        },
+        (ExprKind::Integer(_) | ExprKind::Fraction(_, _), ExprKind::Fraction(p, q)) => {
+            simplify_rational_root(v, p, q).unwrap_or_else(|| u.clone())
+        }
+        (ExprKind::Real(_), ExprKind::Fraction(_, _) | ExprKind::Real(_))
+        | (ExprKind::Integer(_) | ExprKind::Fraction(_, _), ExprKind::Real(_)) => {
+            evaluate_real_power(v, w).unwrap_or_else(|| u.clone())
+        }
         (_, _) => u.clone(),
     }
 }
 
+/// Evaluates v^(p/q) where `v` is a positive integer or fraction which is a perfect `q`-th power.
+fn simplify_rational_root(v: &Expr, p: &BigInt, q: &BigInt) -> Option<Expr> {
+    if !v.is_positive_num() {
+        return None;
+    }
+    let q = q.to_u32()?;
+    let n = v.numerator_rne();
+    let d = v.denominator_rne();
+    let (rn, rd) = (n.nth_root(q), d.nth_root(q));
+    if Pow::pow(&rn, q) == n && Pow::pow(&rd, q) == d {
+        let r = simplify_rational_number(&Expr::frac(rn, rd));
+        Some(simplify_integer_power(&r, p.to_i64()?))
+    } else {
+        None
+    }
+}
+
 pub fn simplify_integer_power(v: &Expr, n: i64) -> Expr {
     match (&v.kind, n) {
-        (ExprKind::Integer(_) | ExprKind::Fraction(_, _) | ExprKind::Gaussian, _) => {
-            simplify_grne(&Expr::power(v.clone(), Expr::int(n)))
-        }
+        (
+            ExprKind::Integer(_)
+            | ExprKind::Fraction(_, _)
+            | ExprKind::Real(_)
+            | ExprKind::Gaussian,
+            _,
+        ) => simplify_grne(&Expr::power(v.clone(), Expr::int(n))),
         (_, 0) => Expr::int(1),
         (_, 1) => v.clone(),
         (ExprKind::Power, _) => {
             let r = v.operands[0].clone();
             let s = v.operands[1].clone();
             let p = simplify_product(&Expr::times(s, Expr::int(n)));
-            if let ExprKind::Integer(p) = p.kind {
-                simplify_integer_power(&r, p)
-            } else {
-                Expr::power(r, p)
+            match p.kind {
+                ExprKind::Integer(ref m) => match m.to_i64() {
+                    Some(m) => simplify_integer_power(&r, m),
+                    None => Expr::power(r, p),
+                },
+                _ => Expr::power(r, p),
             }
         }
         (ExprKind::Product, _) => {
@@ -47,7 +94,12 @@ pub fn simplify_integer_power(v: &Expr, n: i64) -> Expr {
 
 #[cfg(test)]
 mod tests {
-    use crate::{expression::{Expr, ExprKind}, simplify::power::simplify_power};
+    use num::{BigInt, One};
+
+    use crate::{
+        expression::{Expr, ExprKind},
+        simplify::power::simplify_power,
+    };
 
     #[test]
     fn test_simplify_power() {
@@ -84,11 +136,37 @@ mod tests {
             }
         );
         assert_eq!(
-            simplify_power(&Expr::power(
-                Expr::int(100),
-                Expr::int(3)
-            )),
+            simplify_power(&Expr::power(Expr::int(100), Expr::int(3))),
             Expr::int(1000000)
+        );
+        // 2^100 does not overflow
+        assert_eq!(
+            simplify_power(&Expr::power(Expr::int(2), Expr::int(100))),
+            Expr::int(BigInt::one() << 100)
         )
     }
+
+    #[test]
+    fn test_simplify_rational_root() {
+        // 4^(1/2) -> 2
+        assert_eq!(
+            simplify_power(&Expr::power(Expr::int(4), Expr::frac(1, 2))),
+            Expr::int(2)
+        );
+        // (8/27)^(2/3) -> 4/9
+        assert_eq!(
+            simplify_power(&Expr::power(Expr::frac(8, 27), Expr::frac(2, 3))),
+            Expr::frac(4, 9)
+        );
+        // 16^(-3/4) -> 1/8
+        assert_eq!(
+            simplify_power(&Expr::power(Expr::int(16), Expr::frac(-3, 4))),
+            Expr::frac(1, 8)
+        );
+        // 2^(1/2) and (-8)^(1/3) are not perfect powers of a positive number
+        let u = Expr::power(Expr::int(2), Expr::frac(1, 2));
+        assert_eq!(simplify_power(&u), u);
+        let u = Expr::power(Expr::int(-8), Expr::frac(1, 3));
+        assert_eq!(simplify_power(&u), u);
+    }
 }
@@ -1,27 +1,26 @@
+use num::One;
+
 use crate::expression::{Expr, ExprKind};
 
-use super::{
-    gaussian_number::simplify_grne, power::simplify_power,
-    sum::simplify_sum, merge_nary,
-};
+use super::{gaussian_number::simplify_grne, merge_nary, power::simplify_power, sum::simplify_sum};
 
 pub fn simplify_product(u: &Expr) -> Expr {
     if u.operands.iter().find(|v| v.is_undefined()).is_some() {
         // ... * undefined -> undefined
         Expr::undefined()
-    } else if u
-        .operands
-        .iter()
-        .find(|v| v.kind == ExprKind::Integer(0))
-        .is_some()
-    {
+    } else if u.operands.iter().any(|v| v.is_zero()) {
         // ... * 0 -> 0
         Expr::int(0)
     } else if u.operands.len() == 1 {
         // *a -> a
         u.operands[0].clone()
     } else {
-        let v: Vec<Expr> = simplify_product_recursive(&u.operands);
+        let mut v: Vec<Expr> = simplify_product_recursive(&u.operands);
+        if v.iter().filter(|w| is_number(w)).count() > 1 {
+            // Powers of numbers with the same base can merge into a new number, e.g.
+            // 2^(1/2) * 2^(1/2) -> 2, which still has to be combined with the other numbers
+            v = simplify_product_recursive(&v);
+        }
         match v.len() {
             0 => Expr::int(1),
             1 => v[0].clone(),
@@ -33,33 +32,43 @@ pub fn simplify_product(u: &Expr) -> Expr {
     }
 }
 
+fn is_number(u: &Expr) -> bool {
+    u.is_real_number() || u.kind == ExprKind::Gaussian
+}
+
 fn simplify_product_recursive(l: &[Expr]) -> Vec<Expr> {
     if l.len() == 2 && l[0].kind != ExprKind::Product && l[1].kind != ExprKind::Product {
         let u1 = &l[0];
         let u2 = &l[1];
         match (&u1.kind, &u2.kind) {
             (
-                ExprKind::Integer(_) | ExprKind::Fraction(_, _) | ExprKind::Gaussian,
-                ExprKind::Integer(_) | ExprKind::Fraction(_, _) | ExprKind::Gaussian,
+                ExprKind::Integer(_)
+                | ExprKind::Fraction(_, _)
+                | ExprKind::Real(_)
+                | ExprKind::Gaussian,
+                ExprKind::Integer(_)
+                | ExprKind::Fraction(_, _)
+                | ExprKind::Real(_)
+                | ExprKind::Gaussian,
             ) => {
                 let p = simplify_grne(&Expr::times(u1.clone(), u2.clone()));
-                if let ExprKind::Integer(1) = p.kind {
+                if p.is_one() {
                     vec![]
                 } else {
                     vec![p]
                 }
             }
-            (ExprKind::Integer(1), _) => {
+            (ExprKind::Integer(n), _) if n.is_one() => {
                 vec![u2.clone()]
             }
-            (_, ExprKind::Integer(1)) => {
+            (_, ExprKind::Integer(n)) if n.is_one() => {
                 vec![u1.clone()]
             }
             _ => {
                 if u1.base() == u2.base() {
                     let s = simplify_sum(&Expr::plus(u1.exponent(), u2.exponent()));
                     let p = simplify_power(&Expr::power(u1.base(), s));
-                    if let ExprKind::Integer(1) = p.kind {
+                    if p.is_one() {
                         vec![]
                     } else {
                         vec![p]
@@ -75,9 +84,19 @@ fn simplify_product_recursive(l: &[Expr]) -> Vec<Expr> {
         let u1 = &l[0];
         let u2 = &l[1];
         match (&u1.kind, &u2.kind) {
-            (ExprKind::Product, ExprKind::Product) => merge_nary(&u1.operands, &u2.operands, simplify_product_recursive),
-            (ExprKind::Product, _) => merge_nary(&u1.operands, &[u2.clone()], simplify_product_recursive),
-            (_, ExprKind::Product) => merge_nary(&[u1.clone()], &u2.operands, simplify_product_recursive),
+            (ExprKind::Product, ExprKind::Product) => {
+                merge_nary(&u1.operands, &u2.operands, simplify_product_recursive)
+            }
+            (ExprKind::Product, _) => merge_nary(
+                &u1.operands,
+                std::slice::from_ref(u2),
+                simplify_product_recursive,
+            ),
+            (_, ExprKind::Product) => merge_nary(
+                std::slice::from_ref(u1),
+                &u2.operands,
+                simplify_product_recursive,
+            ),
             _ => unreachable!(),
         }
     } else {
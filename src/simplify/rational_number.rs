@@ -1,4 +1,4 @@
-use num::Integer;
+use num::{Integer, Signed, ToPrimitive, Zero};
 
 use crate::{
     evaluate::{
@@ -13,15 +13,15 @@ use crate::{
 pub fn simplify_rational_number(u: &Expr) -> Expr {
     match u.kind {
         ExprKind::Integer(_) => u.clone(),
-        ExprKind::Fraction(n, d) => {
-            if n.rem_euclid(d) == 0 {
-                Expr::int(n.div_euclid(d))
+        ExprKind::Fraction(ref n, ref d) => {
+            if n.mod_floor(d).is_zero() {
+                Expr::int(n.div_floor(d))
             } else {
-                let g = n.gcd(&d);
-                if d > 0 {
-                    Expr::frac(n.div_euclid(g), d.div_euclid(g))
+                let g = n.gcd(d);
+                if d.is_positive() {
+                    Expr::frac(n / &g, d / &g)
                 } else {
-                    Expr::frac((-n).div_euclid(g), (-d).div_euclid(g))
+                    Expr::frac(-n / &g, -d / &g)
                 }
             }
         }
@@ -44,7 +44,7 @@ pub fn simplify_rne(u: &Expr) -> Expr {
 fn simplify_rne_recursive(u: &Expr) -> Expr {
     match u.kind {
         ExprKind::Integer(_) => u.clone(),
-        ExprKind::Fraction(_, 0) => Expr::undefined(),
+        ExprKind::Fraction(_, ref d) if d.is_zero() => Expr::undefined(),
         ExprKind::Fraction(_, _) => u.clone(),
         _ => {
             if u.num_operands() == 1 {
@@ -84,7 +84,10 @@ fn simplify_rne_recursive(u: &Expr) -> Expr {
                             Expr::undefined()
                         } else {
                             // Since u is an RNE, u.operands[1] is an integer
-                            evaluate_power(&v, u.operands[1].numerator_rne())
+                            match u.operands[1].numerator_rne().to_i64() {
+                                Some(n) => evaluate_power(&v, n),
+                                None => Expr::undefined(),
+                            }
                         }
                     }
                     _ => unreachable!(),
@@ -0,0 +1,163 @@
+use std::cmp::Ordering;
+
+use crate::expression::{Expr, ExprKind};
+
+use super::simplify;
+
+/// Simplifies an equation or inequality `lhs ~ rhs`. The relation evaluates to `True` or
+/// `False` if `lhs - rhs` simplifies to a real number, or, for equations, if both sides are
+/// identical or distinct numbers. Otherwise the relation is kept symbolic.
+pub fn simplify_relation(u: &Expr) -> Expr {
+    let (lhs, rhs) = (&u.operands[0], &u.operands[1]);
+    if lhs.is_undefined() || rhs.is_undefined() {
+        return Expr::undefined();
+    }
+    if lhs == rhs {
+        return Expr::bool(matches!(
+            u.kind,
+            ExprKind::Equal | ExprKind::LessEqual | ExprKind::GreaterEqual
+        ));
+    }
+    if !is_algebraic(lhs) || !is_algebraic(rhs) {
+        // Booleans, lists and sets can only be compared for equality
+        return match u.kind {
+            ExprKind::Equal if lhs.is_atomic() && rhs.is_atomic() => Expr::bool(false),
+            ExprKind::Unequal if lhs.is_atomic() && rhs.is_atomic() => Expr::bool(true),
+            _ => u.clone(),
+        };
+    }
+
+    let d = simplify(&Expr::minus(lhs.clone(), rhs.clone()));
+    if d.is_real_number() {
+        let ord = d.cmp(&Expr::int(0));
+        Expr::bool(match u.kind {
+            ExprKind::Equal => ord == Ordering::Equal,
+            ExprKind::Unequal => ord != Ordering::Equal,
+            ExprKind::Less => ord == Ordering::Less,
+            ExprKind::LessEqual => ord != Ordering::Greater,
+            ExprKind::Greater => ord == Ordering::Greater,
+            ExprKind::GreaterEqual => ord != Ordering::Less,
+            _ => unreachable!(),
+        })
+    } else if d.kind == ExprKind::Gaussian {
+        // Distinct complex numbers are unequal, but not ordered
+        match u.kind {
+            ExprKind::Equal => Expr::bool(false),
+            ExprKind::Unequal => Expr::bool(true),
+            _ => u.clone(),
+        }
+    } else {
+        u.clone()
+    }
+}
+
+fn is_algebraic(u: &Expr) -> bool {
+    !u.is_relation()
+        && !matches!(
+            u.kind,
+            ExprKind::Boolean(_)
+                | ExprKind::Not
+                | ExprKind::Or
+                | ExprKind::And
+                | ExprKind::Rule
+                | ExprKind::RuleDelayed
+                | ExprKind::List
+                | ExprKind::Set
+                | ExprKind::Union
+                | ExprKind::Intersection
+                | ExprKind::SetDifference
+                | ExprKind::Member
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::{Expr, ExprKind},
+        parser::AlgomyKernel,
+    };
+
+    use super::simplify_relation;
+
+    #[test]
+    fn test_relations() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("1", ExprKind::Equal, "1", Some(true)),
+            ("1/2", ExprKind::Equal, "2/4", Some(true)),
+            ("1", ExprKind::Unequal, "1", Some(false)),
+            ("2", ExprKind::Less, "3", Some(true)),
+            ("2", ExprKind::LessEqual, "3", Some(true)),
+            ("2", ExprKind::Greater, "3", Some(false)),
+            ("3", ExprKind::GreaterEqual, "3", Some(true)),
+            ("0.5", ExprKind::Less, "1/3", Some(false)),
+            ("x + 1", ExprKind::Greater, "x", Some(true)),
+            ("x", ExprKind::Equal, "x", Some(true)),
+            ("x - x", ExprKind::Less, "1", Some(true)),
+            ("I", ExprKind::Equal, "1", Some(false)),
+            ("I", ExprKind::Unequal, "2*I", Some(true)),
+            ("True", ExprKind::Equal, "False", Some(false)),
+            ("{1, 2}", ExprKind::Equal, "{1, 2}", Some(true)),
+            // Relations which cannot be decided are kept
+            ("Pi", ExprKind::Greater, "3", None),
+            ("x", ExprKind::Equal, "y", None),
+            ("x + 2", ExprKind::Equal, "3", None),
+            ("I", ExprKind::Less, "1", None),
+            ("{1, 2}", ExprKind::Equal, "{x, 2}", None),
+        ];
+        for (lhs, kind, rhs, expected) in cases {
+            let u = Expr::relation(
+                kind,
+                kernel.parse_eval_line(lhs),
+                kernel.parse_eval_line(rhs),
+            );
+            let expected = expected.map_or_else(|| u.clone(), Expr::bool);
+            assert_eq!(simplify_relation(&u), expected, "{}", u);
+        }
+
+        // Chains compare each adjacent pair
+        let cases = [
+            ("1 == 1 == 1", true),
+            ("1 < 2 < 3", true),
+            ("1 < 3 < 2", false),
+            ("1 <= 1 < 2 != 3", true),
+            ("(1 == 1) == True", true),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                kernel.parse_eval_line(input),
+                Expr::bool(expected),
+                "{}",
+                input
+            );
+        }
+        // Decided relations simplify the logical operators around them
+        let cases = [
+            ("1 < 2 && 3 > 4", "False"),
+            ("x > 0 && 2 > 1", "x > 0"),
+            ("x > 0 || 1 != 1", "x > 0"),
+            ("x < 1 || 0 < 1", "True"),
+            ("x > 0 && x < 1", "(x > 0 && x < 1)"),
+            ("!(1 == 2)", "True"),
+            ("5! != 120", "False"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                kernel.parse_eval_line(input).to_string(),
+                expected,
+                "{}",
+                input
+            );
+        }
+
+        let u = kernel.parse_eval_line("x < y <= z");
+        assert_eq!(u.kind, ExprKind::And);
+        assert_eq!(
+            u.operands,
+            vec![
+                kernel.parse_eval_line("Less[x, y]"),
+                kernel.parse_eval_line("LessEqual[y, z]")
+            ]
+        );
+    }
+}
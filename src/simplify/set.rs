@@ -12,7 +12,16 @@ pub fn simplify_set(expr: &Expr) -> Expr {
     }
 }
 
+/// Converts a list to a set. Other expressions are returned unchanged.
+fn as_set(u: &Expr) -> Expr {
+    match u.kind {
+        ExprKind::List => simplify_set(u),
+        _ => u.clone(),
+    }
+}
+
 pub fn simplify_union(u: &Expr) -> Expr {
+    let u = &u.map(as_set);
     if u.operands.len() == 1 {
         u.operands[0].clone()
     } else {
@@ -53,12 +62,16 @@ fn simplify_union_recursive(l: &[Expr]) -> Vec<Expr> {
             (ExprKind::Union, ExprKind::Union) => {
                 merge_nary(&u1.operands, &u2.operands, simplify_union_recursive)
             }
-            (ExprKind::Union, _) => {
-                merge_nary(&u1.operands, &[u2.clone()], simplify_union_recursive)
-            }
-            (_, ExprKind::Union) => {
-                merge_nary(&[u1.clone()], &u2.operands, simplify_union_recursive)
-            }
+            (ExprKind::Union, _) => merge_nary(
+                &u1.operands,
+                std::slice::from_ref(u2),
+                simplify_union_recursive,
+            ),
+            (_, ExprKind::Union) => merge_nary(
+                std::slice::from_ref(u1),
+                &u2.operands,
+                simplify_union_recursive,
+            ),
             _ => unreachable!(),
         }
     } else {
@@ -73,12 +86,13 @@ fn simplify_union_recursive(l: &[Expr]) -> Vec<Expr> {
 }
 
 pub fn simplify_intersection(u: &Expr) -> Expr {
+    let u = &u.map(as_set);
     let has_empty_set = u
         .operands
         .iter()
         .find(|v| {
             if let ExprKind::Set = v.kind {
-                v.operands.len() == 0
+                v.operands.is_empty()
             } else {
                 false
             }
@@ -149,12 +163,16 @@ fn simplify_intersection_recursive(l: &[Expr]) -> Vec<Expr> {
             (ExprKind::Intersection, ExprKind::Intersection) => {
                 merge_nary(&u1.operands, &u2.operands, simplify_intersection_recursive)
             }
-            (ExprKind::Intersection, _) => {
-                merge_nary(&u1.operands, &[u2.clone()], simplify_intersection_recursive)
-            }
-            (_, ExprKind::Intersection) => {
-                merge_nary(&[u1.clone()], &u2.operands, simplify_intersection_recursive)
-            }
+            (ExprKind::Intersection, _) => merge_nary(
+                &u1.operands,
+                std::slice::from_ref(u2),
+                simplify_intersection_recursive,
+            ),
+            (_, ExprKind::Intersection) => merge_nary(
+                std::slice::from_ref(u1),
+                &u2.operands,
+                simplify_intersection_recursive,
+            ),
             _ => unreachable!(),
         }
     } else {
@@ -169,11 +187,11 @@ fn simplify_intersection_recursive(l: &[Expr]) -> Vec<Expr> {
 }
 
 pub fn simplify_set_difference(expr: &Expr) -> Expr {
-    let u = &expr.operands[0];
-    let v = &expr.operands[1];
+    let u = &as_set(&expr.operands[0]);
+    let v = &as_set(&expr.operands[1]);
     match (&u.kind, &v.kind) {
         (ExprKind::Set, ExprKind::Set) => {
-            if u.operands.len() == 0 || v.operands.len() == 0 {
+            if u.operands.is_empty() || v.operands.is_empty() {
                 u.clone()
             } else {
                 Expr {
@@ -210,7 +228,7 @@ fn set_difference_sorted(l: &[Expr], r: &[Expr]) -> Vec<Expr> {
 
 pub fn simplify_member(expr: &Expr) -> Expr {
     let u = &expr.operands[0];
-    let s = &expr.operands[1];
+    let s = &as_set(&expr.operands[1]);
 
     if let ExprKind::Set = s.kind {
         if s.num_operands() == 0 {
@@ -225,3 +243,52 @@ pub fn simplify_member(expr: &Expr) -> Expr {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::{Expr, ExprKind},
+        parser::AlgomyKernel,
+    };
+
+    use super::{simplify_member, simplify_union};
+
+    #[test]
+    fn test_list_as_set() {
+        let mut kernel = AlgomyKernel::new();
+        // Braces are ordered lists, which keep their order and duplicates
+        let u = kernel.parse_eval_line("{3, 1, 1}");
+        assert_eq!(u.kind, ExprKind::List);
+        assert_eq!(u.operands, vec![Expr::int(3), Expr::int(1), Expr::int(1)]);
+
+        // Set[...] writes a set, which is sorted and deduplicated
+        let u = kernel.parse_eval_line("Set[3, 1, x, 1]");
+        assert_eq!(
+            u,
+            Expr::set(vec![Expr::int(1), Expr::int(3), Expr::symbol("x")])
+        );
+        assert_eq!(u.kind, ExprKind::Set);
+        assert_eq!(u.to_string(), "{1, 3, x}");
+        assert_eq!(
+            kernel.parse_eval_line("Union[Set[2, 1], {3, 1}]"),
+            Expr::set(vec![Expr::int(1), Expr::int(2), Expr::int(3)])
+        );
+
+        // Set operations convert lists to sets
+        let u = simplify_union(&Expr {
+            kind: ExprKind::Union,
+            operands: vec![
+                Expr::list(vec![Expr::int(2), Expr::int(1)]),
+                Expr::list(vec![Expr::int(3), Expr::int(1)]),
+            ],
+        });
+        assert_eq!(u, Expr::set(vec![Expr::int(1), Expr::int(2), Expr::int(3)]));
+        assert_eq!(u.kind, ExprKind::Set);
+
+        let u = simplify_member(&Expr {
+            kind: ExprKind::Member,
+            operands: vec![Expr::int(2), Expr::list(vec![Expr::int(2), Expr::int(1)])],
+        });
+        assert_eq!(u, Expr::bool(true));
+    }
+}
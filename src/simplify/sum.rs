@@ -1,3 +1,5 @@
+use num::Zero;
+
 use crate::expression::{Expr, ExprKind};
 
 use super::{gaussian_number::simplify_grne, merge_nary, product::simplify_product};
@@ -28,22 +30,28 @@ pub fn simplify_sum_recursive(l: &[Expr]) -> Vec<Expr> {
         let u2 = &l[1];
         match (&u1.kind, &u2.kind) {
             (
-                ExprKind::Integer(_) | ExprKind::Fraction(_, _) | ExprKind::Gaussian,
-                ExprKind::Integer(_) | ExprKind::Fraction(_, _) | ExprKind::Gaussian,
+                ExprKind::Integer(_)
+                | ExprKind::Fraction(_, _)
+                | ExprKind::Real(_)
+                | ExprKind::Gaussian,
+                ExprKind::Integer(_)
+                | ExprKind::Fraction(_, _)
+                | ExprKind::Real(_)
+                | ExprKind::Gaussian,
             ) => {
                 // addition of constant operands in sum
                 let p = simplify_grne(&Expr::plus(u1.clone(), u2.clone()));
-                if let ExprKind::Integer(0) = p.kind {
+                if p.is_zero() {
                     vec![]
                 } else {
                     vec![p]
                 }
             }
             // a + 0 -> a
-            (ExprKind::Integer(0), _) => {
+            (ExprKind::Integer(n), _) if n.is_zero() => {
                 vec![u2.clone()]
             }
-            (_, ExprKind::Integer(0)) => {
+            (_, ExprKind::Integer(n)) if n.is_zero() => {
                 vec![u1.clone()]
             }
             _ => {
@@ -53,7 +61,7 @@ pub fn simplify_sum_recursive(l: &[Expr]) -> Vec<Expr> {
                     // a*c + b*c -> (a+b)*c
                     let s = simplify_sum(&Expr::plus(u1.product_coeff(), u2.product_coeff()));
                     let p = simplify_product(&Expr::times(s, r1));
-                    if let ExprKind::Integer(0) = p.kind {
+                    if p.is_zero() {
                         vec![]
                     } else {
                         vec![p]
@@ -75,9 +83,17 @@ pub fn simplify_sum_recursive(l: &[Expr]) -> Vec<Expr> {
                 merge_nary(&u1.operands, &u2.operands, simplify_sum_recursive)
             }
             // (a + b) + c -> a + b + c
-            [ExprKind::Sum, _] => merge_nary(&u1.operands, &[u2.clone()], simplify_sum_recursive),
+            [ExprKind::Sum, _] => merge_nary(
+                &u1.operands,
+                std::slice::from_ref(u2),
+                simplify_sum_recursive,
+            ),
             // a + (b + c) -> a + b + c
-            [_, ExprKind::Sum] => merge_nary(&[u1.clone()], &u2.operands, simplify_sum_recursive),
+            [_, ExprKind::Sum] => merge_nary(
+                std::slice::from_ref(u1),
+                &u2.operands,
+                simplify_sum_recursive,
+            ),
             _ => unreachable!(),
         }
     } else {
@@ -0,0 +1,210 @@
+use crate::{
+    expression::{Expr, ExprKind},
+    matrix::fraction_free_eliminate,
+    message::message,
+    polynomial::{cancel, polynomials_in, together, Polynomial},
+    simplify,
+};
+
+/// Solves a system of linear equations in `vars`, whose coefficients may contain other
+/// symbols, with fraction-free Gauss-Jordan elimination. Returns a list containing one list of
+/// rules `x -> value`, or the empty list if the system is inconsistent. The variables of an
+/// underdetermined system that cannot be solved for are left free, and appear on the
+/// right-hand sides of the rules. Returns `None` if the system is not linear in `vars`.
+pub fn solve_linear(eqs: &[Expr], vars: &[Expr]) -> Option<Expr> {
+    let n = vars.len();
+    // The augmented matrix [A | b] of the system A*x = b
+    let mut rows = Vec::new();
+    for eq in eqs {
+        match eq.kind {
+            ExprKind::Boolean(true) => continue,
+            ExprKind::Boolean(false) => return Some(Expr::list(vec![])),
+            _ => {}
+        }
+        // Clear the denominators, which are free of the variables in a linear equation
+        let u = together(&equation_expr(eq)).numerator().algebraic_expand();
+        let mut row: Vec<Expr> = vars.iter().map(|x| u.coefficient_gpe(x, 1)).collect();
+        if vars
+            .iter()
+            .any(|x| !u.is_polynomial_gpe(x) || u.degree_gpe(x) > 1)
+            || row.iter().any(|a| vars.iter().any(|x| !a.is_free_of(x)))
+        {
+            return None;
+        }
+        let constant = vars.iter().fold(u, |c, x| c.coefficient_gpe(x, 0));
+        row.push(simplify(&Expr::times(Expr::int(-1), constant)).algebraic_expand());
+        rows.push(row);
+    }
+    if rows.is_empty() {
+        return Some(Expr::list(vec![Expr::list(vec![])]));
+    }
+    let (matrix, pivots) = eliminate(&rows, n)?;
+    if !is_consistent(&matrix, &pivots, n) {
+        return Some(Expr::list(vec![]));
+    }
+
+    // Every pivot row reads d*x_p + sum(a_j*x_j) = b, where the x_j are the free variables
+    let rules = pivots
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| {
+            let row = &matrix[i];
+            let mut value = vec![row[n].to_expr()];
+            for j in (0..n).filter(|j| !pivots.contains(j)) {
+                value.push(Expr::product(vec![
+                    Expr::int(-1),
+                    row[j].to_expr(),
+                    vars[j].clone(),
+                ]));
+            }
+            let value = simplify(&Expr::quotient(Expr::sum(value), row[p].to_expr()));
+            Expr::rule(vars[p].clone(), cancel(&value))
+        })
+        .collect();
+    Some(Expr::list(vec![Expr::list(rules)]))
+}
+
+/// Solves the matrix equation `m . x == b` for the vector `x`. If the system is
+/// underdetermined, the components of `x` without a pivot are set to zero. Returns `None` if
+/// the dimensions do not match or an entry has a coefficient which is not rational, or if the
+/// system is inconsistent, which emits `LinearSolve::nosol`.
+pub fn linear_solve(m: &[Vec<Expr>], b: &[Expr]) -> Option<Vec<Expr>> {
+    let n = m.first()?.len();
+    if m.len() != b.len() {
+        return None;
+    }
+    let rows: Vec<Vec<Expr>> = m
+        .iter()
+        .zip(b)
+        .map(|(row, c)| row.iter().chain(std::iter::once(c)).cloned().collect())
+        .collect();
+    let (matrix, pivots) = eliminate(&rows, n)?;
+    if !is_consistent(&matrix, &pivots, n) {
+        message(
+            "LinearSolve",
+            "nosol",
+            "Linear equation encountered that has no solution.",
+        );
+        return None;
+    }
+    let mut x = vec![Expr::int(0); n];
+    for (i, &p) in pivots.iter().enumerate() {
+        let value = simplify(&Expr::quotient(
+            matrix[i][n].to_expr(),
+            matrix[i][p].to_expr(),
+        ));
+        x[p] = cancel(&value);
+    }
+    Some(x)
+}
+
+/// Brings the augmented matrix `[A | b]` of a system with `n` unknowns into reduced row
+/// echelon form with fraction-free elimination, and returns it with its pivot columns.
+/// Returns `None` if an entry has a coefficient which is not rational.
+fn eliminate(rows: &[Vec<Expr>], n: usize) -> Option<(Vec<Vec<Polynomial>>, Vec<usize>)> {
+    let entries: Vec<Expr> = rows.iter().flatten().cloned().collect();
+    let entries = polynomials_in(&entries, &[])?;
+    let mut matrix: Vec<Vec<Polynomial>> = entries.chunks(n + 1).map(|r| r.to_vec()).collect();
+    let pivots = fraction_free_eliminate(&mut matrix, n);
+    Some((matrix, pivots))
+}
+
+/// Checks that the rows without a pivot of an eliminated augmented matrix read `0 = 0`.
+fn is_consistent(matrix: &[Vec<Polynomial>], pivots: &[usize], n: usize) -> bool {
+    matrix[pivots.len()..].iter().all(|row| row[n].is_zero())
+}
+
+/// Writes the equation `lhs == rhs` as the expression `lhs - rhs`, which vanishes for its
+/// solutions. Any other expression `u` stands for the equation `u == 0`.
+pub fn equation_expr(eq: &Expr) -> Expr {
+    match eq.kind {
+        ExprKind::Equal => simplify(&Expr::minus(eq.operands[0].clone(), eq.operands[1].clone())),
+        _ => eq.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{expression::Expr, parser::AlgomyKernel};
+
+    use super::{linear_solve, solve_linear};
+
+    #[test]
+    fn test_solve_linear() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            (
+                "{x + y == 3, x - y == 1}",
+                "{x, y}",
+                Some("{{x -> 2, y -> 1}}"),
+            ),
+            (
+                "{2*x + 3*y == 1, 4*x + 9*y == 5}",
+                "{x, y}",
+                Some("{{x -> -1, y -> 1}}"),
+            ),
+            (
+                "{x/2 + y/3 == 1, x - y == 0}",
+                "{x, y}",
+                Some("{{x -> 6/5, y -> 6/5}}"),
+            ),
+            (
+                "{x + y + z == 6, x - y + z == 2, 2*x + y - z == 1}",
+                "{x, y, z}",
+                Some("{{x -> 1, y -> 2, z -> 3}}"),
+            ),
+            (
+                "{a*x + b*y == e, c*x + d*y == f}",
+                "{x, y}",
+                Some("{{x -> -(b*f - d*e)/(a*d - b*c), y -> (a*f - c*e)/(a*d - b*c)}}"),
+            ),
+            ("{x + y == 1, 2*x + 2*y == 3}", "{x, y}", Some("{}")),
+            (
+                "{x + y == 1, 2*x + 2*y == 2}",
+                "{x, y}",
+                Some("{{x -> 1 - y}}"),
+            ),
+            ("{x + y + z == 1}", "{x, y, z}", Some("{{x -> 1 - y - z}}")),
+            ("{x - 2*y, y == a}", "{x, y}", Some("{{x -> 2*a, y -> a}}")),
+            (
+                "{x + y == 2, x - y == 0, 2*x == 2}",
+                "{x, y}",
+                Some("{{x -> 1, y -> 1}}"),
+            ),
+            ("{x == 1, y == y}", "{x, y}", Some("{{x -> 1}}")),
+            ("{x == 1, 1 == 2}", "{x}", Some("{}")),
+            ("{x*y == 1, x + y == 2}", "{x, y}", None),
+            ("{x^2 == 1}", "{x}", None),
+        ];
+        for (eqs, vars, expected) in cases {
+            let eqs = kernel.parse_eval_line(eqs);
+            let vars = kernel.parse_eval_line(vars);
+            assert_eq!(
+                solve_linear(&eqs.operands, &vars.operands),
+                expected.map(|e| kernel.parse_eval_line(e)),
+                "{}",
+                eqs
+            );
+        }
+    }
+
+    #[test]
+    fn test_linear_solve() {
+        let mut kernel = AlgomyKernel::new();
+        let cases = [
+            ("{{1, 2}, {3, 4}}", "{5, 6}", Some("{-4, 9/2}")),
+            ("{{1, 1}, {2, 2}}", "{1, 2}", Some("{1, 0}")),
+            ("{{a, 0}, {0, b}}", "{1, 1}", Some("{1/a, 1/b}")),
+            ("{{1, 1}, {2, 2}}", "{1, 3}", None),
+            ("{{1, 2}, {3, 4}}", "{1, 2, 3}", None),
+        ];
+        for (m, b, expected) in cases {
+            let m = kernel.parse_eval_line(m).as_matrix().unwrap();
+            let b = kernel.parse_eval_line(b).as_vector().unwrap();
+            let expected = expected.map(|e| kernel.parse_eval_line(e).operands);
+            assert_eq!(linear_solve(&m, &b), expected, "{:?}", m);
+        }
+        let x = kernel.parse_eval_line("LinearSolve[{{2, 1}, {1, 3}}, {3, 5}]");
+        assert_eq!(x, Expr::list(vec![Expr::frac(4, 5), Expr::frac(7, 5)]));
+    }
+}
@@ -0,0 +1,5 @@
+mod linear;
+mod polynomial;
+
+pub use linear::*;
+pub use polynomial::*;
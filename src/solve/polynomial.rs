@@ -0,0 +1,266 @@
+use num::{BigInt, BigRational, Integer, One, Signed};
+
+use crate::{
+    expression::{Expr, ExprKind},
+    polynomial::{
+        factor, polynomial_division, polynomial_quotient, rational_coefficients, rational_roots,
+    },
+    simplify,
+};
+
+/// Largest factor tried when extracting squares from a radicand.
+const TRIAL_DIVISION_LIMIT: u64 = 1_000_000;
+
+/// Solves the polynomial equation `u = 0` for `x` and returns the set of solutions.
+/// Rational roots are found for any degree, other factors are solved exactly if they have
+/// degree at most two. Returns `None` if `u` is not a polynomial in `x`, if it vanishes
+/// identically, or if not all solutions can be found. An equation which simplified to
+/// `True` holds for all `x` and is also left unsolved, while `False` has no solutions.
+pub fn solve_polynomial(u: &Expr, x: &Expr) -> Option<Expr> {
+    match u.kind {
+        ExprKind::Boolean(true) => return None,
+        ExprKind::Boolean(false) => return Some(Expr::set(Vec::new())),
+        _ => {}
+    }
+    let u = u.algebraic_expand();
+    if !u.is_polynomial_gpe(x) || u.is_zero() {
+        return None;
+    }
+    let mut solutions = Vec::new();
+    match rational_coefficients(&u, x) {
+        Some(coeffs) => {
+            // Divide out the rational roots, and factor the rest over the integers
+            let mut rest = u.clone();
+            for (r, multiplicity) in rational_roots(&coeffs) {
+                let r = Expr::from_rational(r);
+                for _ in 0..multiplicity {
+                    rest = polynomial_quotient(
+                        &rest,
+                        &simplify(&Expr::minus(x.clone(), r.clone())),
+                        x,
+                    );
+                }
+                solutions.push(r);
+            }
+            if rest.degree_gpe(x) > 0 {
+                let factored = factor(&rest, x);
+                let factors = match factored.kind {
+                    ExprKind::Product => factored.operands.clone(),
+                    _ => vec![factored],
+                };
+                for f in factors.iter().filter(|f| !f.is_free_of(x)) {
+                    solutions.extend(solve_quadratic(&f.base().algebraic_expand(), x)?);
+                }
+            }
+        }
+        None if is_gaussian_polynomial(&u, x) => solutions = solve_gaussian(&u, x)?,
+        None => solutions = solve_quadratic(&u, x)?,
+    }
+    Some(Expr::set(solutions))
+}
+
+/// Returns the distinct roots of a polynomial in `x` with their multiplicities, in the order
+/// of [`solve_polynomial`]. Returns `None` if not all roots can be found.
+pub fn polynomial_roots(u: &Expr, x: &Expr) -> Option<Vec<(Expr, usize)>> {
+    let u = u.algebraic_expand();
+    let roots = solve_polynomial(&u, x)?;
+    let mut rest = u.clone();
+    let mut result = Vec::new();
+    for r in roots.operands {
+        let linear = simplify(&Expr::minus(x.clone(), r.clone()));
+        let mut multiplicity = 0;
+        loop {
+            let (q, remainder) = polynomial_division(&rest, &linear, x);
+            if !remainder.algebraic_expand().is_zero() {
+                break;
+            }
+            rest = q.algebraic_expand();
+            multiplicity += 1;
+        }
+        result.push((r, multiplicity.max(1)));
+    }
+    let count: usize = result.iter().map(|(_, m)| m).sum();
+    (count as i64 == u.degree_gpe(x)).then_some(result)
+}
+
+/// Checks whether `u` is a polynomial in `x` whose coefficients are Gaussian rational numbers.
+fn is_gaussian_polynomial(u: &Expr, x: &Expr) -> bool {
+    u.is_polynomial_gpe(x) && (0..=u.degree_gpe(x)).all(|k| u.coefficient_gpe(x, k).is_grne())
+}
+
+/// Solves a polynomial equation with Gaussian rational coefficients. The Gaussian rational
+/// roots of `u` are among the roots of `u * conj(u)`, which has rational coefficients. The
+/// remaining factor is solved if it has degree at most two.
+fn solve_gaussian(u: &Expr, x: &Expr) -> Option<Vec<Expr>> {
+    let conjugate = simplify(&Expr::sum(
+        (0..=u.degree_gpe(x))
+            .map(|k| {
+                let c = u.coefficient_gpe(x, k);
+                let c = Expr::gaussian(c.re(), simplify(&Expr::times(Expr::int(-1), c.im())));
+                Expr::times(c, Expr::power(x.clone(), Expr::int(k)))
+            })
+            .collect(),
+    ));
+    let norm = simplify(&Expr::times(u.clone(), conjugate)).algebraic_expand();
+    let candidates = solve_polynomial(&norm, x)?;
+
+    let mut solutions = Vec::new();
+    let mut rest = u.clone();
+    for r in candidates.operands.into_iter().filter(|r| r.is_grne()) {
+        // Divide out every factor x - r of a repeated root
+        let linear = simplify(&Expr::minus(x.clone(), r.clone()));
+        let mut is_root = false;
+        loop {
+            let (q, remainder) = polynomial_division(&rest, &linear, x);
+            if !remainder.algebraic_expand().is_zero() {
+                break;
+            }
+            rest = q.algebraic_expand();
+            is_root = true;
+        }
+        if is_root {
+            solutions.push(r);
+        }
+    }
+    if rest.degree_gpe(x) > 0 {
+        solutions.extend(solve_quadratic(&rest, x)?);
+    }
+    Some(solutions)
+}
+
+/// Solves `a*x^2 + b*x + c = 0` or `b*x + c = 0`, where the coefficients may be symbolic.
+fn solve_quadratic(u: &Expr, x: &Expr) -> Option<Vec<Expr>> {
+    let a = u.coefficient_gpe(x, 2);
+    let b = u.coefficient_gpe(x, 1);
+    let c = u.coefficient_gpe(x, 0);
+    match u.degree_gpe(x) {
+        0 => Some(Vec::new()),
+        1 => Some(vec![simplify(&Expr::quotient(
+            Expr::times(Expr::int(-1), c),
+            b,
+        ))]),
+        2 if a.is_rne() && b.is_rne() && c.is_rne() => {
+            let (a, b, c) = (a.rational_rne(), b.rational_rne(), c.rational_rne());
+            let two_a = &a + &a;
+            let d = &b * &b - BigRational::from_integer(4.into()) * &a * &c;
+            // x = -b/(2a) +- k/(2a) * Sqrt[m]
+            let center = Expr::from_rational(-b / &two_a);
+            let (k, m) = sqrt_rational(&d.abs());
+            let mut root = vec![
+                Expr::from_rational(k / two_a),
+                Expr::power(Expr::int(m), Expr::frac(1, 2)),
+            ];
+            if d.is_negative() {
+                root.push(Expr::gaussian(Expr::int(0), Expr::int(1)));
+            }
+            let root = simplify(&Expr::product(root));
+            Some(
+                [-1, 1]
+                    .iter()
+                    .map(|sign| {
+                        simplify(&Expr::plus(
+                            center.clone(),
+                            Expr::times(Expr::int(*sign), root.clone()),
+                        ))
+                    })
+                    .collect(),
+            )
+        }
+        2 => {
+            let d = simplify(&Expr::minus(
+                Expr::power(b.clone(), Expr::int(2)),
+                Expr::product(vec![Expr::int(4), a.clone(), c]),
+            ))
+            .algebraic_expand();
+            let root = simplify(&Expr::power(d, Expr::frac(1, 2)));
+            let solution = |sign: i64| {
+                simplify(&Expr::quotient(
+                    Expr::plus(
+                        Expr::times(Expr::int(-1), b.clone()),
+                        Expr::times(Expr::int(sign), root.clone()),
+                    ),
+                    Expr::times(Expr::int(2), a.clone()),
+                ))
+                .algebraic_expand()
+            };
+            Some(vec![solution(-1), solution(1)])
+        }
+        _ => None,
+    }
+}
+
+/// Writes the square root of a non-negative rational number as `k * m^(1/2)`, where `k` is
+/// rational and `m` is a square-free integer. Returns `(k, m)`.
+fn sqrt_rational(r: &BigRational) -> (BigRational, BigInt) {
+    // Sqrt[p/q] = Sqrt[p*q]/q
+    let mut m = r.numer() * r.denom();
+    let mut k = BigInt::one();
+    let mut p = BigInt::from(2);
+    while &p * &p <= m && p <= BigInt::from(TRIAL_DIVISION_LIMIT) {
+        let square = &p * &p;
+        while m.is_multiple_of(&square) {
+            m /= &square;
+            k *= &p;
+        }
+        p += 1;
+    }
+    (BigRational::new(k, r.denom().clone()), m)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::{Expr, ExprKind},
+        parser::AlgomyKernel,
+    };
+
+    use super::solve_polynomial;
+
+    #[test]
+    fn test_solve_polynomial() {
+        let mut kernel = AlgomyKernel::new();
+        let x = Expr::symbol("x");
+        let cases = [
+            ("2*x - 3", Some("{3/2}")),
+            ("x^2 - 5*x + 6", Some("{2, 3}")),
+            ("x^2 - 2", Some("{-Sqrt[2], Sqrt[2]}")),
+            ("x^2 + 4", Some("{-2*I, 2*I}")),
+            (
+                "x^2 + x + 1",
+                Some("{-1/2 - 1/2*I*Sqrt[3], -1/2 + 1/2*I*Sqrt[3]}"),
+            ),
+            ("x^2 - 8", Some("{-(2^(3/2)), 2^(3/2)}")),
+            ("(x - 1)^3*(x + 2)", Some("{-2, 1}")),
+            ("x^4 - 1", Some("{-1, 1, -I, I}")),
+            ("x^5 - 3*x^3 + 2*x", Some("{0, -1, 1, -Sqrt[2], Sqrt[2]}")),
+            ("6*x^3 - 11*x^2 + 6*x - 1", Some("{1/3, 1/2, 1}")),
+            ("a*x + b", Some("{-b/a}")),
+            (
+                "x^2 + b*x + c",
+                Some("{-1/2*b - 1/2*(b^2 - 4*c)^(1/2), -1/2*b + 1/2*(b^2 - 4*c)^(1/2)}"),
+            ),
+            ("3", Some("{}")),
+            ("x^2 - (2 + I)*x + 2*I", Some("{2, I}")),
+            ("x^3 - I*x^2 + x - I", Some("{-I, I}")),
+            // Repeated Gaussian roots
+            ("(x - I)^4", Some("{I}")),
+            ("(x - I)^2*(x + 2*I)", Some("{-2*I, I}")),
+            ("1 == 2", Some("{}")),
+            ("x^5 - x + 1", None),
+            ("Sin[x]", None),
+            ("0", None),
+            // Identities hold for all x
+            ("True", None),
+            ("x + 1 == x + 1", None),
+        ];
+        for (u, expected) in cases {
+            let u = kernel.parse_eval_line(u);
+            let expected = expected.map(|e| Expr::set(kernel.parse_eval_line(e).operands));
+            assert_eq!(solve_polynomial(&u, &x), expected, "{}", u);
+        }
+        assert_eq!(
+            kernel.parse_eval_line("Solve[x^2 == 4, x]").kind,
+            ExprKind::Set
+        );
+    }
+}